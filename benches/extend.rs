@@ -0,0 +1,81 @@
+//! Host benchmark: bulk `extend` versus byte-by-byte `append` for 1 KB writes
+//!
+//! Run with:
+//!
+//! ```bash
+//! cargo bench --bench extend
+//! ```
+//!
+//! Uses only `std::time`, so it needs no extra dependencies.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use tinyalloc::prelude::*;
+
+const ITERS: u32 = 2_000;
+const PAYLOAD: usize = 1024;
+
+// 64 slots of 32 bytes: 1920 usable bytes, enough for a 1 KB payload
+type Arena = TinySlabAllocator<2048, 64>;
+
+fn bench(name: &str, mut f: impl FnMut(&mut Arena, &[u8])) -> Duration {
+    let mut arena = Arena::new();
+    let mut payload = [0u8; PAYLOAD];
+    for (i, b) in payload.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERS {
+        f(&mut arena, black_box(&payload));
+    }
+    let per_iter = start.elapsed() / ITERS;
+    println!("{name:<28} {per_iter:>10.2?} per iteration");
+    per_iter
+}
+
+fn main() {
+    println!("=== ByteBuffer bulk write benchmark ({ITERS} x {PAYLOAD} bytes) ===\n");
+
+    let per_byte = bench("append x 1 KB (per byte)", |arena, data| {
+        let mut buf = ByteBuffer::new();
+        let mut w = buf.write(arena);
+        for &b in data {
+            w.append(b).unwrap();
+        }
+        w.clear();
+    });
+
+    let bulk = bench("extend 1 KB (chunk-wise)", |arena, data| {
+        let mut buf = ByteBuffer::new();
+        let mut w = buf.write(arena);
+        w.extend(data).unwrap();
+        w.clear();
+    });
+
+    let copy = bench("copy_from (512 B)", |arena, data| {
+        let mut src = ByteBuffer::new();
+        src.write(arena).extend(&data[..PAYLOAD / 2]).unwrap();
+        let mut dst = ByteBuffer::new();
+        dst.copy_from(arena, &src, 0..PAYLOAD / 2).unwrap();
+        black_box(dst.len());
+        src.write(arena).clear();
+        dst.write(arena).clear();
+    });
+
+    let split = bench("split_off_front (512 B)", |arena, data| {
+        let mut buf = ByteBuffer::new();
+        buf.write(arena).extend(&data[..PAYLOAD / 2]).unwrap();
+        let mut front = buf.split_off_front(arena, PAYLOAD / 4).unwrap();
+        black_box(front.len());
+        front.write(arena).clear();
+        buf.write(arena).clear();
+    });
+
+    println!();
+    println!(
+        "extend speedup over per-byte append: {:.1}x",
+        per_byte.as_secs_f64() / bulk.as_secs_f64()
+    );
+    black_box((copy, split));
+}
@@ -28,10 +28,22 @@
 //! ## Features
 //!
 //! - `tinyslab` (default) - Slab allocator with generation tracking
+//! - `tinyslab-large-blocks` - Slab blocks up to 65535 bytes (4 bytes of metadata per slot instead of 3)
 //! - `bytebuffer` (default) - Dynamic byte buffer backed by allocators
 //! - `bytebuffer-deque` (default) - Double-ended queue for stream processing
+//! - `bytebuffer-len-u32` - Widen ByteBuffer lengths from `u16` to `u32`
+//! - `checksum-tables` - Table-driven CRCs (faster, 256 entries per CRC in flash)
 //! - `global-alloc` (default) - Single-threaded global allocator
 //! - `global-alloc-custom` - Custom-sized global allocators
+//! - `deque-async` - Async `GlobalDeque` consumers woken by `push`
+//! - `nominput` (default) - nom `Input` for ByteBuffers in the global allocator
+//!
+//! `ByteBuffer::take_from` and `ByteBuffer::take_split` need both
+//! `bytebuffer-deque` and `global-alloc`, since they resolve chunks through
+//! the global allocator. Without them, use `ByteBuffer::view_from` with the
+//! buffer's arena (or `ByteBuffer::slice`) instead.
+//! - `embedded-io` - `embedded_io` `Read`/`Write`/`BufRead` for ByteBuffer, `Read`/`Write` for `GlobalDeque`, `BufRead` via `GlobalDeque::reader`
+//! - `std` - `std::io` `Read`/`Write`/`BufRead` for ByteBuffer, `Read`/`Write` for `GlobalDeque`, `BufRead` via `GlobalDeque::reader` (host tools, tests)
 //!
 //! ## Architecture
 //!
@@ -160,10 +172,12 @@
 //! ## no_std Support
 //!
 //! TinyAlloc is fully `no_std` compatible:
-//! - Zero dependencies
+//! - No required dependencies; `nom` and `embedded-io` are optional, pulled
+//!   in by the `nominput` and `embedded-io` features
 //! - No heap allocation required
 //! - Works on bare metal
 //! - Supports embedded platforms
+//! - The optional `std` feature only adds `std::io` adapters for host-side use
 //!
 //! ## Testing
 //!
@@ -181,6 +195,9 @@
 #![warn(missing_docs)]
 #![doc(html_root_url = "https://docs.rs/tinyalloc/0.1.0")]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 // Internal modules - use prelude for public API
 mod backend;
 mod utils;
@@ -208,14 +225,15 @@ pub fn version() -> &'static str {
     "0.1.0"
 }
 
-/// Defines bit widths for packing metadata into a u16
+/// Defines bit widths for packing metadata into a chunk header
 ///
 /// This layout is determined by the allocator based on its constraints:
 /// - `slot_bits`: Bits needed to represent slot indices (log2 of max slots)
 /// - `gen_bits`: Bits for generation counter (typically 4-8 bits)
 /// - `len_bits`: Bits for data length (remaining bits after slot + gen)
 ///
-/// Total: slot_bits + gen_bits + len_bits = 16 bits
+/// Total: slot_bits + gen_bits + len_bits <= 32 bits, packed into a 2-byte
+/// header when they fit in 16 bits and a 4-byte header otherwise
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct BitLayout {
     /// Number of bits for slot index
@@ -226,6 +244,17 @@ pub struct BitLayout {
     pub len_bits: u8,
 }
 
+impl BitLayout {
+    /// Bytes taken by a header packing this layout (2 or 4)
+    pub const fn header_len(&self) -> usize {
+        if self.slot_bits + self.gen_bits + self.len_bits <= 16 {
+            2
+        } else {
+            4
+        }
+    }
+}
+
 /// A handle for accessing allocated memory with generation tracking
 ///
 /// Handles combine a slot index with a generation counter to prevent use-after-free.
@@ -339,6 +368,17 @@ pub trait Allocator {
     fn block_size(&self) -> usize;
     /// Returns the bit layout used for metadata packing
     fn bit_layout(&self) -> BitLayout;
+    /// Rebuilds a full handle from a link stored in packed metadata
+    ///
+    /// Packed links keep only the low `bit_layout().gen_bits` bits of the
+    /// generation. Returns the live handle for `link.slot` if its generation
+    /// matches those bits. The default returns `link` unchanged, which is
+    /// only correct when `gen_bits` is 8: allocators reporting fewer bits
+    /// must override it, or links stop resolving once a generation wraps
+    /// past the stored bits.
+    fn resolve_link(&self, link: Handle) -> Option<Handle> {
+        Some(link)
+    }
     /// Allocates memory and copies data into it
     ///
     /// This is a convenience method that combines `alloc_uninit` with `copy_from_slice`.
@@ -2,17 +2,27 @@ use crate::{Allocator, Handle};
 
 const NONE_SLOT: u8 = u8::MAX;
 
+/// Allocated size of a slot: caps the block size at 255 bytes
+#[cfg(not(feature = "tinyslab-large-blocks"))]
+type SlotSize = u8;
+/// Allocated size of a slot: widened with `tinyslab-large-blocks`
+#[cfg(feature = "tinyslab-large-blocks")]
+type SlotSize = u16;
+
 /// Metadata for each slot in the slab allocator
 ///
-/// Stores allocation state using compact u8 fields:
+/// Stores allocation state using compact fields:
+/// - size: Actual allocated bytes (max 255, or 65535 with `tinyslab-large-blocks`)
 /// - generation: Incremented on free to invalidate handles
-/// - size: Actual allocated bytes (max 255)
 /// - next_free: Index of next free slot in free list
+///
+/// `size` comes first so a `u16` size packs with the two `u8` fields into
+/// 4 bytes without padding.
 #[derive(Clone, Copy)]
 #[repr(C)]
 struct SlotMeta {
+    size: SlotSize, // actual allocated size in bytes
     generation: u8,
-    size: u8,      // actual allocated size in bytes (max 255)
     next_free: u8, // next free slot index (for free list, max 255 slots)
 }
 
@@ -28,7 +38,8 @@ struct SlotMeta {
 ///
 /// # Block Size
 ///
-/// Block size = `CAPACITY / SLOTS`
+/// Block size = `CAPACITY / SLOTS`, at most 255 bytes (65535 with the
+/// `tinyslab-large-blocks` feature)
 /// - Larger blocks = fewer allocations, less waste for large objects
 /// - Smaller blocks = more allocations, less waste for small objects
 ///
@@ -37,6 +48,8 @@ struct SlotMeta {
 /// Each block: `[metadata: 2 bytes][data: block_size - 2 bytes]`
 /// - Metadata is bit-packed: `[generation | length | next_slot]`
 /// - Actual usable space per block = `block_size - 2`
+/// - Large blocks whose slot and length bits overflow 16 bits take 4 bytes of
+///   metadata, leaving `block_size - 4` for data (see [`BitLayout::header_len`](crate::BitLayout::header_len))
 ///
 /// # Examples
 ///
@@ -73,6 +86,16 @@ struct SlotMeta {
 /// let alloc = TinySlabAllocator::<8192, 256>::new(); // SLOTS > 255
 /// ```
 ///
+/// Blocks too large for the slot size are rejected too:
+///
+/// ```compile_fail
+/// use tinyalloc::prelude::*;
+/// # #[cfg(not(feature = "tinyslab-large-blocks"))]
+/// let alloc = TinySlabAllocator::<{ 4 * 512 }, 4>::new(); // 512-byte blocks
+/// # #[cfg(feature = "tinyslab-large-blocks")]
+/// # let alloc = TinySlabAllocator::<{ 4 * 65536 }, 4>::new();
+/// ```
+///
 /// # Performance
 ///
 /// | Operation | Time Complexity | Space |
@@ -81,7 +104,7 @@ struct SlotMeta {
 /// | alloc() | O(1) | Free list |
 /// | free() | O(1) | Free list |
 /// | get() | O(1) | Array index |
-/// | Metadata | O(1) | 3 bytes/slot |
+/// | Metadata | O(1) | 3 bytes/slot (4 with `tinyslab-large-blocks`) |
 pub struct TinySlabAllocator<const CAPACITY: usize, const SLOTS: usize> {
     memory: [u8; CAPACITY],
     slots: [SlotMeta; SLOTS],
@@ -92,6 +115,29 @@ pub struct TinySlabAllocator<const CAPACITY: usize, const SLOTS: usize> {
 impl<const CAPACITY: usize, const SLOTS: usize> TinySlabAllocator<CAPACITY, SLOTS> {
     const BLOCK_SIZE: usize = CAPACITY / SLOTS;
 
+    // Metadata bit widths used by chunk headers (see `bit_layout`)
+    const SLOT_BITS: u8 = Self::bits_needed(SLOTS);
+    // Chunk headers keep 4 generation bits. Slots track the full u8
+    // generation; only links read back from headers are matched on these
+    // low bits.
+    const GEN_BITS: u8 = 4;
+    // Sized for the data a 2-byte header leaves, so the header width derived
+    // from the bit sum (`BitLayout::header_len`) always matches HEADER_BITS
+    const LEN_BITS: u8 = Self::bits_needed(Self::BLOCK_SIZE.saturating_sub(2));
+    // Headers take 2 bytes unless slot + len + generation bits overflow them
+    const HEADER_BITS: u8 = if Self::SLOT_BITS + Self::LEN_BITS + Self::GEN_BITS <= 16 {
+        16
+    } else {
+        32
+    };
+    const GEN_MASK: u8 = ((1u16 << Self::GEN_BITS) - 1) as u8;
+
+    // Compile-time assertion: a block's size must fit in its slot metadata
+    const _ASSERT_BLOCK_FITS_SIZE: () = assert!(
+        Self::BLOCK_SIZE <= SlotSize::MAX as usize,
+        "CAPACITY / SLOTS exceeds the largest block size (255, or 65535 with tinyslab-large-blocks)"
+    );
+
     // Compile-time assertion: SLOTS must fit in u8 (max 255)
     const _ASSERT_SLOTS_FITS_U8: () = assert!(
         SLOTS <= u8::MAX as usize,
@@ -130,6 +176,7 @@ impl<const CAPACITY: usize, const SLOTS: usize> TinySlabAllocator<CAPACITY, SLOT
     pub const fn new() -> Self {
         // Force evaluation of compile-time assertion by referencing it
         let _: () = Self::_ASSERT_SLOTS_FITS_U8;
+        let _: () = Self::_ASSERT_BLOCK_FITS_SIZE;
 
         Self {
             memory: [0u8; CAPACITY],
@@ -197,7 +244,7 @@ impl<const CAPACITY: usize, const SLOTS: usize> Allocator for TinySlabAllocator<
         self.free_head = meta.next_free;
 
         // Update metadata
-        meta.size = len as u8;
+        meta.size = len as SlotSize;
         meta.next_free = NONE_SLOT;
 
         let generation = meta.generation;
@@ -300,18 +347,14 @@ impl<const CAPACITY: usize, const SLOTS: usize> Allocator for TinySlabAllocator<
         // Calculate optimal bit allocation for metadata packing
         // We need to account for a sentinel value for "none" marker
         // So for SLOTS slots (0..SLOTS-1), we need SLOTS as the sentinel
-        let max_len = Self::BLOCK_SIZE.saturating_sub(2);
-        let max_gen = 15; // 4 bits for generation (0-15)
-
-        // Need enough bits to represent SLOTS (as sentinel), not SLOTS-1
-        let slot_bits = Self::bits_needed(SLOTS);
-        let gen_bits = Self::bits_needed(max_gen);
-        let len_bits = Self::bits_needed(max_len);
+        let slot_bits = Self::SLOT_BITS;
+        let gen_bits = Self::GEN_BITS;
+        let len_bits = Self::LEN_BITS;
 
         // Verify total fits in u16
         debug_assert!(
-            slot_bits + gen_bits + len_bits <= 16,
-            "Bit layout exceeds u16 capacity"
+            slot_bits + gen_bits + len_bits <= Self::HEADER_BITS,
+            "Bit layout exceeds chunk header capacity"
         );
 
         crate::BitLayout {
@@ -321,6 +364,17 @@ impl<const CAPACITY: usize, const SLOTS: usize> Allocator for TinySlabAllocator<
         }
     }
 
+    #[inline]
+    fn resolve_link(&self, link: Handle) -> Option<Handle> {
+        let meta = self.slots.get(link.slot as usize)?;
+        // Headers only store the low GEN_BITS of the generation
+        if (meta.generation ^ link.generation) & Self::GEN_MASK != 0 || meta.next_free != NONE_SLOT
+        {
+            return None;
+        }
+        Some(Handle::new(link.slot, meta.generation))
+    }
+
     fn clear(&mut self) {
         // Reset all metadata
         for slot in &mut self.slots {
@@ -523,4 +577,81 @@ mod tests {
         // Total should be 14 bits, leaving 2 bits unused
         assert_eq!(layout.slot_bits + layout.gen_bits + layout.len_bits, 14);
     }
+
+    #[test]
+    fn test_bit_layout_widens_header() {
+        // 128 slots need 8 slot bits and 30-byte blocks 5 len bits, leaving
+        // only 3 generation bits in 2 bytes, so the header takes 4
+        let mut alloc: TinySlabAllocator<4096, 128> = TinySlabAllocator::new();
+        let layout = alloc.bit_layout();
+        assert_eq!(layout.header_len(), 4);
+        assert_eq!(layout.gen_bits, 4);
+
+        // The slot keeps counting past the header's gen bits
+        let (h, _) = alloc.alloc_uninit(4).unwrap();
+        let mut last = h;
+        for _ in 0..17 {
+            let stale = last;
+            alloc.free(last);
+            last = alloc.alloc_uninit(4).unwrap().0;
+            assert!(alloc.get(stale).is_none());
+        }
+        assert_eq!(last.slot, h.slot);
+        assert_eq!(last.generation, 17);
+
+        // Caller handles are checked on the full generation
+        let from_header = Handle::new(last.slot, last.generation & 0b1111);
+        assert!(alloc.get(from_header).is_none());
+
+        // A link read back from a chunk header resolves on the low gen bits
+        assert_eq!(alloc.resolve_link(from_header), Some(last));
+        assert_eq!(alloc.resolve_link(Handle::new(last.slot, 0)), None);
+    }
+
+    #[test]
+    fn test_full_header_keeps_gen_bits() {
+        // 255 slots (8 bits) and 128-byte chunks (8 len bits) would fill a
+        // 2-byte header; the wider header keeps stale links detectable
+        let mut alloc: TinySlabAllocator<{ 255 * 130 }, 255> = TinySlabAllocator::new();
+        let layout = alloc.bit_layout();
+        assert_eq!(layout.header_len(), 4);
+        assert_eq!(layout.gen_bits, 4);
+
+        let (old, _) = alloc.alloc_uninit(8).unwrap();
+        alloc.free(old);
+        let (new, _) = alloc.alloc_uninit(8).unwrap();
+        assert_eq!(new.slot, old.slot);
+        assert!(alloc.get(old).is_none());
+        assert_eq!(alloc.resolve_link(old), None);
+        assert_eq!(alloc.resolve_link(new), Some(new));
+    }
+
+    #[test]
+    fn test_slot_meta_size() {
+        #[cfg(not(feature = "tinyslab-large-blocks"))]
+        assert_eq!(core::mem::size_of::<SlotMeta>(), 3);
+        #[cfg(feature = "tinyslab-large-blocks")]
+        assert_eq!(core::mem::size_of::<SlotMeta>(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "tinyslab-large-blocks")]
+    fn test_large_block_layout() {
+        // 2 slots of 1 KiB still pack into a 2-byte header
+        let alloc: TinySlabAllocator<2048, 2> = TinySlabAllocator::new();
+        assert_eq!(alloc.block_size(), 1024);
+        assert_eq!(alloc.bit_layout().header_len(), 2);
+
+        // 100 slots (7 bits) and 1020-byte chunks (10 len bits) need 4 bytes
+        let mut alloc: TinySlabAllocator<{ 100 * 1024 }, 100> = TinySlabAllocator::new();
+        let layout = alloc.bit_layout();
+        assert_eq!(layout.header_len(), 4);
+        assert_eq!(
+            (layout.slot_bits, layout.gen_bits, layout.len_bits),
+            (7, 4, 10)
+        );
+        let (h, buf) = alloc.alloc_uninit(1024).unwrap();
+        assert_eq!(buf.len(), 1024);
+        assert_eq!(alloc.get(h).unwrap().len(), 1024);
+    }
 }
@@ -244,6 +244,15 @@ impl Allocator for GlobalAllocatorRef {
         }
     }
 
+    fn resolve_link(&self, link: Handle) -> Option<Handle> {
+        unsafe {
+            (*GLOBAL.allocator_ptr.get())
+                .as_deref()
+                .unwrap()
+                .resolve_link(link)
+        }
+    }
+
     fn clear(&mut self) {
         unsafe {
             (*GLOBAL.allocator_ptr.get())
@@ -13,13 +13,48 @@ pub use crate::Handle;
 pub use crate::backend::tinyslab::TinySlabAllocator;
 
 #[cfg(feature = "bytebuffer")]
-pub use crate::utils::bytebuffer::{ByteBuffer, ByteBufferError};
+pub use crate::utils::bytebuffer::{
+    BufLen, ByteBuffer, ByteBufferError, ByteSlice, OverflowPolicy,
+};
 
 #[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
 pub use crate::utils::bytebuffer::GlobalDeque;
 
+#[cfg(feature = "bytebuffer")]
+pub use crate::utils::checksum::{
+    Adler32, CRC8, CRC8_MAXIM, CRC16_CCITT_FALSE, CRC16_KERMIT, CRC16_MODBUS, CRC16_X25,
+    CRC16_XMODEM, CRC32, CRC32C, Checksum, Crc8, Crc16, Crc32, CrcSpec, Fletcher16, Sum8, Xor8,
+};
+
+#[cfg(feature = "bytebuffer")]
+pub use crate::utils::textcodec::TextEncoding;
+
+#[cfg(feature = "bytebuffer")]
+pub use crate::utils::textbuffer::TextBuffer;
+
+#[cfg(feature = "bytebuffer")]
+pub use crate::utils::compare::ByteBufferDisplay;
+
+#[cfg(feature = "bytebuffer-stream")]
+pub use crate::utils::framing::Framing;
+
+#[cfg(feature = "bytebuffer-stream")]
+pub use crate::utils::msgqueue::MessageQueue;
+
+#[cfg(all(feature = "bytebuffer-stream", feature = "global-alloc"))]
+pub use crate::utils::msgqueue::GlobalMessageQueue;
+
 #[cfg(feature = "global-alloc")]
 pub use crate::global::{GlobalAllocatorConfig, init_global_allocator, with_global_allocator};
 
+#[cfg(any(feature = "embedded-io", feature = "std"))]
+pub use crate::utils::ioadapter::ByteBufferIo;
+
+#[cfg(all(
+    any(feature = "embedded-io", feature = "std"),
+    feature = "global-alloc"
+))]
+pub use crate::utils::ioadapter::GlobalDequeReader;
+
 // Nom integration traits are implemented directly on ByteBuffer when nominput feature is enabled
 // No additional types need to be exported
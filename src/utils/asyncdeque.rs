@@ -0,0 +1,315 @@
+//! Async consumer API for GlobalDeque
+//!
+//! Lets an async executor wait for data instead of polling `GlobalDeque::pop`
+//! in a loop. The consumer registers its `core::task::Waker` in the deque and
+//! `push` (typically called from an interrupt handler) wakes it.
+//!
+//! # Feature Flag
+//!
+//! This module is only available when the `deque-async` feature is enabled.
+//! It only depends on `core::future` and `core::task`, so it works in no_std.
+//!
+//! # Single Consumer
+//!
+//! The deque stores a single waker: the most recently polled future is the one
+//! woken. Use one consumer task per deque.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use tinyalloc::prelude::*;
+//!
+//! static UART_RX: GlobalDeque = GlobalDeque::new();
+//!
+//! fn uart_interrupt() {
+//!     UART_RX.push(read_uart()).ok(); // wakes the consumer task
+//! }
+//!
+//! async fn consumer() {
+//!     loop {
+//!         let byte = UART_RX.pop_async().await;
+//!         process(byte);
+//!     }
+//! }
+//! # fn read_uart() -> u8 { 0 }
+//! # fn process(_: u8) {}
+//! ```
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+#[cfg(feature = "bytebuffer-stream")]
+use crate::Allocator;
+use crate::utils::bytebuffer::GlobalDeque;
+#[cfg(feature = "bytebuffer-stream")]
+use crate::utils::bytebuffer::{ByteBuffer, ByteBufferError, OverflowPolicy};
+
+impl GlobalDeque {
+    /// Wait for a byte and pop it
+    ///
+    /// Resolves immediately if the deque already holds data, otherwise
+    /// registers the task's waker and resolves after the next `push`.
+    pub fn pop_async(&self) -> Pop<'_> {
+        Pop { deque: self }
+    }
+
+    /// Wait until `delimiter` is in the deque and split off everything up to it
+    ///
+    /// The returned buffer includes the delimiter and is backed by the global
+    /// allocator. A deque that rejects new bytes resolves early when no `push`
+    /// could complete the line, leaving the queued bytes in place:
+    /// `ByteBufferError::Overflow` if it is full without the delimiter, and
+    /// `ByteBufferError::AllocationFailed` if its tail chunk is full and the
+    /// global allocator has no free chunk left.
+    ///
+    /// ```rust,no_run
+    /// use tinyalloc::prelude::*;
+    ///
+    /// static UART_RX: GlobalDeque = GlobalDeque::new();
+    ///
+    /// async fn next_line() -> Result<ByteBuffer, ByteBufferError> {
+    ///     UART_RX.read_until(b'\n').await
+    /// }
+    /// ```
+    #[cfg(feature = "bytebuffer-stream")]
+    pub fn read_until(&self, delimiter: u8) -> ReadUntil<'_> {
+        ReadUntil {
+            deque: self,
+            delimiter,
+        }
+    }
+
+    /// Register the waker of the task waiting for data
+    fn register_waker(&self, waker: &Waker) {
+        // Safety: single-threaded access, same contract as `with_mut`
+        let slot = unsafe { &mut *self.waker.get() };
+        match slot {
+            Some(current) if current.will_wake(waker) => {}
+            _ => *slot = Some(waker.clone()),
+        }
+    }
+
+    /// Wake the waiting task, if any
+    pub(super) fn wake(&self) {
+        // Safety: single-threaded access, same contract as `with_mut`
+        if let Some(waker) = unsafe { (*self.waker.get()).take() } {
+            waker.wake();
+        }
+    }
+
+    /// Run `attempt`, registering the waker and retrying once if it yields nothing
+    ///
+    /// The retry closes the window where a `push` lands between the first
+    /// attempt and the registration.
+    fn poll_with<T>(
+        &self,
+        cx: &mut Context<'_>,
+        mut attempt: impl FnMut() -> Option<T>,
+    ) -> Poll<T> {
+        if let Some(value) = attempt() {
+            return Poll::Ready(value);
+        }
+        self.register_waker(cx.waker());
+        match attempt() {
+            Some(value) => Poll::Ready(value),
+            None => Poll::Pending,
+        }
+    }
+}
+
+/// Future returned by [`GlobalDeque::pop_async`]
+pub struct Pop<'a> {
+    deque: &'a GlobalDeque,
+}
+
+impl Future for Pop<'_> {
+    type Output = u8;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u8> {
+        let deque = self.deque;
+        deque.poll_with(cx, || deque.pop())
+    }
+}
+
+/// Future returned by [`GlobalDeque::read_until`]
+#[cfg(feature = "bytebuffer-stream")]
+pub struct ReadUntil<'a> {
+    deque: &'a GlobalDeque,
+    delimiter: u8,
+}
+
+#[cfg(feature = "bytebuffer-stream")]
+impl Future for ReadUntil<'_> {
+    type Output = Result<ByteBuffer, ByteBufferError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let (deque, delimiter) = (self.deque, self.delimiter);
+        deque.poll_with(cx, || {
+            deque.with_mut(|buf| {
+                crate::global::with_global_allocator(|alloc| {
+                    match buf.position(alloc, |b| b == delimiter) {
+                        Some(pos) => Some(buf.split_off_front(alloc, pos + 1)),
+                        None => stalled(buf, alloc).map(Err),
+                    }
+                })
+            })
+        })
+    }
+}
+
+/// Error for a rejecting deque that no `push` can add a byte to
+///
+/// Such a push fails without waking the task, so `read_until` would stay
+/// pending forever.
+#[cfg(feature = "bytebuffer-stream")]
+fn stalled<A: Allocator>(buf: &ByteBuffer, arena: &A) -> Option<ByteBufferError> {
+    if buf.overflow_policy() != OverflowPolicy::RejectNewest {
+        None
+    } else if buf.room() == 0 {
+        Some(ByteBufferError::Overflow)
+    } else if buf.tail_room(arena) == 0 && arena.len() >= arena.capacity() {
+        Some(ByteBufferError::AllocationFailed)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::global::GlobalAllocatorConfig;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use core::task::{RawWaker, RawWakerVTable};
+
+    // Waker that counts wake() calls in the AtomicUsize it points to
+    fn counting_waker(counter: &'static AtomicUsize) -> Waker {
+        fn clone(data: *const ()) -> RawWaker {
+            RawWaker::new(data, &VTABLE)
+        }
+        fn wake(data: *const ()) {
+            let counter = unsafe { &*(data as *const AtomicUsize) };
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+        fn drop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake, drop);
+
+        let raw = RawWaker::new(counter as *const AtomicUsize as *const (), &VTABLE);
+        unsafe { Waker::from_raw(raw) }
+    }
+
+    // Minimal host executor: polls until ready, running `on_pending` between polls
+    fn block_on<F: Future>(fut: F, waker: &Waker, mut on_pending: impl FnMut()) -> F::Output {
+        let mut fut = core::pin::pin!(fut);
+        let mut cx = Context::from_waker(waker);
+        loop {
+            if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+                return out;
+            }
+            on_pending();
+        }
+    }
+
+    #[test]
+    fn test_pop_async_woken_by_push() {
+        static RX: GlobalDeque = GlobalDeque::new();
+        static WAKES: AtomicUsize = AtomicUsize::new(0);
+        GlobalAllocatorConfig::Slab1K32.init();
+
+        let waker = counting_waker(&WAKES);
+        let mut cx = Context::from_waker(&waker);
+        let mut fut = core::pin::pin!(RX.pop_async());
+
+        // Empty deque: pending, no wake yet
+        assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(WAKES.load(Ordering::SeqCst), 0);
+
+        // Push wakes the registered task exactly once
+        RX.push(b'A').unwrap();
+        RX.push(b'B').unwrap();
+        assert_eq!(WAKES.load(Ordering::SeqCst), 1);
+
+        assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(b'A'));
+
+        // Data already available resolves without waiting
+        assert_eq!(block_on(RX.pop_async(), &waker, || unreachable!()), b'B');
+        assert!(RX.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "bytebuffer-stream")]
+    fn test_read_until_across_pushes() {
+        static RX: GlobalDeque = GlobalDeque::new();
+        static WAKES: AtomicUsize = AtomicUsize::new(0);
+        GlobalAllocatorConfig::Slab1K32.init();
+
+        let waker = counting_waker(&WAKES);
+        let mut incoming = b"OK\r\nREST".iter();
+
+        // Each pending poll lets the "interrupt" deliver one more byte
+        let mut line = block_on(RX.read_until(b'\n'), &waker, || {
+            RX.push(*incoming.next().unwrap()).unwrap();
+        })
+        .unwrap();
+
+        assert_eq!(WAKES.load(Ordering::SeqCst), 4);
+        assert_eq!(line.len(), 4);
+        let mut out = [0u8; 4];
+        assert_eq!(line.copy_prefix_to_global(&mut out), 4);
+        assert_eq!(&out, b"OK\r\n");
+
+        // Bytes after the delimiter stay queued
+        for &b in incoming {
+            RX.push(b).unwrap();
+        }
+        assert_eq!(RX.len(), 4);
+        RX.clear();
+    }
+
+    #[test]
+    #[cfg(feature = "bytebuffer-stream")]
+    fn test_read_until_full_deque_overflows() {
+        static RX: GlobalDeque = GlobalDeque::with_max_len(4);
+        static WAKES: AtomicUsize = AtomicUsize::new(0);
+        GlobalAllocatorConfig::Slab1K32.init();
+
+        let waker = counting_waker(&WAKES);
+        let mut incoming = b"ABCD".iter();
+
+        // Once full, further pushes fail, so the future must not stay pending
+        let result = block_on(RX.read_until(b'\n'), &waker, || {
+            RX.push(*incoming.next().unwrap()).unwrap();
+        });
+        assert_eq!(result.unwrap_err(), ByteBufferError::Overflow);
+        assert_eq!(RX.push(b'\n'), Err(ByteBufferError::Full));
+        assert_eq!(RX.len(), 4);
+        RX.clear();
+    }
+
+    #[test]
+    #[cfg(feature = "bytebuffer-stream")]
+    fn test_stalled_on_exhausted_allocator() {
+        use crate::backend::tinyslab::TinySlabAllocator;
+
+        // Two 14-byte chunks
+        let mut arena = TinySlabAllocator::<32, 2>::new();
+        let mut buf = ByteBuffer::new();
+        buf.write(&mut arena).extend(&[b'x'; 20]).unwrap();
+        assert_eq!(stalled(&buf, &arena), None);
+
+        // The last chunk fills up: no push can succeed any more
+        buf.write(&mut arena).extend(&[b'x'; 8]).unwrap();
+        assert_eq!(
+            buf.write(&mut arena).append(b'\n'),
+            Err(ByteBufferError::AllocationFailed)
+        );
+        assert_eq!(
+            stalled(&buf, &arena),
+            Some(ByteBufferError::AllocationFailed)
+        );
+
+        // Evicting policies keep accepting bytes
+        buf.set_overflow_policy(OverflowPolicy::DropOldest);
+        assert_eq!(stalled(&buf, &arena), None);
+    }
+}
@@ -57,6 +57,7 @@
 //! ```
 
 use crate::{Allocator, Handle};
+use core::ops::{Bound, RangeBounds};
 
 /// Errors that can occur during ByteBuffer operations
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -67,100 +68,226 @@ pub enum ByteBufferError {
     AllocationFailed,
     /// Buffer reached maximum length limit
     Full,
+    /// Not enough bytes in the buffer to read the requested value
+    Incomplete,
+    /// Stored bytes are not a valid encoding of the requested value
+    Malformed,
+    /// Item is longer than the space or limit provided for it
+    Overflow,
+    /// Index or range past the end of the readable data
+    OutOfRange,
+    /// Input contains a byte that is not valid in its format
+    InvalidByte {
+        /// Position of the offending byte in the input
+        offset: usize,
+    },
 }
 
+impl core::fmt::Display for ByteBufferError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Uninitialized => f.write_str("global allocator not initialized"),
+            Self::AllocationFailed => f.write_str("allocation failed"),
+            Self::Full => f.write_str("buffer full"),
+            Self::Incomplete => f.write_str("not enough data"),
+            Self::Malformed => f.write_str("malformed data"),
+            Self::Overflow => f.write_str("item too long"),
+            Self::OutOfRange => f.write_str("index out of range"),
+            Self::InvalidByte { offset } => write!(f, "invalid byte at offset {offset}"),
+        }
+    }
+}
+
+/// What `append` does when the buffer cannot take another byte
+///
+/// A buffer overflows when it reaches its `max_len` or when the allocator has
+/// no free chunk left. Whatever the policy, every lost byte is counted in
+/// [`ByteBuffer::overruns`].
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "bytebuffer-deque")]
+/// # {
+/// use tinyalloc::prelude::*;
+///
+/// let mut alloc = TinySlabAllocator::<512, 16>::new();
+/// let mut buf = ByteBuffer::with_overflow_policy(4, OverflowPolicy::DropOldest);
+///
+/// buf.write(&mut alloc).extend(b"ABCDEF").unwrap();
+/// assert_eq!(buf.len(), 4);
+/// assert_eq!(buf.peek_front(&alloc), Some(b'C')); // "AB" evicted
+/// assert_eq!(buf.overruns(), 2);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Reject the new byte and return the error (default)
+    #[default]
+    RejectNewest,
+    /// Evict bytes from the front to make room for the new byte
+    ///
+    /// On `max_len` one byte is evicted; when the allocator is exhausted the
+    /// whole head chunk is evicted so its slot can be reused.
+    #[cfg(feature = "bytebuffer-deque")]
+    DropOldest,
+    /// Overwrite the oldest data in place, like a ring buffer
+    ///
+    /// On `max_len` the oldest byte makes way for the new one. Drained head
+    /// chunks are relinked as the tail instead of being freed, and the buffer
+    /// takes at most one chunk more than `max_len` needs, so once that ring
+    /// is built it never frees or allocates. Without `max_len`, or when the
+    /// allocator cannot provide the extra chunk, the rest of the head chunk
+    /// is overwritten at once to make room.
+    #[cfg(feature = "bytebuffer-deque")]
+    Overwrite,
+}
+
+/// Integer type used for ByteBuffer lengths, limits and offsets
+///
+/// `u16` by default to keep buffers small on tiny targets, capping a buffer at
+/// 65535 bytes. The `bytebuffer-len-u32` feature widens it to `u32` for large
+/// staging buffers. Either way a buffer also stops growing when its allocator
+/// runs out of chunks.
+#[cfg(not(feature = "bytebuffer-len-u32"))]
+pub type BufLen = u16;
+
+/// Integer type used for ByteBuffer lengths, limits and offsets
+///
+/// Widened to `u32` by the `bytebuffer-len-u32` feature.
+#[cfg(feature = "bytebuffer-len-u32")]
+pub type BufLen = u32;
+
 const NONE_GEN: u8 = 0;
 
+/// Packed chunk header, stored in 2 or 4 bytes as the bit layout requires
+type Header = u32;
+
+/// Length of the data stored in one chunk
+pub(super) type ChunkLen = u16;
+
 /// Block layout in arena memory (zero-copy interpretation):
-/// [0..1]    = metadata (u16) - packed: [gen_bits | len_bits | slot_bits]
-/// [2..n]    = data bytes (variable size, depends on allocator)
+/// [0..header_len]  = metadata (2 or 4 bytes, see `BitLayout::header_len`) -
+///                    packed: [gen_bits | len_bits | slot_bits]
+/// [header_len..n]  = data bytes (variable size, depends on allocator)
 ///
 /// The bit layout is determined by allocator.bit_layout() and optimizes
 /// space usage based on the allocator's constraints (max slots, generations, block size)
-/// Helper functions to access block metadata using bit-packed u16
+/// Helper functions to access block metadata using the bit-packed header
+#[inline(always)]
+fn read_header(block: &[u8], layout: &crate::BitLayout) -> Header {
+    let mut bytes = [0u8; 4];
+    let n = layout.header_len();
+    bytes[..n].copy_from_slice(&block[..n]);
+    Header::from_le_bytes(bytes)
+}
+
 #[inline(always)]
-pub(super) fn get_len(block: &[u8], layout: &crate::BitLayout) -> u8 {
-    let metadata = u16::from_le_bytes([block[0], block[1]]);
-    let len_mask = (1u16 << layout.len_bits) - 1;
-    let len = (metadata >> layout.slot_bits) & len_mask;
-    len as u8
+fn write_header(block: &mut [u8], metadata: Header, layout: &crate::BitLayout) {
+    let n = layout.header_len();
+    block[..n].copy_from_slice(&metadata.to_le_bytes()[..n]);
 }
 
 #[inline(always)]
-fn set_len(block: &mut [u8], len: u8, layout: &crate::BitLayout) {
-    let mut metadata = u16::from_le_bytes([block[0], block[1]]);
-    let len_mask = (1u16 << layout.len_bits) - 1;
+fn field_mask(bits: u8) -> Header {
+    ((1 as Header) << bits) - 1
+}
+
+#[inline(always)]
+pub(super) fn get_len(block: &[u8], layout: &crate::BitLayout) -> ChunkLen {
+    let metadata = read_header(block, layout);
+    let len = (metadata >> layout.slot_bits) & field_mask(layout.len_bits);
+    len as ChunkLen
+}
+
+#[inline(always)]
+fn set_len(block: &mut [u8], len: ChunkLen, layout: &crate::BitLayout) {
+    let mut metadata = read_header(block, layout);
+    let len_mask = field_mask(layout.len_bits);
     // Clear len bits and set new value
     metadata &= !(len_mask << layout.slot_bits);
-    metadata |= ((len as u16) & len_mask) << layout.slot_bits;
-    let bytes = metadata.to_le_bytes();
-    block[0] = bytes[0];
-    block[1] = bytes[1];
+    metadata |= ((len as Header) & len_mask) << layout.slot_bits;
+    write_header(block, metadata, layout);
 }
 
 #[inline(always)]
 fn get_next_slot(block: &[u8], layout: &crate::BitLayout) -> u8 {
-    let metadata = u16::from_le_bytes([block[0], block[1]]);
-    let slot_mask = (1u16 << layout.slot_bits) - 1;
-    let slot = metadata & slot_mask;
+    let slot = read_header(block, layout) & field_mask(layout.slot_bits);
     slot as u8
 }
 
 #[inline(always)]
 fn set_next_slot(block: &mut [u8], slot: u8, layout: &crate::BitLayout) {
-    let mut metadata = u16::from_le_bytes([block[0], block[1]]);
-    let slot_mask = (1u16 << layout.slot_bits) - 1;
+    let mut metadata = read_header(block, layout);
+    let slot_mask = field_mask(layout.slot_bits);
     // Clear slot bits and set new value
     metadata &= !slot_mask;
-    metadata |= (slot as u16) & slot_mask;
-    let bytes = metadata.to_le_bytes();
-    block[0] = bytes[0];
-    block[1] = bytes[1];
+    metadata |= (slot as Header) & slot_mask;
+    write_header(block, metadata, layout);
 }
 
 #[inline]
 fn get_next_gen(block: &[u8], layout: &crate::BitLayout) -> u8 {
-    let metadata = u16::from_le_bytes([block[0], block[1]]);
+    if layout.gen_bits == 0 {
+        return 0;
+    }
     let gen_shift = layout.slot_bits + layout.len_bits;
-    let gen_mask = (1u16 << layout.gen_bits) - 1;
-    let generation = (metadata >> gen_shift) & gen_mask;
+    let generation = (read_header(block, layout) >> gen_shift) & field_mask(layout.gen_bits);
     generation as u8
 }
 
 #[inline]
 fn set_next_gen(block: &mut [u8], generation: u8, layout: &crate::BitLayout) {
-    let mut metadata = u16::from_le_bytes([block[0], block[1]]);
+    if layout.gen_bits == 0 {
+        return;
+    }
+    let mut metadata = read_header(block, layout);
     let gen_shift = layout.slot_bits + layout.len_bits;
-    let gen_mask = (1u16 << layout.gen_bits) - 1;
+    let gen_mask = field_mask(layout.gen_bits);
     // Clear gen bits and set new value
     metadata &= !(gen_mask << gen_shift);
-    metadata |= ((generation as u16) & gen_mask) << gen_shift;
-    let bytes = metadata.to_le_bytes();
-    block[0] = bytes[0];
-    block[1] = bytes[1];
+    metadata |= ((generation as Header) & gen_mask) << gen_shift;
+    write_header(block, metadata, layout);
+}
+
+#[inline]
+fn set_next_handle(block: &mut [u8], next: Option<Handle>, layout: &crate::BitLayout) {
+    match next {
+        Some(h) => {
+            set_next_slot(block, h.slot as u8, layout);
+            set_next_gen(block, h.generation, layout);
+        }
+        None => {
+            let none_slot = field_mask(layout.slot_bits) as u8;
+            set_next_slot(block, none_slot, layout);
+            set_next_gen(block, NONE_GEN, layout);
+        }
+    }
 }
 
 #[inline]
 fn get_data<'a>(block: &'a [u8], layout: &crate::BitLayout) -> &'a [u8] {
     let len = get_len(block, layout) as usize;
-    &block[2..2 + len]
+    let start = layout.header_len();
+    &block[start..start + len]
 }
 
 #[inline]
-fn get_data_mut(block: &mut [u8]) -> &mut [u8] {
-    &mut block[2..]
+fn get_data_mut<'a>(block: &'a mut [u8], layout: &crate::BitLayout) -> &'a mut [u8] {
+    &mut block[layout.header_len()..]
 }
 
 #[inline]
-fn data_capacity(block: &[u8]) -> usize {
-    block.len().saturating_sub(2)
+fn data_capacity(block: &[u8], layout: &crate::BitLayout) -> usize {
+    block.len().saturating_sub(layout.header_len())
 }
 
+/// Raw next link as stored in the header, with only the low generation bits
 #[inline(always)]
-pub(super) fn get_next_handle(block: &[u8], layout: &crate::BitLayout) -> Option<Handle> {
+fn get_next_link(block: &[u8], layout: &crate::BitLayout) -> Option<Handle> {
     let slot = get_next_slot(block, layout);
     let generation = get_next_gen(block, layout);
-    let max_slot = ((1u16 << layout.slot_bits) - 1) as u8;
+    let max_slot = field_mask(layout.slot_bits) as u8;
     if slot == max_slot {
         None
     } else {
@@ -168,6 +295,16 @@ pub(super) fn get_next_handle(block: &[u8], layout: &crate::BitLayout) -> Option
     }
 }
 
+/// Next chunk handle, resolved to its full generation by the allocator
+#[inline(always)]
+pub(super) fn get_next_handle<A: Allocator + ?Sized>(
+    arena: &A,
+    block: &[u8],
+    layout: &crate::BitLayout,
+) -> Option<Handle> {
+    get_next_link(block, layout).and_then(|link| arena.resolve_link(link))
+}
+
 /// A dynamic byte buffer backed by allocator chunks
 ///
 /// Stores data as a linked list of fixed-size blocks allocated from an `Allocator`.
@@ -222,12 +359,16 @@ pub struct ByteBuffer {
     /// Tail of the chunk linked list
     pub(super) tail: Option<Handle>,
     /// Total number of bytes stored
-    pub(super) len: u16,
+    pub(super) len: BufLen,
     /// Optional maximum length limit
-    pub(super) max_len: Option<u16>,
+    pub(super) max_len: Option<BufLen>,
     /// Offset for deque-style operations (bytes consumed from front)
     #[cfg(feature = "bytebuffer-deque")]
-    pub(super) read_offset: u16,
+    pub(super) read_offset: BufLen,
+    /// Policy applied when the buffer overflows
+    pub(super) overflow: OverflowPolicy,
+    /// Number of bytes lost to overflow since the last reset
+    pub(super) overruns: BufLen,
 }
 
 impl Default for ByteBuffer {
@@ -255,6 +396,8 @@ impl ByteBuffer {
             max_len: None,
             #[cfg(feature = "bytebuffer-deque")]
             read_offset: 0,
+            overflow: OverflowPolicy::RejectNewest,
+            overruns: 0,
         }
     }
 
@@ -268,7 +411,7 @@ impl ByteBuffer {
     /// assert_eq!(buf.max_len(), Some(100));
     /// ```
     #[inline(always)]
-    pub const fn with_max_len(max: u16) -> Self {
+    pub const fn with_max_len(max: BufLen) -> Self {
         Self {
             head: None,
             tail: None,
@@ -276,24 +419,66 @@ impl ByteBuffer {
             max_len: Some(max),
             #[cfg(feature = "bytebuffer-deque")]
             read_offset: 0,
+            overflow: OverflowPolicy::RejectNewest,
+            overruns: 0,
         }
     }
 
+    /// Creates a new ByteBuffer with a maximum length and an overflow policy
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "bytebuffer-deque")]
+    /// # {
+    /// use tinyalloc::prelude::*;
+    /// let buf = ByteBuffer::with_overflow_policy(64, OverflowPolicy::Overwrite);
+    /// assert_eq!(buf.max_len(), Some(64));
+    /// assert_eq!(buf.overflow_policy(), OverflowPolicy::Overwrite);
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub const fn with_overflow_policy(max: BufLen, policy: OverflowPolicy) -> Self {
+        let mut buf = Self::with_max_len(max);
+        buf.overflow = policy;
+        buf
+    }
+
     /// Sets the maximum length limit
     ///
     /// Pass `None` to remove the limit.
     #[inline(always)]
-    pub fn set_max_len(&mut self, v: Option<u16>) {
+    pub fn set_max_len(&mut self, v: Option<BufLen>) {
         self.max_len = v;
     }
     /// Returns the current maximum length limit
     #[inline(always)]
-    pub fn max_len(&self) -> Option<u16> {
+    pub fn max_len(&self) -> Option<BufLen> {
         self.max_len
     }
+    /// Sets the policy applied when the buffer overflows
+    #[inline(always)]
+    pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+        self.overflow = policy;
+    }
+    /// Returns the current overflow policy
+    #[inline(always)]
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.overflow
+    }
+    /// Returns the number of bytes lost to overflow (saturates at `BufLen::MAX`)
+    #[inline(always)]
+    pub fn overruns(&self) -> BufLen {
+        self.overruns
+    }
+    /// Resets the overrun counter, returning its previous value
+    #[inline(always)]
+    pub fn reset_overruns(&mut self) -> BufLen {
+        core::mem::take(&mut self.overruns)
+    }
     /// Returns the number of bytes in the buffer (available to read)
     #[inline(always)]
-    pub fn len(&self) -> u16 {
+    pub fn len(&self) -> BufLen {
         #[cfg(feature = "bytebuffer-deque")]
         {
             self.len.saturating_sub(self.read_offset)
@@ -306,7 +491,7 @@ impl ByteBuffer {
     /// Returns true if the buffer is empty
     #[inline(always)]
     pub fn is_empty(&self) -> bool {
-        self.len == 0
+        self.len() == 0
     }
 
     /// Returns the input length (for nom compatibility)
@@ -320,36 +505,43 @@ impl ByteBuffer {
     /// Takes the first `count` bytes as a view
     ///
     /// Creates a new ByteBuffer view limited to the first `count` bytes.
+    /// Readers of the view stop after those bytes.
     /// This is a direct mapping used by nom's Input trait.
     #[inline(always)]
     pub fn take(&self, count: usize) -> Self {
+        let count = count.min(self.len() as usize);
         ByteBuffer {
             head: self.head,
             tail: self.tail,
-            len: count.min(self.len as usize) as u16,
-            max_len: Some(count.min(self.len as usize) as u16),
+            len: (self.start_offset() + count) as BufLen,
+            max_len: Some(count as BufLen),
             #[cfg(feature = "bytebuffer-deque")]
-            read_offset: 0,
+            read_offset: self.read_offset,
+            overflow: self.overflow,
+            overruns: 0,
         }
     }
 
-    /// Takes bytes starting from `index`
+    /// Takes bytes starting from `index` as a view
     ///
-    /// This is a direct mapping used by nom's Input trait.
-    /// Note: Due to chunked storage, this has limitations without allocator access.
+    /// Finds the chunk holding byte `index` through the global allocator, so
+    /// the buffer must live there; use [`view_from`](Self::view_from) for
+    /// buffers in a local arena. Returns an empty buffer if `index` is past
+    /// the end.
+    /// This is a direct mapping used by nom's Input trait. Only available with
+    /// `bytebuffer-deque` and `global-alloc`; other builds use `view_from`.
+    #[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
     #[inline(always)]
     pub fn take_from(&self, index: usize) -> Self {
-        if index >= self.len as usize {
-            ByteBuffer::new()
-        } else {
-            // Limitation: can't skip without allocator
-            *self
-        }
+        crate::global::with_global_allocator(|alloc| self.view_from(alloc, index))
     }
 
     /// Splits buffer at `index`, returning (remaining, taken)
     ///
+    /// Like [`take_from`](Self::take_from), the buffer must live in the
+    /// global allocator.
     /// This is a direct mapping used by nom's Input trait.
+    #[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
     #[inline(always)]
     pub fn take_split(&self, index: usize) -> (Self, Self) {
         let remaining = self.take_from(index);
@@ -357,15 +549,41 @@ impl ByteBuffer {
         (remaining, taken)
     }
 
+    /// Returns a view of the bytes starting at `index`
+    ///
+    /// The view shares this buffer's chunks: its head is the chunk holding
+    /// byte `index` and its read offset points inside that chunk, so readers
+    /// never rescan the skipped bytes. Like [`take`](Self::take), the result
+    /// is meant for reading only. Returns an empty buffer if `index` is past
+    /// the end or the chunks cannot be resolved in `arena`.
+    #[cfg(feature = "bytebuffer-deque")]
+    pub fn view_from<A: Allocator>(&self, arena: &A, index: usize) -> Self {
+        let live = self.len() as usize;
+        if index >= live {
+            return ByteBuffer::new();
+        }
+        match self.locate(arena, index) {
+            Ok((_, chunk, pos)) => ByteBuffer {
+                head: Some(chunk),
+                len: (pos + live - index) as BufLen,
+                read_offset: pos as BufLen,
+                overruns: 0,
+                ..*self
+            },
+            Err(_) => ByteBuffer::new(),
+        }
+    }
+
     /// Validates if `count` bytes can be taken
     ///
     /// This is a direct mapping used by nom's Input trait.
     #[inline(always)]
     pub fn slice_index(&self, count: usize) -> Result<usize, usize> {
-        if count <= self.len as usize {
+        let len = self.len() as usize;
+        if count <= len {
             Ok(count)
         } else {
-            Err(count - self.len as usize)
+            Err(count - len)
         }
     }
 
@@ -377,274 +595,450 @@ impl ByteBuffer {
     where
         P: Fn(u8) -> bool,
     {
-        self.read(arena).bytes().position(predicate)
-    }
-
-    /// Immutable read context
-    pub fn read<'a, A: Allocator>(&'a self, arena: &'a A) -> ByteBufferRead<'a, A> {
-        ByteBufferRead { arena, buf: self }
+        self.read(arena).position(predicate)
     }
 
-    /// Mutable write context
-    pub fn write<'a, A: Allocator>(&'a mut self, arena: &'a mut A) -> ByteBufferWrite<'a, A> {
-        ByteBufferWrite { arena, buf: self }
+    /// Borrows the bytes in `range` as a [`ByteSlice`] view
+    ///
+    /// Finding the start walks the chunk list once; the view then addresses
+    /// the range directly, without copying.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tinyalloc::prelude::*;
+    ///
+    /// let mut alloc = TinySlabAllocator::<1024, 32>::new();
+    /// let mut buf = ByteBuffer::new();
+    /// buf.write(&mut alloc).extend(b"key=value").unwrap();
+    ///
+    /// let value = buf.slice(&alloc, 4..).unwrap();
+    /// let mut out = [0u8; 5];
+    /// assert_eq!(value.copy_to_slice(&alloc, &mut out), 5);
+    /// assert_eq!(&out, b"value");
+    /// assert!(buf.slice(&alloc, 4..20).is_err());
+    /// ```
+    pub fn slice<A: Allocator, R: RangeBounds<usize>>(
+        &self,
+        arena: &A,
+        range: R,
+    ) -> Result<ByteSlice, ByteBufferError> {
+        let (start, end) = resolve_range(range, self.len() as usize)?;
+        Ok(ByteSlice {
+            start: ChunkCursor::seek(arena, self, start),
+            len: (end - start) as BufLen,
+        })
     }
 
-    /// Zero-copy transfer
-    pub fn move_from(&mut self, src: &mut Self) {
-        core::mem::swap(self, src);
-        src.head = None;
-        src.tail = None;
-        src.len = 0;
+    /// Returns the byte at `idx` without consuming it
+    ///
+    /// Whole chunks before `idx` are skipped using their stored lengths.
+    /// Returns `None` if `idx` is past the end.
+    pub fn get<A: Allocator>(&self, arena: &A, idx: usize) -> Option<u8> {
+        if idx >= self.len() as usize {
+            return None;
+        }
+        let mut byte = [0u8];
+        let mut cursor = ChunkCursor::seek(arena, self, idx);
+        (cursor.read(arena, &mut byte) == 1).then_some(byte[0])
     }
 
-    /// Zero-copy drain to a new buffer - empties self and returns new buffer with the data
-    pub fn drain(&mut self) -> Self {
-        let mut drained = Self::new();
-        drained.move_from(self);
-        drained
+    /// Overwrites the byte at `idx`
+    ///
+    /// Returns `ByteBufferError::OutOfRange` if `idx` is past the end.
+    pub fn set<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        idx: usize,
+        byte: u8,
+    ) -> Result<(), ByteBufferError> {
+        self.write_at(arena, idx, &[byte])
     }
 
-    /// Copies bytes from a specific range of another ByteBuffer into this one.
-    /// This allows reading from one buffer and appending to another using the same allocator.
-    /// Uses unsafe raw pointers internally to avoid borrow checker conflicts.
-    pub fn copy_from<A: Allocator>(
+    /// Overwrites bytes starting at `offset`
+    ///
+    /// Only bytes already stored are replaced; the buffer never grows. A
+    /// write running past the end fails with `ByteBufferError::OutOfRange`
+    /// and changes nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tinyalloc::prelude::*;
+    ///
+    /// let mut alloc = TinySlabAllocator::<1024, 32>::new();
+    /// let mut buf = ByteBuffer::new();
+    ///
+    /// // Reserve a length field, write the payload, then back-patch it
+    /// buf.write(&mut alloc).extend(&[0, 0]).unwrap();
+    /// buf.write(&mut alloc).extend(b"payload").unwrap();
+    /// let len = ((buf.len() - 2) as u16).to_be_bytes();
+    /// buf.write_at(&mut alloc, 0, &len).unwrap();
+    ///
+    /// assert_eq!(buf.get(&alloc, 1), Some(7));
+    /// ```
+    pub fn write_at<A: Allocator>(
         &mut self,
         arena: &mut A,
-        src: &Self,
-        range: core::ops::Range<usize>,
+        offset: usize,
+        data: &[u8],
     ) -> Result<(), ByteBufferError> {
-        // Use raw pointer to arena to allow simultaneous read and write
-        let arena_ptr = arena as *mut A;
-
-        unsafe {
-            for byte in src
-                .read(&*arena)
-                .bytes()
-                .skip(range.start)
-                .take(range.end - range.start)
-            {
-                self.write(&mut *arena_ptr).append(byte)?;
-            }
+        let end = offset
+            .checked_add(data.len())
+            .ok_or(ByteBufferError::OutOfRange)?;
+        if end > self.len() as usize {
+            return Err(ByteBufferError::OutOfRange);
+        }
+        let mut cursor = ChunkCursor::seek(arena, self, offset);
+        if cursor.write(arena, data) < data.len() {
+            return Err(ByteBufferError::OutOfRange);
         }
         Ok(())
     }
 
-    /* ---- Internal helpers ---- */
-
-    fn alloc_node<A: Allocator>(
-        &self,
-        arena: &mut A,
-        block_size: usize,
-    ) -> Result<Handle, ByteBufferError> {
-        if block_size < 2 {
-            return Err(ByteBufferError::AllocationFailed);
+    /// Copies bytes starting at `offset` into `dest` without consuming them
+    ///
+    /// Returns the number of bytes copied, which is less than `dest.len()`
+    /// when the buffer ends first and 0 when `offset` is past the end.
+    pub fn copy_to_slice<A: Allocator>(&self, arena: &A, offset: usize, dest: &mut [u8]) -> usize {
+        let n = dest.len().min((self.len() as usize).saturating_sub(offset));
+        if n == 0 {
+            return 0;
         }
-
-        let layout = arena.bit_layout();
-        let (h, buf) = arena
-            .alloc_uninit(block_size)
-            .ok_or(ByteBufferError::AllocationFailed)?;
-
-        let none_slot = ((1u16 << layout.slot_bits) - 1) as u8;
-        set_len(buf, 0, &layout);
-        set_next_slot(buf, none_slot, &layout);
-        set_next_gen(buf, NONE_GEN, &layout);
-        // Data area is already uninitialized, no need to zero it
-
-        Ok(h)
-    }
-}
-
-// ============================================================================
-// Global Allocator API (enabled with feature = "global-alloc")
-// ============================================================================
-
-#[cfg(feature = "global-alloc")]
-impl ByteBuffer {
-    /// Append a single byte using the global allocator
-    pub fn append(&mut self, byte: u8) -> Result<(), ByteBufferError> {
-        crate::global::with_global_allocator(|alloc| self.write(alloc).append(byte))
+        let mut cursor = ChunkCursor::seek(arena, self, offset);
+        cursor.read(arena, &mut dest[..n])
     }
 
-    /// Extend with multiple bytes using the global allocator
-    pub fn extend(&mut self, data: &[u8]) -> Result<(), ByteBufferError> {
-        crate::global::with_global_allocator(|alloc| self.write(alloc).extend(data))
+    /// Returns the unread bytes stored in the head chunk
+    ///
+    /// Returns `None` for an empty buffer.
+    pub fn first_chunk<'a, A: Allocator>(&self, arena: &'a A) -> Option<&'a [u8]> {
+        self.live_chunks(arena).next()
     }
 
-    /// Clear all data using the global allocator
-    pub fn clear(&mut self) {
-        crate::global::with_global_allocator(|alloc| self.write(alloc).clear())
+    /// Returns all unread bytes as one slice if they live in a single chunk
+    ///
+    /// An empty buffer yields an empty slice. Returns `None` when the data
+    /// spans several chunks; see [`with_contiguous`](Self::with_contiguous)
+    /// and [`make_contiguous`](Self::make_contiguous).
+    pub fn as_single_slice<'a, A: Allocator>(&self, arena: &'a A) -> Option<&'a [u8]> {
+        let mut chunks = self.live_chunks(arena);
+        match (chunks.next(), chunks.next()) {
+            (None, _) => Some(&[]),
+            (Some(first), None) => Some(first),
+            (Some(_), Some(_)) => None,
+        }
     }
 
-    /// Iterate over bytes using the global allocator
-    pub fn bytes(&self) -> ByteBufferGlobalIter<'_> {
-        ByteBufferGlobalIter {
-            buf: self,
-            index: 0,
+    /// Calls `f` with all unread bytes as one contiguous slice
+    ///
+    /// Data held in a single chunk is borrowed directly. Otherwise it is copied
+    /// into `scratch` first, so `scratch` only needs to be as long as the
+    /// buffer when the data spans chunks.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ByteBufferError::Full` if the data spans chunks and `scratch`
+    /// is too short to hold it; `f` is not called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tinyalloc::prelude::*;
+    ///
+    /// let mut alloc = TinySlabAllocator::<1024, 32>::new();
+    /// let mut buf = ByteBuffer::new();
+    /// buf.write(&mut alloc).extend(b"temperature=21.5").unwrap();
+    ///
+    /// let mut scratch = [0u8; 32];
+    /// let text = buf
+    ///     .with_contiguous(&alloc, &mut scratch, |bytes| {
+    ///         core::str::from_utf8(bytes).map(|s| s.len())
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(text, Ok(16));
+    /// ```
+    pub fn with_contiguous<A: Allocator, R>(
+        &self,
+        arena: &A,
+        scratch: &mut [u8],
+        f: impl FnOnce(&[u8]) -> R,
+    ) -> Result<R, ByteBufferError> {
+        if let Some(slice) = self.as_single_slice(arena) {
+            return Ok(f(slice));
+        }
+        let len = self.len() as usize;
+        if scratch.len() < len {
+            return Err(ByteBufferError::Full);
         }
+        let n = self.copy_to_slice(arena, 0, &mut scratch[..len]);
+        Ok(f(&scratch[..n]))
     }
 
-    /// Copy from another buffer using global allocator
-    pub fn copy_from_global(
+    /// Moves all unread bytes into the head chunk and returns them as one slice
+    ///
+    /// Uses [`compact`](Self::compact), which frees the chunks it empties.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ByteBufferError::Full` if the data is longer than one chunk
+    /// can hold; the buffer is unchanged.
+    pub fn make_contiguous<'a, A: Allocator>(
         &mut self,
-        src: &Self,
-        range: core::ops::Range<usize>,
-    ) -> Result<(), ByteBufferError> {
-        crate::global::with_global_allocator(|alloc| self.copy_from(alloc, src, range))
+        arena: &'a mut A,
+    ) -> Result<&'a [u8], ByteBufferError> {
+        if let Some(head) = self.head {
+            let block = arena.get(head).ok_or(ByteBufferError::Uninitialized)?;
+            if self.len() as usize > data_capacity(block, &arena.bit_layout()) {
+                return Err(ByteBufferError::Full);
+            }
+        }
+        self.compact(arena);
+        let arena: &'a A = arena;
+        self.as_single_slice(arena)
+            .ok_or(ByteBufferError::Uninitialized)
     }
 
-    /// Finds the first byte position matching the predicate using global allocator
-    pub fn position_global<P>(&self, predicate: P) -> Option<usize>
-    where
-        P: Fn(u8) -> bool,
-    {
-        crate::global::with_global_allocator(|alloc| self.position(alloc, predicate))
+    /// Shortens the buffer to `len` bytes, freeing chunks past the new end
+    ///
+    /// Does nothing if `len` is not less than the current length.
+    pub fn truncate<A: Allocator>(&mut self, arena: &mut A, len: usize) {
+        if len >= self.len() as usize {
+            return;
+        }
+        if len == 0 {
+            self.write(arena).clear();
+            return;
+        }
+        let layout = arena.bit_layout();
+        let Ok((_, last, offset)) = self.locate(arena, len - 1) else {
+            return;
+        };
+        let Some(block) = arena.get_mut(last) else {
+            return;
+        };
+        let rest = get_next_link(block, &layout);
+        set_len(block, (offset + 1) as ChunkLen, &layout);
+        set_next_handle(block, None, &layout);
+        let rest = rest.and_then(|link| arena.resolve_link(link));
+        let _ = free_chain(arena, rest);
+        self.tail = Some(last);
+        self.len = (self.start_offset() + len) as BufLen;
     }
 
-    // ========================================================================
-    // Deque-style operations - Global API (enabled with features)
-    // ========================================================================
+    /// Removes and returns the last byte
+    ///
+    /// Frees the tail chunk once it is empty.
+    pub fn pop_back<A: Allocator>(&mut self, arena: &mut A) -> Option<u8> {
+        let last = (self.len() as usize).checked_sub(1)?;
+        let byte = self.get(arena, last)?;
+        self.truncate(arena, last);
+        Some(byte)
+    }
 
-    /// Peek at the front byte without consuming it (global allocator version)
-    #[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
-    pub fn peek_front_global(&self) -> Option<u8> {
-        crate::global::with_global_allocator(|alloc| self.peek_front(alloc))
+    /// Prepends a byte
+    ///
+    /// With `bytebuffer-deque`, space freed by earlier pops in the head chunk
+    /// is reused. Once it runs out, a new head chunk is linked in and filled
+    /// from its end, so a run of prepends shares chunks. Without the feature
+    /// every prepend links a new chunk in front of the head, one chunk per
+    /// byte; build the buffer in order instead where possible.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ByteBufferError::Full` if the buffer is at `max_len` (the
+    /// overflow policy only applies to appends) and
+    /// `ByteBufferError::AllocationFailed` if a new chunk is needed but the
+    /// allocator is exhausted.
+    pub fn push_front<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        byte: u8,
+    ) -> Result<(), ByteBufferError> {
+        #[cfg(feature = "bytebuffer-deque")]
+        {
+            if self.room() == 0 {
+                return Err(ByteBufferError::Full);
+            }
+            let layout = arena.bit_layout();
+            if self.read_offset == 0 || self.head.is_none() {
+                let cap = arena.block_size().saturating_sub(layout.header_len());
+                if self.len() as usize + cap > BufLen::MAX as usize {
+                    // No length left to count a whole chunk as consumed
+                    return self.insert(arena, 0, &[byte]);
+                }
+                self.push_head_chunk(arena)?;
+            }
+            let head = self.head.ok_or(ByteBufferError::Uninitialized)?;
+            let block = arena.get_mut(head).ok_or(ByteBufferError::Uninitialized)?;
+            get_data_mut(block, &layout)[self.read_offset as usize - 1] = byte;
+            self.read_offset -= 1;
+            Ok(())
+        }
+        #[cfg(not(feature = "bytebuffer-deque"))]
+        self.insert(arena, 0, &[byte])
     }
 
-    /// Pop a byte from the front of the buffer (global allocator version)
-    #[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
-    pub fn pop_front_global(&mut self) -> Option<u8> {
-        crate::global::with_global_allocator(|alloc| self.pop_front(alloc))
+    /// Links a new head chunk whose space all counts as consumed, so
+    /// `push_front` fills it from the end
+    #[cfg(feature = "bytebuffer-deque")]
+    fn push_head_chunk<A: Allocator>(&mut self, arena: &mut A) -> Result<(), ByteBufferError> {
+        let layout = arena.bit_layout();
+        let live = self.len();
+        let fresh = self.alloc_node(arena, arena.block_size())?;
+        let block = arena.get_mut(fresh).ok_or(ByteBufferError::Uninitialized)?;
+        let cap = data_capacity(block, &layout);
+        set_len(block, cap as ChunkLen, &layout);
+        set_next_handle(block, self.head, &layout);
+        if self.head.is_none() {
+            self.tail = Some(fresh);
+        }
+        self.head = Some(fresh);
+        self.read_offset = cap as BufLen;
+        self.len = live + cap as BufLen;
+        Ok(())
     }
 
-    /// Remove N bytes from the front of the buffer (global allocator version)
-    #[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
-    pub fn remove_prefix_global(&mut self, count: usize) -> usize {
-        crate::global::with_global_allocator(|alloc| self.remove_prefix(alloc, count))
-    }
-
-    // ========================================================================
-    // Stream processing operations - Global API (enabled with features)
-    // ========================================================================
-
-    /// Peek at the nth byte from the front (global allocator version)
-    #[cfg(all(feature = "bytebuffer-stream", feature = "global-alloc"))]
-    pub fn peek_n_global(&self, n: usize) -> Option<u8> {
-        crate::global::with_global_allocator(|alloc| self.peek_n(alloc, n))
-    }
-
-    /// Remove bytes from the front while predicate is true (global allocator version)
-    #[cfg(all(feature = "bytebuffer-stream", feature = "global-alloc"))]
-    pub fn skip_while_global<F>(&mut self, predicate: F) -> usize
-    where
-        F: Fn(u8) -> bool,
-    {
-        crate::global::with_global_allocator(|alloc| self.skip_while(alloc, predicate))
-    }
-
-    /// Remove bytes until delimiter is found (global allocator version)
-    #[cfg(all(feature = "bytebuffer-stream", feature = "global-alloc"))]
-    pub fn consume_until_global(&mut self, delimiter: u8) -> Option<usize> {
-        crate::global::with_global_allocator(|alloc| self.consume_until(alloc, delimiter))
-    }
-
-    /// Copy bytes from front into slice and consume them (global allocator version)
-    #[cfg(all(feature = "bytebuffer-stream", feature = "global-alloc"))]
-    pub fn copy_prefix_to_global(&mut self, dest: &mut [u8]) -> usize {
-        crate::global::with_global_allocator(|alloc| self.copy_prefix_to(alloc, dest))
-    }
-
-    /// Split off the front N bytes into a new ByteBuffer (global allocator version)
-    #[cfg(all(feature = "bytebuffer-stream", feature = "global-alloc"))]
-    pub fn split_off_front_global(&mut self, at: usize) -> Result<ByteBuffer, ByteBufferError> {
-        crate::global::with_global_allocator(|alloc| self.split_off_front(alloc, at))
-    }
-
-    /// Move bytes from front to back of another buffer (global allocator version)
-    #[cfg(all(feature = "bytebuffer-stream", feature = "global-alloc"))]
-    pub fn drain_into_global(&mut self, other: &mut ByteBuffer, count: usize) -> usize {
-        crate::global::with_global_allocator(|alloc| self.drain_into(alloc, other, count))
-    }
-
-    // ========================================================================
-    // Deque-style operations (enabled with feature = "bytebuffer-deque")
-    // ========================================================================
-
-    /// Peek at the front byte without consuming it
+    /// Inserts `data` before the byte at `idx`
     ///
-    /// Returns `None` if the buffer is empty.
+    /// The chunk holding `idx` is split (one allocation unless `idx` falls on
+    /// a chunk boundary), `data` is written after the front part, and the back
+    /// part is relinked without copying.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ByteBufferError::OutOfRange` if `idx` is past the end and
+    /// `ByteBufferError::Full` if the result would exceed `max_len`; the
+    /// buffer is unchanged. Returns `ByteBufferError::AllocationFailed` if the
+    /// allocator runs out part way, after restoring the original contents.
     ///
     /// # Examples
     ///
     /// ```
-    /// # #[cfg(feature = "bytebuffer-deque")]
-    /// # {
     /// use tinyalloc::prelude::*;
     ///
-    /// let mut alloc = TinySlabAllocator::<512, 16>::new();
+    /// let mut alloc = TinySlabAllocator::<1024, 32>::new();
     /// let mut buf = ByteBuffer::new();
+    /// buf.write(&mut alloc).extend(b"helo").unwrap();
     ///
-    /// buf.write(&mut alloc).extend(b"ABC").unwrap();
+    /// buf.insert(&mut alloc, 3, b"l").unwrap();
+    /// assert_eq!(buf.remove(&mut alloc, 0..1), Ok(1));
+    /// buf.push_front(&mut alloc, b'j').unwrap();
+    /// assert_eq!(buf.pop_back(&mut alloc), Some(b'o'));
     ///
-    /// // Peek doesn't consume
-    /// assert_eq!(buf.peek_front(&alloc), Some(b'A'));
-    /// assert_eq!(buf.len(), 3);
-    /// assert_eq!(buf.peek_front(&alloc), Some(b'A')); // Still 'A'
-    /// # }
+    /// let mut out = [0u8; 4];
+    /// buf.copy_to_slice(&alloc, 0, &mut out);
+    /// assert_eq!(&out, b"jell");
     /// ```
-    #[cfg(feature = "bytebuffer-deque")]
-    pub fn peek_front<A: Allocator>(&self, arena: &A) -> Option<u8> {
-        if self.is_empty() {
-            return None;
+    pub fn insert<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        idx: usize,
+        data: &[u8],
+    ) -> Result<(), ByteBufferError> {
+        if idx > self.len() as usize {
+            return Err(ByteBufferError::OutOfRange);
+        }
+        if data.len() > self.room() {
+            return Err(ByteBufferError::Full);
+        }
+        if data.is_empty() {
+            return Ok(());
         }
 
-        self.read(arena).bytes().nth(self.read_offset as usize)
+        let mut back = self.split_chain(arena, idx)?;
+        if let Err(e) = self.write(arena).extend_exact(data) {
+            self.truncate(arena, idx);
+            let _ = self.append_buffer(arena, &mut back);
+            return Err(e);
+        }
+        self.append_buffer(arena, &mut back)
     }
 
-    /// Pop a byte from the front of the buffer
-    ///
-    /// Automatically frees fully-consumed chunks for efficient memory usage.
-    /// Returns `None` if the buffer is empty.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// # #[cfg(feature = "bytebuffer-deque")]
-    /// # {
-    /// use tinyalloc::prelude::*;
+    /// Removes the bytes in `range`, returning how many were removed
     ///
-    /// let mut alloc = TinySlabAllocator::<512, 16>::new();
-    /// let mut buf = ByteBuffer::new();
+    /// Chunks entirely inside the range are freed. Only the bytes after the
+    /// range in its last chunk are moved, within that chunk, so this never
+    /// allocates.
     ///
-    /// buf.write(&mut alloc).extend(b"Hello").unwrap();
+    /// # Errors
     ///
-    /// assert_eq!(buf.pop_front(&mut alloc), Some(b'H'));
-    /// assert_eq!(buf.pop_front(&mut alloc), Some(b'e'));
-    /// assert_eq!(buf.len(), 3); // "llo" remains
-    /// # }
-    /// ```
-    #[cfg(feature = "bytebuffer-deque")]
-    pub fn pop_front<A: Allocator>(&mut self, arena: &mut A) -> Option<u8> {
-        if self.is_empty() {
-            return None;
+    /// Returns `ByteBufferError::OutOfRange` if the range is past the end.
+    pub fn remove<A: Allocator, R: RangeBounds<usize>>(
+        &mut self,
+        arena: &mut A,
+        range: R,
+    ) -> Result<usize, ByteBufferError> {
+        let live = self.len() as usize;
+        let (start, end) = resolve_range(range, live)?;
+        if start == end {
+            return Ok(0);
+        }
+        if end == live {
+            self.truncate(arena, start);
+            return Ok(end - start);
         }
 
-        // Read the byte at current read offset
-        let byte = self.read(arena).bytes().nth(self.read_offset as usize)?;
-
-        self.read_offset += 1;
+        let layout = arena.bit_layout();
+        let (prev, first, first_off) = self.locate(arena, start)?;
+        let (_, last, last_off) = self.locate(arena, end)?;
+
+        let block = arena.get_mut(last).ok_or(ByteBufferError::Uninitialized)?;
+        let last_len = get_len(block, &layout) as usize;
+        if first == last {
+            get_data_mut(block, &layout).copy_within(last_off..last_len, first_off);
+            set_len(
+                block,
+                (last_len - (last_off - first_off)) as ChunkLen,
+                &layout,
+            );
+        } else {
+            // Keep the bytes after the range at the start of the last chunk
+            get_data_mut(block, &layout).copy_within(last_off..last_len, 0);
+            set_len(block, (last_len - last_off) as ChunkLen, &layout);
+
+            let block = arena.get(first).ok_or(ByteBufferError::Uninitialized)?;
+            let mut next = get_next_handle(&*arena, block, &layout);
+            while let Some(h) = next
+                && h != last
+            {
+                next = arena
+                    .get(h)
+                    .and_then(|block| get_next_handle(&*arena, block, &layout));
+                let _ = arena.free(h);
+            }
 
-        // Try to free fully-consumed head chunks
-        self.try_free_head_chunks(arena);
+            if first_off == 0 {
+                // Nothing left in the first chunk either
+                let _ = arena.free(first);
+                match prev {
+                    Some(p) => {
+                        let block = arena.get_mut(p).ok_or(ByteBufferError::Uninitialized)?;
+                        set_next_handle(block, Some(last), &layout);
+                    }
+                    None => self.head = Some(last),
+                }
+            } else {
+                let block = arena.get_mut(first).ok_or(ByteBufferError::Uninitialized)?;
+                set_len(block, first_off as ChunkLen, &layout);
+                set_next_handle(block, Some(last), &layout);
+            }
+        }
 
-        Some(byte)
+        self.len -= (end - start) as BufLen;
+        Ok(end - start)
     }
 
-    /// Remove N bytes from the front of the buffer
+    /// Repacks the live bytes into as few chunks as possible
     ///
-    /// This is more efficient than calling `pop_front()` N times as it
-    /// frees chunks in bulk. Returns the actual number of bytes removed.
+    /// Bytes are moved towards the head, filling every chunk to capacity,
+    /// which also drops the space consumed from the head chunk. Chunks left
+    /// empty are freed. Returns the number of slots reclaimed.
+    ///
+    /// Compaction copies every live byte once and does not allocate, so it
+    /// suits long-lived buffers that see many pops, removals or small writes.
+    /// The chain is checked before anything is moved: if a chunk cannot be
+    /// resolved in `arena`, the buffer is left untouched and 0 is returned.
     ///
     /// # Examples
     ///
@@ -656,977 +1050,3357 @@ impl ByteBuffer {
     /// let mut alloc = TinySlabAllocator::<512, 16>::new();
     /// let mut buf = ByteBuffer::new();
     ///
-    /// buf.write(&mut alloc).extend(b"Hello, World!").unwrap();
-    ///
-    /// // Remove first 7 bytes ("Hello, ")
-    /// let removed = buf.remove_prefix(&mut alloc, 7);
-    /// assert_eq!(removed, 7);
-    /// assert_eq!(buf.len(), 6); // "World!" remains
+    /// buf.write(&mut alloc).extend(&[7u8; 40]).unwrap();
+    /// buf.remove_prefix(&mut alloc, 20);
+    /// assert_eq!(alloc.len(), 2);
     ///
-    /// // Try to remove more than available
-    /// let removed = buf.remove_prefix(&mut alloc, 100);
-    /// assert_eq!(removed, 6); // Only removed what was available
-    /// assert_eq!(buf.len(), 0);
+    /// assert_eq!(buf.compact(&mut alloc), 1);
+    /// assert_eq!(alloc.len(), 1);
+    /// assert_eq!(buf.len(), 20);
     /// # }
     /// ```
-    #[cfg(feature = "bytebuffer-deque")]
-    pub fn remove_prefix<A: Allocator>(&mut self, arena: &mut A, count: usize) -> usize {
-        let available = self.len() as usize;
-        let to_remove = count.min(available);
+    pub fn compact<A: Allocator>(&mut self, arena: &mut A) -> usize {
+        let live = self.len() as usize;
+        let Some(head) = self.head else {
+            return 0;
+        };
+        if live == 0 {
+            let freed = free_chain(arena, Some(head));
+            self.write(arena).clear();
+            return freed;
+        }
 
-        if to_remove == 0 {
+        if !self.chain_is_intact(arena) {
             return 0;
         }
 
-        self.read_offset += to_remove as u16;
-        self.try_free_head_chunks(arena);
+        let layout = arena.bit_layout();
+        let mut scratch = [0u8; COPY_SCRATCH];
+        let (mut dst, mut dst_len) = (head, 0);
+        let mut src = Some(head);
+        let mut src_off = self.start_offset();
+
+        // Chunk lengths are only rewritten once the write position has left a
+        // chunk, so source headers are always read before they change. The
+        // lookups below cannot fail on the chain checked above.
+        while let Some(s) = src {
+            let Some(block) = arena.get(s) else { return 0 };
+            let (s_len, s_next) = (
+                get_len(block, &layout) as usize,
+                get_next_handle(&*arena, block, &layout),
+            );
+            let cap = data_capacity(block, &layout);
+            let mut off = src_off;
+            while off < s_len {
+                if dst_len == cap {
+                    let Some(block) = arena.get_mut(dst) else {
+                        return 0;
+                    };
+                    set_len(block, cap as ChunkLen, &layout);
+                    let next = get_next_link(block, &layout);
+                    let Some(next) = next.and_then(|link| arena.resolve_link(link)) else {
+                        return 0;
+                    };
+                    (dst, dst_len) = (next, 0);
+                }
+                let n = (s_len - off).min(cap - dst_len).min(COPY_SCRATCH);
+                if dst == s {
+                    let Some(block) = arena.get_mut(s) else {
+                        return 0;
+                    };
+                    get_data_mut(block, &layout).copy_within(off..off + n, dst_len);
+                } else {
+                    let Some(block) = arena.get(s) else { return 0 };
+                    scratch[..n].copy_from_slice(&get_data(block, &layout)[off..off + n]);
+                    let Some(block) = arena.get_mut(dst) else {
+                        return 0;
+                    };
+                    get_data_mut(block, &layout)[dst_len..dst_len + n]
+                        .copy_from_slice(&scratch[..n]);
+                }
+                off += n;
+                dst_len += n;
+            }
+            src = s_next;
+            src_off = 0;
+        }
 
-        to_remove
+        let Some(block) = arena.get_mut(dst) else {
+            return 0;
+        };
+        set_len(block, dst_len as ChunkLen, &layout);
+        let rest = get_next_link(block, &layout);
+        set_next_handle(block, None, &layout);
+        let rest = rest.and_then(|link| arena.resolve_link(link));
+        self.tail = Some(dst);
+        self.len = live as BufLen;
+        #[cfg(feature = "bytebuffer-deque")]
+        {
+            self.read_offset = 0;
+        }
+        free_chain(arena, rest)
     }
 
-    /// Try to free head chunks that have been fully consumed
-    ///
-    /// This is called automatically by `pop_front()` and `remove_prefix()`.
-    #[cfg(feature = "bytebuffer-deque")]
-    fn try_free_head_chunks<A: Allocator>(&mut self, arena: &mut A) {
+    /// Checks that every chunk resolves in `arena` and that the chain ends at
+    /// the tail holding exactly this buffer's bytes
+    fn chain_is_intact<A: Allocator>(&self, arena: &A) -> bool {
         let layout = arena.bit_layout();
-
-        // Walk through chunks and free those that are fully consumed
-        while let Some(head) = self.head {
-            let block = match arena.get(head) {
-                Some(b) => b,
-                None => break,
+        let (mut total, mut last, mut next) = (0, None, self.head);
+        while let Some(h) = next {
+            let Some(block) = arena.get(h) else {
+                return false;
             };
+            total += get_len(block, &layout) as usize;
+            last = Some(h);
+            next = get_next_handle(arena, block, &layout);
+        }
+        last == self.tail && total == self.len as usize
+    }
 
-            let chunk_len = get_len(block, &layout) as u16;
+    /// Immutable read context
+    ///
+    /// The view covers the unread bytes only: bytes already consumed from the
+    /// front are skipped and readers stop after [`len`](Self::len) bytes.
+    pub fn read<'a, A: Allocator>(&'a self, arena: &'a A) -> ByteBufferRead<'a, A> {
+        ByteBufferRead {
+            arena,
+            buf: self,
+            start: self.start_offset(),
+            len: self.len() as usize,
+        }
+    }
 
-            if self.read_offset >= chunk_len {
-                // This chunk is fully consumed, free it
-                let next = get_next_handle(block, &layout);
+    /// Mutable write context
+    pub fn write<'a, A: Allocator>(&'a mut self, arena: &'a mut A) -> ByteBufferWrite<'a, A> {
+        ByteBufferWrite {
+            arena,
+            buf: self,
+            fmt_error: None,
+        }
+    }
 
-                // Now free the head node and update buffer internals
-                if arena.free(head) {
-                    self.read_offset -= chunk_len;
-                    self.head = next;
-                    if next.is_none() {
-                        self.tail = None;
-                    }
-                    self.len = self.len.saturating_sub(chunk_len);
-                } else {
-                    break;
-                }
-            } else {
-                // Head chunk still has unread data
+    /// Zero-copy transfer
+    pub fn move_from(&mut self, src: &mut Self) {
+        core::mem::swap(self, src);
+        src.head = None;
+        src.tail = None;
+        src.len = 0;
+        #[cfg(feature = "bytebuffer-deque")]
+        {
+            src.read_offset = 0;
+        }
+    }
+
+    /// Zero-copy drain to a new buffer - empties self and returns new buffer with the data
+    pub fn drain(&mut self) -> Self {
+        let mut drained = Self::new();
+        drained.move_from(self);
+        drained
+    }
+
+    /// Copies bytes from a specific range of another ByteBuffer into this one.
+    /// This allows reading from one buffer and appending to another using the same allocator.
+    ///
+    /// The range is relative to the source's readable data and is clamped to its
+    /// length. Bytes move through a small stack buffer, a chunk slice at a time.
+    pub fn copy_from<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        src: &Self,
+        range: core::ops::Range<usize>,
+    ) -> Result<(), ByteBufferError> {
+        let end = range.end.min(src.len() as usize);
+        let mut pos = range.start.min(end);
+        let mut cursor = ChunkCursor::seek(arena, src, pos);
+        let mut scratch = [0u8; COPY_SCRATCH];
+
+        while pos < end {
+            let want = (end - pos).min(COPY_SCRATCH);
+            let n = cursor.read(arena, &mut scratch[..want]);
+            if n == 0 {
                 break;
             }
+            self.write(arena).extend(&scratch[..n])?;
+            pos += n;
         }
+        Ok(())
     }
 
-    // ========================================================================
-    // Stream processing operations (enabled with feature = "bytebuffer-stream")
-    // ========================================================================
-
-    /// Peek at the nth byte from the front without consuming
+    /// Moves all bytes of `other` to the end of this buffer
     ///
-    /// Returns `None` if `n` is beyond the buffer length.
+    /// Links this buffer's tail chunk to `other`'s head chunk. If `other` has
+    /// consumed bytes at the front of its head chunk, only that chunk's unread
+    /// bytes are shifted down. When `other` fits in the spare room of this
+    /// buffer's tail chunk, its bytes are copied there and its chunks freed
+    /// instead, so appending small buffers does not leave partly filled chunks
+    /// behind. `other` is left empty but keeps its limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ByteBufferError::Full` if the combined length would exceed
+    /// `max_len`; the overflow policy is not applied and neither buffer changes.
     ///
     /// # Examples
     ///
     /// ```
-    /// # #[cfg(feature = "bytebuffer-stream")]
-    /// # {
     /// use tinyalloc::prelude::*;
     ///
     /// let mut alloc = TinySlabAllocator::<512, 16>::new();
-    /// let mut buf = ByteBuffer::new();
+    /// let mut a = ByteBuffer::new();
+    /// let mut b = ByteBuffer::new();
     ///
-    /// buf.write(&mut alloc).extend(b"Hello").unwrap();
+    /// a.write(&mut alloc).extend(b"Hello, ").unwrap();
+    /// b.write(&mut alloc).extend(b"World!").unwrap();
     ///
-    /// assert_eq!(buf.peek_n(&alloc, 0), Some(b'H'));
-    /// assert_eq!(buf.peek_n(&alloc, 4), Some(b'o'));
-    /// assert_eq!(buf.peek_n(&alloc, 5), None);
-    /// # }
+    /// a.append_buffer(&mut alloc, &mut b).unwrap();
+    /// assert_eq!(a.len(), 13);
+    /// assert!(b.is_empty());
+    /// assert_eq!(alloc.len(), 1); // Copied into a's tail chunk, b's freed
     /// ```
-    #[cfg(feature = "bytebuffer-stream")]
-    pub fn peek_n<A: Allocator>(&self, arena: &A, n: usize) -> Option<u8> {
-        if n >= self.len() as usize {
-            return None;
+    pub fn append_buffer<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        other: &mut ByteBuffer,
+    ) -> Result<(), ByteBufferError> {
+        if (other.len() as usize) > self.room() {
+            return Err(ByteBufferError::Full);
+        }
+        if other.is_empty() {
+            return Ok(());
+        }
+        if (other.len() as usize) <= self.tail_room(arena) {
+            self.copy_from(arena, other, 0..other.len() as usize)?;
+            free_chain(arena, other.head);
+            other.swap_contents(&mut ByteBuffer::new());
+            return Ok(());
+        }
+        other.normalize_head(arena)?;
+
+        match self.tail {
+            Some(tail) => {
+                let layout = arena.bit_layout();
+                let block = arena.get_mut(tail).ok_or(ByteBufferError::Uninitialized)?;
+                set_next_handle(block, other.head, &layout);
+                self.tail = other.tail;
+                self.len += other.len;
+            }
+            None => {
+                self.head = other.head;
+                self.tail = other.tail;
+                self.len = other.len;
+                #[cfg(feature = "bytebuffer-deque")]
+                {
+                    self.read_offset = 0;
+                }
+            }
         }
 
-        self.read(arena).bytes().nth(self.read_offset as usize + n)
+        other.swap_contents(&mut ByteBuffer::new());
+        Ok(())
     }
 
-    /// Remove bytes from the front while the predicate returns true
+    /// Splits the buffer at `at`: keeps bytes `0..at` and returns `at..`
     ///
-    /// Returns the number of bytes removed. Stops at the first byte
-    /// where the predicate returns false.
+    /// Whole chunks are relinked into the returned buffer. Only the chunk that
+    /// `at` falls inside is copied, which takes one allocation; on failure
+    /// the buffer is unchanged. The returned buffer has no length limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ByteBufferError::OutOfRange` if `at` is greater than the buffer length.
+    /// Returns `ByteBufferError::AllocationFailed` if the boundary chunk cannot be split.
     ///
     /// # Examples
     ///
     /// ```
-    /// # #[cfg(feature = "bytebuffer-stream")]
-    /// # {
     /// use tinyalloc::prelude::*;
     ///
     /// let mut alloc = TinySlabAllocator::<512, 16>::new();
     /// let mut buf = ByteBuffer::new();
     ///
-    /// buf.write(&mut alloc).extend(b"   Hello").unwrap();
+    /// buf.write(&mut alloc).extend(b"Hello, World!").unwrap();
     ///
-    /// // Skip whitespace
-    /// let skipped = buf.skip_while(&mut alloc, |b| b == b' ');
-    /// assert_eq!(skipped, 3);
-    /// assert_eq!(buf.peek_front(&alloc), Some(b'H'));
-    /// # }
+    /// let back = buf.split_at(&mut alloc, 7).unwrap();
+    /// assert_eq!(buf.len(), 7);  // "Hello, "
+    /// assert_eq!(back.len(), 6); // "World!"
     /// ```
-    #[cfg(feature = "bytebuffer-stream")]
-    pub fn skip_while<A: Allocator, F>(&mut self, arena: &mut A, predicate: F) -> usize
-    where
-        F: Fn(u8) -> bool,
-    {
-        let mut count = 0;
+    pub fn split_at<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        at: usize,
+    ) -> Result<ByteBuffer, ByteBufferError> {
+        self.split_chain(arena, at)
+    }
 
-        while let Some(byte) = self.peek_front(arena) {
-            if !predicate(byte) {
-                break;
-            }
-            self.pop_front(arena);
-            count += 1;
-        }
+    /* ---- Internal helpers ---- */
 
-        count
+    /// Bytes that can still be added before hitting `max_len` or the length type's limit
+    pub(super) fn room(&self) -> usize {
+        let limit = match self.max_len {
+            Some(max) => max.saturating_sub(self.len()),
+            None => BufLen::MAX,
+        };
+        limit.min(BufLen::MAX - self.len) as usize
     }
 
-    /// Remove bytes from front until (and including) the delimiter
-    ///
-    /// Returns the number of bytes consumed if the delimiter was found,
-    /// or `None` if the delimiter is not in the buffer.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// # #[cfg(feature = "bytebuffer-stream")]
-    /// # {
-    /// use tinyalloc::prelude::*;
+    /// Unused bytes at the end of the tail chunk
+    pub(super) fn tail_room<A: Allocator>(&self, arena: &A) -> usize {
+        let layout = arena.bit_layout();
+        self.tail
+            .and_then(|tail| arena.get(tail))
+            .map_or(0, |block| {
+                data_capacity(block, &layout).saturating_sub(get_len(block, &layout) as usize)
+            })
+    }
+
+    /// Exchanges chunk lists with `other`, leaving limits and policies in place
+    fn swap_contents(&mut self, other: &mut Self) {
+        core::mem::swap(&mut self.head, &mut other.head);
+        core::mem::swap(&mut self.tail, &mut other.tail);
+        core::mem::swap(&mut self.len, &mut other.len);
+        #[cfg(feature = "bytebuffer-deque")]
+        core::mem::swap(&mut self.read_offset, &mut other.read_offset);
+    }
+
+    /// Shifts unread head bytes to the start of the head chunk so the chunk
+    /// can be linked after another chunk
+    fn normalize_head<A: Allocator>(&mut self, arena: &mut A) -> Result<(), ByteBufferError> {
+        #[cfg(feature = "bytebuffer-deque")]
+        if self.read_offset > 0 {
+            let layout = arena.bit_layout();
+            let head = self.head.ok_or(ByteBufferError::Uninitialized)?;
+            let block = arena.get_mut(head).ok_or(ByteBufferError::Uninitialized)?;
+            let len = get_len(block, &layout) as usize;
+            let skip = self.read_offset as usize;
+            get_data_mut(block, &layout).copy_within(skip..len, 0);
+            set_len(block, (len - skip) as ChunkLen, &layout);
+            self.len -= self.read_offset;
+            self.read_offset = 0;
+        }
+        #[cfg(not(feature = "bytebuffer-deque"))]
+        let _ = arena;
+        Ok(())
+    }
+
+    /// Detaches bytes `at..` into a new buffer, relinking whole chunks
     ///
-    /// let mut alloc = TinySlabAllocator::<512, 16>::new();
-    /// let mut buf = ByteBuffer::new();
+    /// Only the chunk containing `at` is copied, and only when `at` falls
+    /// inside it. That copy needs one allocation, made before anything is
+    /// modified, so on failure `self` is unchanged.
+    fn split_chain<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        at: usize,
+    ) -> Result<ByteBuffer, ByteBufferError> {
+        let live = self.len() as usize;
+        if at > live {
+            return Err(ByteBufferError::OutOfRange);
+        }
+        let mut back = ByteBuffer::new();
+        if at == live {
+            return Ok(back);
+        }
+        if at == 0 {
+            self.swap_contents(&mut back);
+            return Ok(back);
+        }
+
+        let layout = arena.bit_layout();
+        let (prev, chunk, pos) = self.locate(arena, at)?;
+
+        let old_tail = self.tail;
+        let back_head = if pos == 0 {
+            // Split on a chunk boundary: just unlink
+            let prev = prev.ok_or(ByteBufferError::Uninitialized)?;
+            let block = arena.get_mut(prev).ok_or(ByteBufferError::Uninitialized)?;
+            set_next_handle(block, None, &layout);
+            self.tail = Some(prev);
+            back.tail = old_tail;
+            chunk
+        } else {
+            // Split inside a chunk: move its tail part into a fresh chunk
+            let fresh = self.alloc_node(arena, arena.block_size())?;
+            let (len, next) = {
+                let block = arena.get(chunk).ok_or(ByteBufferError::Uninitialized)?;
+                (
+                    get_len(block, &layout) as usize,
+                    get_next_handle(&*arena, block, &layout),
+                )
+            };
+            let mut scratch = [0u8; COPY_SCRATCH];
+            let mut from = pos;
+            while from < len {
+                let n = (len - from).min(COPY_SCRATCH);
+                let src = arena.get(chunk).ok_or(ByteBufferError::Uninitialized)?;
+                scratch[..n].copy_from_slice(&get_data(src, &layout)[from..from + n]);
+                let dst = arena.get_mut(fresh).ok_or(ByteBufferError::Uninitialized)?;
+                get_data_mut(dst, &layout)[from - pos..from - pos + n]
+                    .copy_from_slice(&scratch[..n]);
+                from += n;
+            }
+
+            let dst = arena.get_mut(fresh).ok_or(ByteBufferError::Uninitialized)?;
+            set_len(dst, (len - pos) as ChunkLen, &layout);
+            set_next_handle(dst, next, &layout);
+            let block = arena.get_mut(chunk).ok_or(ByteBufferError::Uninitialized)?;
+            set_len(block, pos as ChunkLen, &layout);
+            set_next_handle(block, None, &layout);
+
+            self.tail = Some(chunk);
+            back.tail = if old_tail == Some(chunk) {
+                Some(fresh)
+            } else {
+                old_tail
+            };
+            fresh
+        };
+
+        back.head = Some(back_head);
+        back.len = (live - at) as BufLen;
+        self.len = (self.start_offset() + at) as BufLen;
+        Ok(back)
+    }
+
+    /// Iterates over the unread part of each chunk
     ///
-    /// buf.write(&mut alloc).extend(b"Hello\nWorld").unwrap();
+    /// Unlike `read().chunks()` the slices borrow only the allocator.
+    fn live_chunks<'a, A: Allocator>(&self, arena: &'a A) -> ByteChunkIter<'a, A> {
+        ByteChunkIter::window(arena, self.head, self.start_offset(), self.len() as usize)
+    }
+
+    /// Finds the chunk holding live byte `at`
     ///
-    /// // Consume until newline
-    /// let consumed = buf.consume_until(&mut alloc, b'\n');
-    /// assert_eq!(consumed, Some(6)); // "Hello\n"
-    /// assert_eq!(buf.peek_front(&alloc), Some(b'W'));
-    /// # }
-    /// ```
-    #[cfg(feature = "bytebuffer-stream")]
-    pub fn consume_until<A: Allocator>(&mut self, arena: &mut A, delimiter: u8) -> Option<usize> {
-        // Find the delimiter position
-        let pos = self.position(arena, |b| b == delimiter)?;
+    /// Returns the chunk's predecessor, the chunk, and the byte's offset within
+    /// the chunk's data (counting consumed bytes for the head chunk).
+    fn locate<A: Allocator>(
+        &self,
+        arena: &A,
+        at: usize,
+    ) -> Result<(Option<Handle>, Handle, usize), ByteBufferError> {
+        let layout = arena.bit_layout();
+        let mut prev = None;
+        let mut chunk = self.head.ok_or(ByteBufferError::Uninitialized)?;
+        let mut pos = self.start_offset() + at;
+        loop {
+            let block = arena.get(chunk).ok_or(ByteBufferError::Uninitialized)?;
+            let len = get_len(block, &layout) as usize;
+            if pos < len {
+                return Ok((prev, chunk, pos));
+            }
+            pos -= len;
+            prev = Some(chunk);
+            chunk = get_next_handle(arena, block, &layout).ok_or(ByteBufferError::Uninitialized)?;
+        }
+    }
 
-        // Remove up to and including the delimiter
-        let consumed = self.remove_prefix(arena, pos + 1);
+    /// Offset of the first readable byte within the head chunk
+    #[inline(always)]
+    pub(super) fn start_offset(&self) -> usize {
+        #[cfg(feature = "bytebuffer-deque")]
+        {
+            self.read_offset as usize
+        }
+        #[cfg(not(feature = "bytebuffer-deque"))]
+        {
+            0
+        }
+    }
 
-        Some(consumed)
+    fn alloc_node<A: Allocator>(
+        &self,
+        arena: &mut A,
+        block_size: usize,
+    ) -> Result<Handle, ByteBufferError> {
+        if block_size < 2 {
+            return Err(ByteBufferError::AllocationFailed);
+        }
+
+        let layout = arena.bit_layout();
+        let (h, buf) = arena
+            .alloc_uninit(block_size)
+            .ok_or(ByteBufferError::AllocationFailed)?;
+
+        set_len(buf, 0, &layout);
+        set_next_handle(buf, None, &layout);
+        // Data area is already uninitialized, no need to zero it
+
+        Ok(h)
     }
 
-    /// Copy bytes from the front into a slice and consume them
+    // ========================================================================
+    // Deque-style operations (enabled with feature = "bytebuffer-deque")
+    // ========================================================================
+
+    /// Peek at the front byte without consuming it
     ///
-    /// Returns the number of bytes actually copied (which may be less
-    /// than `dest.len()` if the buffer contains fewer bytes).
+    /// Returns `None` if the buffer is empty.
     ///
     /// # Examples
     ///
     /// ```
-    /// # #[cfg(feature = "bytebuffer-stream")]
+    /// # #[cfg(feature = "bytebuffer-deque")]
     /// # {
     /// use tinyalloc::prelude::*;
     ///
     /// let mut alloc = TinySlabAllocator::<512, 16>::new();
     /// let mut buf = ByteBuffer::new();
     ///
-    /// buf.write(&mut alloc).extend(b"Hello, World!").unwrap();
+    /// buf.write(&mut alloc).extend(b"ABC").unwrap();
     ///
-    /// let mut dest = [0u8; 5];
-    /// let copied = buf.copy_prefix_to(&mut alloc, &mut dest);
-    /// assert_eq!(copied, 5);
-    /// assert_eq!(&dest, b"Hello");
-    /// assert_eq!(buf.len(), 8); // ", World!" remains
+    /// // Peek doesn't consume
+    /// assert_eq!(buf.peek_front(&alloc), Some(b'A'));
+    /// assert_eq!(buf.len(), 3);
+    /// assert_eq!(buf.peek_front(&alloc), Some(b'A')); // Still 'A'
     /// # }
     /// ```
-    #[cfg(feature = "bytebuffer-stream")]
-    pub fn copy_prefix_to<A: Allocator>(&mut self, arena: &mut A, dest: &mut [u8]) -> usize {
-        let to_copy = dest.len().min(self.len() as usize);
-
-        for (i, item) in dest.iter_mut().enumerate().take(to_copy) {
-            if let Some(byte) = self.pop_front(arena) {
-                *item = byte;
-            } else {
-                return i;
-            }
+    #[cfg(feature = "bytebuffer-deque")]
+    pub fn peek_front<A: Allocator>(&self, arena: &A) -> Option<u8> {
+        if self.is_empty() {
+            return None;
         }
 
-        to_copy
+        self.read(arena).bytes().next()
     }
 
-    /// Split off the front N bytes into a new ByteBuffer
+    /// Pop a byte from the front of the buffer
     ///
-    /// Creates a new buffer with the first `at` bytes. The original buffer
-    /// retains the remaining bytes. Returns an error if `at` is greater than
-    /// the buffer length.
+    /// Automatically frees fully-consumed chunks for efficient memory usage.
+    /// Returns `None` if the buffer is empty.
     ///
     /// # Examples
     ///
     /// ```
-    /// # #[cfg(feature = "bytebuffer-stream")]
+    /// # #[cfg(feature = "bytebuffer-deque")]
     /// # {
     /// use tinyalloc::prelude::*;
     ///
     /// let mut alloc = TinySlabAllocator::<512, 16>::new();
     /// let mut buf = ByteBuffer::new();
     ///
-    /// buf.write(&mut alloc).extend(b"Hello, World!").unwrap();
+    /// buf.write(&mut alloc).extend(b"Hello").unwrap();
     ///
-    /// let front = buf.split_off_front(&mut alloc, 5).unwrap();
-    /// assert_eq!(front.len(), 5); // "Hello"
-    /// assert_eq!(buf.len(), 8);   // ", World!" remains
+    /// assert_eq!(buf.pop_front(&mut alloc), Some(b'H'));
+    /// assert_eq!(buf.pop_front(&mut alloc), Some(b'e'));
+    /// assert_eq!(buf.len(), 3); // "llo" remains
     /// # }
     /// ```
-    #[cfg(feature = "bytebuffer-stream")]
-    pub fn split_off_front<A: Allocator>(
-        &mut self,
-        arena: &mut A,
-        at: usize,
-    ) -> Result<ByteBuffer, ByteBufferError> {
-        if at > self.len() as usize {
-            return Err(ByteBufferError::Full);
+    #[cfg(feature = "bytebuffer-deque")]
+    pub fn pop_front<A: Allocator>(&mut self, arena: &mut A) -> Option<u8> {
+        if self.is_empty() {
+            return None;
         }
 
-        let mut new_buf = ByteBuffer::new();
+        // Read the byte at current read offset
+        let byte = self.read(arena).bytes().next()?;
 
-        // Copy the first 'at' bytes to the new buffer
-        for _ in 0..at {
-            if let Some(byte) = self.pop_front(arena) {
-                new_buf.write(arena).append(byte)?;
-            }
-        }
+        self.read_offset += 1;
+
+        // Try to free fully-consumed head chunks
+        self.try_free_head_chunks(arena);
 
-        Ok(new_buf)
+        Some(byte)
     }
 
-    /// Move bytes from the front of this buffer to the back of another
+    /// Remove N bytes from the front of the buffer
     ///
-    /// Moves up to `count` bytes from the front of `self` to the back of `other`.
-    /// Returns the number of bytes actually moved.
+    /// This is more efficient than calling `pop_front()` N times as it
+    /// frees chunks in bulk. Returns the actual number of bytes removed.
     ///
     /// # Examples
     ///
     /// ```
-    /// # #[cfg(feature = "bytebuffer-stream")]
+    /// # #[cfg(feature = "bytebuffer-deque")]
     /// # {
     /// use tinyalloc::prelude::*;
     ///
     /// let mut alloc = TinySlabAllocator::<512, 16>::new();
-    /// let mut src = ByteBuffer::new();
-    /// let mut dst = ByteBuffer::new();
+    /// let mut buf = ByteBuffer::new();
     ///
-    /// src.write(&mut alloc).extend(b"Hello").unwrap();
-    /// dst.write(&mut alloc).extend(b"World").unwrap();
+    /// buf.write(&mut alloc).extend(b"Hello, World!").unwrap();
     ///
-    /// let moved = src.drain_into(&mut alloc, &mut dst, 3);
-    /// assert_eq!(moved, 3);
-    /// assert_eq!(src.len(), 2); // "lo" remains
-    /// assert_eq!(dst.len(), 8); // "WorldHel"
+    /// // Remove first 7 bytes ("Hello, ")
+    /// let removed = buf.remove_prefix(&mut alloc, 7);
+    /// assert_eq!(removed, 7);
+    /// assert_eq!(buf.len(), 6); // "World!" remains
+    ///
+    /// // Try to remove more than available
+    /// let removed = buf.remove_prefix(&mut alloc, 100);
+    /// assert_eq!(removed, 6); // Only removed what was available
+    /// assert_eq!(buf.len(), 0);
     /// # }
     /// ```
-    #[cfg(feature = "bytebuffer-stream")]
-    pub fn drain_into<A: Allocator>(
-        &mut self,
-        arena: &mut A,
-        other: &mut ByteBuffer,
-        count: usize,
-    ) -> usize {
-        let to_move = count.min(self.len() as usize);
-        let mut moved = 0;
+    #[cfg(feature = "bytebuffer-deque")]
+    pub fn remove_prefix<A: Allocator>(&mut self, arena: &mut A, count: usize) -> usize {
+        let available = self.len() as usize;
+        let to_remove = count.min(available);
 
-        for _ in 0..to_move {
-            if let Some(byte) = self.pop_front(arena) {
-                if other.write(arena).append(byte).is_ok() {
-                    moved += 1;
-                } else {
-                    // Can't append to destination, stop
-                    break;
-                }
-            } else {
-                break;
-            }
+        if to_remove == 0 {
+            return 0;
         }
 
-        moved
+        self.read_offset += to_remove as BufLen;
+        self.try_free_head_chunks(arena);
+
+        to_remove
     }
-}
 
-// ============================================================================
-// Global Deque Wrapper (enabled with features = "bytebuffer-deque" + "global-alloc")
-// ============================================================================
+    /// Try to free head chunks that have been fully consumed
+    ///
+    /// This is called automatically by `pop_front()` and `remove_prefix()`.
+    #[cfg(feature = "bytebuffer-deque")]
+    fn try_free_head_chunks<A: Allocator>(&mut self, arena: &mut A) {
+        let layout = arena.bit_layout();
 
-/// Global wrapper for ByteBuffer with deque operations (single-threaded, no mutex needed)
-///
-/// This provides a safe interface for sharing a ByteBuffer globally
-/// in single-threaded embedded contexts (e.g., between interrupt handlers
-/// and main code).
-///
-/// # Safety
-/// This is only safe in single-threaded contexts. Do not use with multiple
-/// threads or concurrent access.
-///
-/// # Examples
-///
-/// ```rust,no_run
-/// use tinyalloc::prelude::*;
-///
-/// static UART_QUEUE: GlobalDeque = GlobalDeque::new();
-///
-/// fn interrupt_handler() {
-///     let byte = read_uart_byte();
-///     UART_QUEUE.push(byte).ok();
-/// }
-///
-/// fn main() {
-///     GlobalAllocatorConfig::Slab512b16.init();
-///     
-///     loop {
-///         while let Some(byte) = UART_QUEUE.pop() {
-///             process_byte(byte);
-///         }
-///     }
-/// }
-/// # fn read_uart_byte() -> u8 { 0 }
-/// # fn process_byte(_: u8) {}
-/// ```
-#[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
-pub struct GlobalDeque {
-    inner: core::cell::UnsafeCell<ByteBuffer>,
-}
+        // Walk through chunks and free those that are fully consumed
+        while let Some(head) = self.head {
+            let block = match arena.get(head) {
+                Some(b) => b,
+                None => break,
+            };
 
-#[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
-unsafe impl Sync for GlobalDeque {}
+            let chunk_len = get_len(block, &layout) as BufLen;
 
-#[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
-impl GlobalDeque {
-    /// Create a new global deque
-    pub const fn new() -> Self {
-        Self {
-            inner: core::cell::UnsafeCell::new(ByteBuffer::new()),
+            if self.read_offset >= chunk_len {
+                // This chunk is fully consumed, free it
+                let next = get_next_handle(&*arena, block, &layout);
+
+                // Now free the head node and update buffer internals
+                if arena.free(head) {
+                    self.read_offset -= chunk_len;
+                    self.head = next;
+                    if next.is_none() {
+                        self.tail = None;
+                    }
+                    self.len = self.len.saturating_sub(chunk_len);
+                } else {
+                    break;
+                }
+            } else {
+                // Head chunk still has unread data
+                break;
+            }
         }
     }
 
-    /// Create a new global deque with maximum length
-    pub const fn with_max_len(max: u16) -> Self {
-        Self {
-            inner: core::cell::UnsafeCell::new(ByteBuffer::with_max_len(max)),
-        }
+    // ========================================================================
+    // Stream processing operations (enabled with feature = "bytebuffer-stream")
+    // ========================================================================
+
+    /// Peek at the nth byte from the front without consuming
+    ///
+    /// Returns `None` if `n` is beyond the buffer length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "bytebuffer-stream")]
+    /// # {
+    /// use tinyalloc::prelude::*;
+    ///
+    /// let mut alloc = TinySlabAllocator::<512, 16>::new();
+    /// let mut buf = ByteBuffer::new();
+    ///
+    /// buf.write(&mut alloc).extend(b"Hello").unwrap();
+    ///
+    /// assert_eq!(buf.peek_n(&alloc, 0), Some(b'H'));
+    /// assert_eq!(buf.peek_n(&alloc, 4), Some(b'o'));
+    /// assert_eq!(buf.peek_n(&alloc, 5), None);
+    /// # }
+    /// ```
+    #[cfg(feature = "bytebuffer-stream")]
+    pub fn peek_n<A: Allocator>(&self, arena: &A, n: usize) -> Option<u8> {
+        self.get(arena, n)
     }
 
-    /// Execute a closure with mutable access to the buffer
+    /// Remove bytes from the front while the predicate returns true
     ///
-    /// # Safety
-    /// Safe in single-threaded context. Caller must ensure no concurrent access.
-    #[inline]
-    pub fn with_mut<F, R>(&self, f: F) -> R
+    /// Returns the number of bytes removed. Stops at the first byte
+    /// where the predicate returns false.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "bytebuffer-stream")]
+    /// # {
+    /// use tinyalloc::prelude::*;
+    ///
+    /// let mut alloc = TinySlabAllocator::<512, 16>::new();
+    /// let mut buf = ByteBuffer::new();
+    ///
+    /// buf.write(&mut alloc).extend(b"   Hello").unwrap();
+    ///
+    /// // Skip whitespace
+    /// let skipped = buf.skip_while(&mut alloc, |b| b == b' ');
+    /// assert_eq!(skipped, 3);
+    /// assert_eq!(buf.peek_front(&alloc), Some(b'H'));
+    /// # }
+    /// ```
+    #[cfg(feature = "bytebuffer-stream")]
+    pub fn skip_while<A: Allocator, F>(&mut self, arena: &mut A, predicate: F) -> usize
     where
-        F: FnOnce(&mut ByteBuffer) -> R,
+        F: Fn(u8) -> bool,
     {
-        unsafe { f(&mut *self.inner.get()) }
-    }
+        let mut count = 0;
 
-    /// Push a byte using the global allocator
-    #[inline]
-    pub fn push(&self, byte: u8) -> Result<(), ByteBufferError> {
-        self.with_mut(|buf| {
-            crate::global::with_global_allocator(|alloc| buf.write(alloc).append(byte))
-        })
+        while let Some(byte) = self.peek_front(arena) {
+            if !predicate(byte) {
+                break;
+            }
+            self.pop_front(arena);
+            count += 1;
+        }
+
+        count
     }
 
-    /// Pop a byte using the global allocator
-    #[inline]
-    pub fn pop(&self) -> Option<u8> {
-        self.with_mut(|buf| crate::global::with_global_allocator(|alloc| buf.pop_front(alloc)))
+    /// Remove bytes from front until (and including) the delimiter
+    ///
+    /// Returns the number of bytes consumed if the delimiter was found,
+    /// or `None` if the delimiter is not in the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "bytebuffer-stream")]
+    /// # {
+    /// use tinyalloc::prelude::*;
+    ///
+    /// let mut alloc = TinySlabAllocator::<512, 16>::new();
+    /// let mut buf = ByteBuffer::new();
+    ///
+    /// buf.write(&mut alloc).extend(b"Hello\nWorld").unwrap();
+    ///
+    /// // Consume until newline
+    /// let consumed = buf.consume_until(&mut alloc, b'\n');
+    /// assert_eq!(consumed, Some(6)); // "Hello\n"
+    /// assert_eq!(buf.peek_front(&alloc), Some(b'W'));
+    /// # }
+    /// ```
+    #[cfg(feature = "bytebuffer-stream")]
+    pub fn consume_until<A: Allocator>(&mut self, arena: &mut A, delimiter: u8) -> Option<usize> {
+        // Find the delimiter position
+        let pos = self.position(arena, |b| b == delimiter)?;
+
+        // Remove up to and including the delimiter
+        let consumed = self.remove_prefix(arena, pos + 1);
+
+        Some(consumed)
     }
 
-    /// Peek at the front byte using the global allocator
-    #[inline]
-    pub fn peek(&self) -> Option<u8> {
-        self.with_mut(|buf| crate::global::with_global_allocator(|alloc| buf.peek_front(alloc)))
+    /// Copy bytes from the front into a slice and consume them
+    ///
+    /// Returns the number of bytes actually copied (which may be less
+    /// than `dest.len()` if the buffer contains fewer bytes).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "bytebuffer-stream")]
+    /// # {
+    /// use tinyalloc::prelude::*;
+    ///
+    /// let mut alloc = TinySlabAllocator::<512, 16>::new();
+    /// let mut buf = ByteBuffer::new();
+    ///
+    /// buf.write(&mut alloc).extend(b"Hello, World!").unwrap();
+    ///
+    /// let mut dest = [0u8; 5];
+    /// let copied = buf.copy_prefix_to(&mut alloc, &mut dest);
+    /// assert_eq!(copied, 5);
+    /// assert_eq!(&dest, b"Hello");
+    /// assert_eq!(buf.len(), 8); // ", World!" remains
+    /// # }
+    /// ```
+    #[cfg(feature = "bytebuffer-stream")]
+    pub fn copy_prefix_to<A: Allocator>(&mut self, arena: &mut A, dest: &mut [u8]) -> usize {
+        let to_copy = dest.len().min(self.len() as usize);
+
+        for (i, item) in dest.iter_mut().enumerate().take(to_copy) {
+            if let Some(byte) = self.pop_front(arena) {
+                *item = byte;
+            } else {
+                return i;
+            }
+        }
+
+        to_copy
     }
 
-    /// Get the current length
-    #[inline]
-    pub fn len(&self) -> u16 {
-        self.with_mut(|buf| buf.len())
+    /// Split off the front N bytes into a new ByteBuffer
+    ///
+    /// Creates a new buffer with the first `at` bytes. The original buffer
+    /// retains the remaining bytes. Returns an error if `at` is greater than
+    /// the buffer length. When `at` is a chunk boundary or at least a whole
+    /// chunk, chunks are relinked rather than copied; see
+    /// [`split_at`](Self::split_at). A shorter front ending inside a chunk is
+    /// copied into a fresh chunk, leaving the original chunks in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "bytebuffer-stream")]
+    /// # {
+    /// use tinyalloc::prelude::*;
+    ///
+    /// let mut alloc = TinySlabAllocator::<512, 16>::new();
+    /// let mut buf = ByteBuffer::new();
+    ///
+    /// buf.write(&mut alloc).extend(b"Hello, World!").unwrap();
+    ///
+    /// let front = buf.split_off_front(&mut alloc, 5).unwrap();
+    /// assert_eq!(front.len(), 5); // "Hello"
+    /// assert_eq!(buf.len(), 8);   // ", World!" remains
+    /// # }
+    /// ```
+    #[cfg(feature = "bytebuffer-stream")]
+    pub fn split_off_front<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        at: usize,
+    ) -> Result<ByteBuffer, ByteBufferError> {
+        if at > self.len() as usize {
+            return Err(ByteBufferError::Full);
+        }
+
+        if !self.relinks_front(arena, at) {
+            let mut front = ByteBuffer::new();
+            if let Err(e) = front.copy_from(arena, self, 0..at) {
+                free_chain(arena, front.head);
+                return Err(e);
+            }
+            self.remove_prefix(arena, at);
+            return Ok(front);
+        }
+
+        // Relink chunks: `self` keeps the back part, the front is returned
+        let mut front = self.split_chain(arena, at)?;
+        self.swap_contents(&mut front);
+
+        Ok(front)
+    }
+
+    /// Move bytes from the front of this buffer to the back of another
+    ///
+    /// Moves up to `count` bytes from the front of `self` to the back of `other`.
+    /// Returns the number of bytes actually moved. Chunks are relinked when
+    /// the move ends on a chunk boundary or covers at least a whole chunk;
+    /// smaller moves are copied into the spare room of `other`'s tail chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "bytebuffer-stream")]
+    /// # {
+    /// use tinyalloc::prelude::*;
+    ///
+    /// let mut alloc = TinySlabAllocator::<512, 16>::new();
+    /// let mut src = ByteBuffer::new();
+    /// let mut dst = ByteBuffer::new();
+    ///
+    /// src.write(&mut alloc).extend(b"Hello").unwrap();
+    /// dst.write(&mut alloc).extend(b"World").unwrap();
+    ///
+    /// let moved = src.drain_into(&mut alloc, &mut dst, 3);
+    /// assert_eq!(moved, 3);
+    /// assert_eq!(src.len(), 2); // "lo" remains
+    /// assert_eq!(dst.len(), 8); // "WorldHel"
+    /// # }
+    /// ```
+    #[cfg(feature = "bytebuffer-stream")]
+    pub fn drain_into<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        other: &mut ByteBuffer,
+        count: usize,
+    ) -> usize {
+        let to_move = count.min(self.len() as usize);
+
+        // Fast path: relink chunks when the destination can take everything
+        if to_move > 0
+            && to_move <= other.room()
+            && self.relinks_front(arena, to_move)
+            && let Ok(mut front) = self.split_off_front(arena, to_move)
+        {
+            if other.append_buffer(arena, &mut front).is_ok() {
+                return to_move;
+            }
+            // Put the bytes back in front of what is left
+            let _ = front.append_buffer(arena, self);
+            self.swap_contents(&mut front);
+        }
+
+        let mut moved = 0;
+        let mut scratch = [0u8; COPY_SCRATCH];
+
+        while moved < to_move {
+            let want = (to_move - moved).min(COPY_SCRATCH);
+            let n = ChunkCursor::seek(arena, self, 0).read(arena, &mut scratch[..want]);
+            if n == 0 {
+                break;
+            }
+
+            // Only consume what the destination accepted
+            let before = other.len();
+            let accepted = match other.write(arena).extend(&scratch[..n]) {
+                Ok(()) => n,
+                Err(_) => other.len().saturating_sub(before) as usize,
+            };
+            self.remove_prefix(arena, accepted);
+            moved += accepted;
+            if accepted < n {
+                break;
+            }
+        }
+
+        moved
+    }
+
+    /// Whether bytes `..at` should move by relinking chunks rather than copying
+    ///
+    /// Relinking pays off when `at` is a chunk boundary or at least a whole
+    /// chunk moves; splitting a chunk for fewer bytes would leave a partly
+    /// filled chunk behind.
+    #[cfg(feature = "bytebuffer-stream")]
+    fn relinks_front<A: Allocator>(&self, arena: &A, at: usize) -> bool {
+        at == 0
+            || at >= self.len() as usize
+            || at
+                >= arena
+                    .block_size()
+                    .saturating_sub(arena.bit_layout().header_len())
+            || matches!(self.locate(arena, at), Ok((_, _, 0)))
+    }
+}
+
+// ============================================================================
+// Global Allocator API (enabled with feature = "global-alloc")
+// ============================================================================
+
+#[cfg(feature = "global-alloc")]
+impl ByteBuffer {
+    /// Append a single byte using the global allocator
+    pub fn append(&mut self, byte: u8) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.write(alloc).append(byte))
+    }
+
+    /// Extend with multiple bytes using the global allocator
+    pub fn extend(&mut self, data: &[u8]) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.write(alloc).extend(data))
+    }
+
+    /// Append all of `data` or nothing using the global allocator
+    pub fn put_slice(&mut self, data: &[u8]) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.write(alloc).put_slice(data))
+    }
+
+    /// Clear all data using the global allocator
+    pub fn clear(&mut self) {
+        crate::global::with_global_allocator(|alloc| self.write(alloc).clear())
+    }
+
+    /// Iterate over bytes using the global allocator
+    pub fn bytes(&self) -> ByteBufferGlobalIter<'_> {
+        ByteBufferGlobalIter {
+            buf: self,
+            index: 0,
+        }
+    }
+
+    /// Copy from another buffer using global allocator
+    pub fn copy_from_global(
+        &mut self,
+        src: &Self,
+        range: core::ops::Range<usize>,
+    ) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.copy_from(alloc, src, range))
+    }
+
+    /// Move all bytes of another buffer to the end of this one (global allocator version)
+    pub fn append_buffer_global(&mut self, other: &mut ByteBuffer) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.append_buffer(alloc, other))
+    }
+
+    /// Split the buffer at `at`, returning the back part (global allocator version)
+    pub fn split_at_global(&mut self, at: usize) -> Result<ByteBuffer, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.split_at(alloc, at))
+    }
+
+    /// Returns the byte at `idx` using global allocator
+    pub fn get_global(&self, idx: usize) -> Option<u8> {
+        crate::global::with_global_allocator(|alloc| self.get(alloc, idx))
+    }
+
+    /// Overwrites the byte at `idx` using global allocator
+    pub fn set_global(&mut self, idx: usize, byte: u8) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.set(alloc, idx, byte))
+    }
+
+    /// Overwrites bytes starting at `offset` using global allocator
+    pub fn write_at_global(&mut self, offset: usize, data: &[u8]) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.write_at(alloc, offset, data))
+    }
+
+    /// Copies bytes starting at `offset` into `dest` using global allocator
+    pub fn copy_to_slice_global(&self, offset: usize, dest: &mut [u8]) -> usize {
+        crate::global::with_global_allocator(|alloc| self.copy_to_slice(alloc, offset, dest))
+    }
+
+    /// Shortens the buffer to `len` bytes using global allocator
+    pub fn truncate_global(&mut self, len: usize) {
+        crate::global::with_global_allocator(|alloc| self.truncate(alloc, len))
+    }
+
+    /// Removes and returns the last byte using global allocator
+    pub fn pop_back_global(&mut self) -> Option<u8> {
+        crate::global::with_global_allocator(|alloc| self.pop_back(alloc))
+    }
+
+    /// Prepends a byte using global allocator
+    pub fn push_front_global(&mut self, byte: u8) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.push_front(alloc, byte))
+    }
+
+    /// Inserts `data` before the byte at `idx` using global allocator
+    pub fn insert_global(&mut self, idx: usize, data: &[u8]) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.insert(alloc, idx, data))
+    }
+
+    /// Removes the bytes in `range` using global allocator
+    pub fn remove_global<R: RangeBounds<usize>>(
+        &mut self,
+        range: R,
+    ) -> Result<usize, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.remove(alloc, range))
+    }
+
+    /// Calls `f` with all unread bytes as one contiguous slice using global allocator
+    pub fn with_contiguous_global<R>(
+        &self,
+        scratch: &mut [u8],
+        f: impl FnOnce(&[u8]) -> R,
+    ) -> Result<R, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.with_contiguous(alloc, scratch, f))
+    }
+
+    /// Repacks the live bytes into as few chunks as possible using global allocator
+    pub fn compact_global(&mut self) -> usize {
+        crate::global::with_global_allocator(|alloc| self.compact(alloc))
+    }
+
+    /// Borrows a range as a [`ByteSlice`] using global allocator
+    pub fn slice_global<R: RangeBounds<usize>>(
+        &self,
+        range: R,
+    ) -> Result<ByteSlice, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.slice(alloc, range))
+    }
+
+    /// Finds the first byte position matching the predicate using global allocator
+    pub fn position_global<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(u8) -> bool,
+    {
+        crate::global::with_global_allocator(|alloc| self.position(alloc, predicate))
+    }
+
+    // ========================================================================
+    // Deque-style operations - Global API (enabled with features)
+    // ========================================================================
+
+    /// Peek at the front byte without consuming it (global allocator version)
+    #[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
+    pub fn peek_front_global(&self) -> Option<u8> {
+        crate::global::with_global_allocator(|alloc| self.peek_front(alloc))
+    }
+
+    /// Pop a byte from the front of the buffer (global allocator version)
+    #[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
+    pub fn pop_front_global(&mut self) -> Option<u8> {
+        crate::global::with_global_allocator(|alloc| self.pop_front(alloc))
+    }
+
+    /// Remove N bytes from the front of the buffer (global allocator version)
+    #[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
+    pub fn remove_prefix_global(&mut self, count: usize) -> usize {
+        crate::global::with_global_allocator(|alloc| self.remove_prefix(alloc, count))
+    }
+
+    // ========================================================================
+    // Stream processing operations - Global API (enabled with features)
+    // ========================================================================
+
+    /// Peek at the nth byte from the front (global allocator version)
+    #[cfg(all(feature = "bytebuffer-stream", feature = "global-alloc"))]
+    pub fn peek_n_global(&self, n: usize) -> Option<u8> {
+        crate::global::with_global_allocator(|alloc| self.peek_n(alloc, n))
+    }
+
+    /// Remove bytes from the front while predicate is true (global allocator version)
+    #[cfg(all(feature = "bytebuffer-stream", feature = "global-alloc"))]
+    pub fn skip_while_global<F>(&mut self, predicate: F) -> usize
+    where
+        F: Fn(u8) -> bool,
+    {
+        crate::global::with_global_allocator(|alloc| self.skip_while(alloc, predicate))
+    }
+
+    /// Remove bytes until delimiter is found (global allocator version)
+    #[cfg(all(feature = "bytebuffer-stream", feature = "global-alloc"))]
+    pub fn consume_until_global(&mut self, delimiter: u8) -> Option<usize> {
+        crate::global::with_global_allocator(|alloc| self.consume_until(alloc, delimiter))
+    }
+
+    /// Copy bytes from front into slice and consume them (global allocator version)
+    #[cfg(all(feature = "bytebuffer-stream", feature = "global-alloc"))]
+    pub fn copy_prefix_to_global(&mut self, dest: &mut [u8]) -> usize {
+        crate::global::with_global_allocator(|alloc| self.copy_prefix_to(alloc, dest))
+    }
+
+    /// Split off the front N bytes into a new ByteBuffer (global allocator version)
+    #[cfg(all(feature = "bytebuffer-stream", feature = "global-alloc"))]
+    pub fn split_off_front_global(&mut self, at: usize) -> Result<ByteBuffer, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.split_off_front(alloc, at))
+    }
+
+    /// Move bytes from front to back of another buffer (global allocator version)
+    #[cfg(all(feature = "bytebuffer-stream", feature = "global-alloc"))]
+    pub fn drain_into_global(&mut self, other: &mut ByteBuffer, count: usize) -> usize {
+        crate::global::with_global_allocator(|alloc| self.drain_into(alloc, other, count))
+    }
+}
+
+// ============================================================================
+// Global Deque Wrapper (enabled with features = "bytebuffer-deque" + "global-alloc")
+// ============================================================================
+
+/// Global wrapper for ByteBuffer with deque operations (single-threaded, no mutex needed)
+///
+/// This provides a safe interface for sharing a ByteBuffer globally
+/// in single-threaded embedded contexts (e.g., between interrupt handlers
+/// and main code).
+///
+/// # Safety
+/// This is only safe in single-threaded contexts. Do not use with multiple
+/// threads or concurrent access.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tinyalloc::prelude::*;
+///
+/// static UART_QUEUE: GlobalDeque = GlobalDeque::new();
+///
+/// fn interrupt_handler() {
+///     let byte = read_uart_byte();
+///     UART_QUEUE.push(byte).ok();
+/// }
+///
+/// fn main() {
+///     GlobalAllocatorConfig::Slab512b16.init();
+///     
+///     loop {
+///         while let Some(byte) = UART_QUEUE.pop() {
+///             process_byte(byte);
+///         }
+///     }
+/// }
+/// # fn read_uart_byte() -> u8 { 0 }
+/// # fn process_byte(_: u8) {}
+/// ```
+#[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
+pub struct GlobalDeque {
+    inner: core::cell::UnsafeCell<ByteBuffer>,
+    /// Consumer task waiting for data (woken by `push`)
+    #[cfg(feature = "deque-async")]
+    pub(super) waker: core::cell::UnsafeCell<Option<core::task::Waker>>,
+}
+
+#[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
+unsafe impl Sync for GlobalDeque {}
+
+#[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
+impl GlobalDeque {
+    /// Create a new global deque
+    pub const fn new() -> Self {
+        Self::from_buffer(ByteBuffer::new())
+    }
+
+    /// Create a new global deque with maximum length
+    pub const fn with_max_len(max: BufLen) -> Self {
+        Self::from_buffer(ByteBuffer::with_max_len(max))
+    }
+
+    /// Create a new global deque with maximum length and an overflow policy
+    ///
+    /// ```rust,no_run
+    /// use tinyalloc::prelude::*;
+    ///
+    /// // Keep the most recent 256 bytes of log output
+    /// static UART_LOG: GlobalDeque = GlobalDeque::with_overflow_policy(256, OverflowPolicy::DropOldest);
+    /// ```
+    pub const fn with_overflow_policy(max: BufLen, policy: OverflowPolicy) -> Self {
+        Self::from_buffer(ByteBuffer::with_overflow_policy(max, policy))
+    }
+
+    const fn from_buffer(buf: ByteBuffer) -> Self {
+        Self {
+            inner: core::cell::UnsafeCell::new(buf),
+            #[cfg(feature = "deque-async")]
+            waker: core::cell::UnsafeCell::new(None),
+        }
+    }
+
+    /// Execute a closure with mutable access to the buffer
+    ///
+    /// # Safety
+    /// Safe in single-threaded context. Caller must ensure no concurrent access.
+    #[inline]
+    pub fn with_mut<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut ByteBuffer) -> R,
+    {
+        unsafe { f(&mut *self.inner.get()) }
+    }
+
+    /// Push a byte using the global allocator
+    ///
+    /// With the `deque-async` feature, a successful push wakes the task
+    /// waiting in [`pop_async`](Self::pop_async) or [`read_until`](Self::read_until).
+    #[inline]
+    pub fn push(&self, byte: u8) -> Result<(), ByteBufferError> {
+        self.with_mut(|buf| {
+            crate::global::with_global_allocator(|alloc| buf.write(alloc).append(byte))
+        })?;
+        #[cfg(feature = "deque-async")]
+        self.wake();
+        Ok(())
+    }
+
+    /// Pop a byte using the global allocator
+    #[inline]
+    pub fn pop(&self) -> Option<u8> {
+        self.with_mut(|buf| crate::global::with_global_allocator(|alloc| buf.pop_front(alloc)))
+    }
+
+    /// Peek at the front byte using the global allocator
+    #[inline]
+    pub fn peek(&self) -> Option<u8> {
+        self.with_mut(|buf| crate::global::with_global_allocator(|alloc| buf.peek_front(alloc)))
+    }
+
+    /// Get the current length
+    #[inline]
+    pub fn len(&self) -> BufLen {
+        self.with_mut(|buf| buf.len())
+    }
+
+    /// Check if the deque is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.with_mut(|buf| buf.is_empty())
+    }
+
+    /// Clear all data using the global allocator
+    #[inline]
+    pub fn clear(&self) {
+        self.with_mut(|buf| crate::global::with_global_allocator(|alloc| buf.write(alloc).clear()))
+    }
+
+    /// Set the policy applied when the deque overflows
+    #[inline]
+    pub fn set_overflow_policy(&self, policy: OverflowPolicy) {
+        self.with_mut(|buf| buf.set_overflow_policy(policy))
+    }
+
+    /// Get the number of bytes lost to overflow
+    #[inline]
+    pub fn overruns(&self) -> BufLen {
+        self.with_mut(|buf| buf.overruns())
+    }
+
+    /// Reset the overrun counter, returning its previous value
+    #[inline]
+    pub fn reset_overruns(&self) -> BufLen {
+        self.with_mut(|buf| buf.reset_overruns())
+    }
+}
+
+#[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
+impl Default for GlobalDeque {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Iterator over bytes in a ByteBuffer using the global allocator
+#[cfg(feature = "global-alloc")]
+pub struct ByteBufferGlobalIter<'a> {
+    buf: &'a ByteBuffer,
+    index: usize,
+}
+
+#[cfg(feature = "global-alloc")]
+impl<'a> Iterator for ByteBufferGlobalIter<'a> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.buf.len() as usize {
+            return None;
+        }
+
+        let byte = crate::global::with_global_allocator(|alloc| {
+            self.buf.read(alloc).bytes().nth(self.index)
+        });
+
+        self.index += 1;
+        byte
+    }
+}
+
+// ============================================================================
+
+/// Read-only view of a ByteBuffer with an allocator
+///
+/// Provides methods to read data without modifying the buffer. The view is a
+/// window over the chunk list: it starts `start` bytes into the head chunk and
+/// covers `len` bytes, so consumed bytes and bytes past a `take()` limit are
+/// never visible.
+pub struct ByteBufferRead<'a, A: Allocator> {
+    /// Reference to the allocator
+    pub(crate) arena: &'a A,
+    /// Reference to the buffer
+    pub(crate) buf: &'a ByteBuffer,
+    /// Offset of the first visible byte within the head chunk
+    pub(crate) start: usize,
+    /// Number of visible bytes
+    pub(crate) len: usize,
+}
+
+impl<'a, A: Allocator> ByteBufferRead<'a, A> {
+    /// Returns the number of bytes in the view
+    pub fn len(&self) -> BufLen {
+        self.len as BufLen
+    }
+
+    /// Returns true if the view is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the chunks in the view
+    ///
+    /// The first and last slices are trimmed to the window.
+    pub fn chunks(&self) -> ByteChunkIter<'a, A> {
+        ByteChunkIter::window(self.arena, self.buf.head, self.start, self.len)
+    }
+
+    /// Returns an iterator over individual bytes in the view
+    pub fn bytes(&self) -> impl Iterator<Item = u8> + 'a {
+        self.chunks().flat_map(|c| c.iter().copied())
+    }
+
+    /// Finds the first byte position in the view matching the predicate
+    pub fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(u8) -> bool,
+    {
+        self.bytes().position(predicate)
+    }
+}
+/// Iterator over chunks in a ByteBuffer
+///
+/// Each item is a slice representing the visible part of one allocated chunk.
+/// Build one by hand with [`ByteChunkIter::new`].
+pub struct ByteChunkIter<'a, A: Allocator> {
+    /// Reference to the allocator
+    pub arena: &'a A,
+    /// Handle to the next chunk
+    pub next_handle: Option<Handle>,
+    /// Phantom data for lifetime
+    pub _marker: core::marker::PhantomData<A>,
+    /// Bytes still to skip at the start of the window
+    pub(crate) skip: usize,
+    /// Bytes left in the window
+    pub(crate) remaining: usize,
+}
+
+impl<'a, A: Allocator> ByteChunkIter<'a, A> {
+    /// Iterates over the full data of every chunk linked from `first`
+    pub fn new(arena: &'a A, first: Option<Handle>) -> Self {
+        Self::window(arena, first, 0, usize::MAX)
+    }
+
+    /// Iterates over `len` bytes starting `skip` bytes into chunk `first`
+    pub(crate) fn window(arena: &'a A, first: Option<Handle>, skip: usize, len: usize) -> Self {
+        ByteChunkIter {
+            arena,
+            next_handle: first,
+            _marker: core::marker::PhantomData,
+            skip,
+            remaining: len,
+        }
+    }
+}
+
+impl<'a, A: Allocator> Iterator for ByteChunkIter<'a, A> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let layout = self.arena.bit_layout();
+        while self.remaining > 0 {
+            let h = self.next_handle?;
+            let block = self.arena.get(h)?;
+            let data = get_data(block, &layout);
+            self.next_handle = get_next_handle(self.arena, block, &layout);
+
+            let skipped = self.skip.min(data.len());
+            self.skip -= skipped;
+            let data = &data[skipped..];
+            let data = &data[..data.len().min(self.remaining)];
+            self.remaining -= data.len();
+            if !data.is_empty() {
+                return Some(data);
+            }
+        }
+        None
+    }
+}
+
+/// Non-owning view of a byte range inside a ByteBuffer
+///
+/// Stores the chunk holding the first byte, the offset within that chunk and
+/// the length, so reading it never rescans the bytes before the range. Like
+/// any view it is only valid while the buffer's chunks are unchanged: do not
+/// use it after the buffer is cleared, popped or dropped.
+///
+/// Create one with [`ByteBuffer::slice`] and narrow it with
+/// [`ByteSlice::slice`].
+///
+/// # Examples
+///
+/// ```
+/// use tinyalloc::prelude::*;
+///
+/// let mut alloc = TinySlabAllocator::<1024, 32>::new();
+/// let mut buf = ByteBuffer::new();
+/// buf.write(&mut alloc).extend(b"GET /index.html HTTP/1.1").unwrap();
+///
+/// let target = buf.slice(&alloc, 4..15).unwrap();
+/// let name = target.slice(&alloc, 1..6).unwrap();
+/// assert!(name.bytes(&alloc).eq(b"index".iter().copied()));
+/// ```
+#[derive(Clone, Copy)]
+pub struct ByteSlice {
+    /// Position of the first byte
+    start: ChunkCursor,
+    /// Number of bytes in the view
+    len: BufLen,
+}
+
+impl ByteSlice {
+    /// Returns the number of bytes in the view
+    #[inline(always)]
+    pub fn len(&self) -> BufLen {
+        self.len
+    }
+
+    /// Returns true if the view is empty
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Narrows the view to `range`, relative to its own start
+    pub fn slice<A: Allocator, R: RangeBounds<usize>>(
+        &self,
+        arena: &A,
+        range: R,
+    ) -> Result<ByteSlice, ByteBufferError> {
+        let (start, end) = resolve_range(range, self.len as usize)?;
+        Ok(ByteSlice {
+            start: self.start.advance(arena, start),
+            len: (end - start) as BufLen,
+        })
+    }
+
+    /// Returns an iterator over the chunk slices covered by the view
+    pub fn chunks<'a, A: Allocator>(&self, arena: &'a A) -> ByteChunkIter<'a, A> {
+        ByteChunkIter::window(
+            arena,
+            self.start.chunk,
+            self.start.offset,
+            self.len as usize,
+        )
+    }
+
+    /// Returns an iterator over the bytes in the view
+    pub fn bytes<'a, A: Allocator>(&self, arena: &'a A) -> impl Iterator<Item = u8> + 'a {
+        self.chunks(arena).flat_map(|c| c.iter().copied())
+    }
+
+    /// Finds the first byte position in the view matching the predicate
+    pub fn position<A: Allocator, P>(&self, arena: &A, predicate: P) -> Option<usize>
+    where
+        P: Fn(u8) -> bool,
+    {
+        self.bytes(arena).position(predicate)
+    }
+
+    /// Copies the start of the view into `dest`
+    ///
+    /// Returns the number of bytes copied: the smaller of `dest.len()` and
+    /// the view length.
+    pub fn copy_to_slice<A: Allocator>(&self, arena: &A, dest: &mut [u8]) -> usize {
+        let n = dest.len().min(self.len as usize);
+        let mut cursor = self.start;
+        cursor.read(arena, &mut dest[..n])
+    }
+
+    /// Copies the start of the view into `dest` using global allocator
+    #[cfg(feature = "global-alloc")]
+    pub fn copy_to_slice_global(&self, dest: &mut [u8]) -> usize {
+        crate::global::with_global_allocator(|alloc| self.copy_to_slice(alloc, dest))
+    }
+}
+
+/// Resolves `range` against `len` into `(start, end)` offsets
+pub(super) fn resolve_range<R: RangeBounds<usize>>(
+    range: R,
+    len: usize,
+) -> Result<(usize, usize), ByteBufferError> {
+    let start = match range.start_bound() {
+        Bound::Included(&s) => s,
+        Bound::Excluded(&s) => s.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&e) => e.saturating_add(1),
+        Bound::Excluded(&e) => e,
+        Bound::Unbounded => len,
+    };
+    if start > end || end > len {
+        return Err(ByteBufferError::OutOfRange);
+    }
+    Ok((start, end))
+}
+
+/// Mutable view of a ByteBuffer with an allocator
+///
+/// Provides methods to modify the buffer contents.
+pub struct ByteBufferWrite<'a, A: Allocator> {
+    /// Mutable reference to the allocator
+    pub(crate) arena: &'a mut A,
+    /// Mutable reference to the buffer
+    pub(crate) buf: &'a mut ByteBuffer,
+    /// Error behind the last `fmt::Error` returned by `write_str`
+    pub(crate) fmt_error: Option<ByteBufferError>,
+}
+
+impl<'a, A: Allocator> ByteBufferWrite<'a, A> {
+    /// Clears the buffer, freeing all allocated chunks
+    pub fn clear(&mut self) {
+        let _ = free_chain(self.arena, self.buf.head);
+        self.buf.head = None;
+        self.buf.tail = None;
+        self.buf.len = 0;
+        #[cfg(feature = "bytebuffer-deque")]
+        {
+            self.buf.read_offset = 0;
+        }
+    }
+
+    /// Appends a single byte to the buffer
+    ///
+    /// When the buffer is full the buffer's [`OverflowPolicy`] decides whether
+    /// the byte is rejected or replaces older data.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ByteBufferError::Full` if maximum length is reached.
+    /// Returns `ByteBufferError::AllocationFailed` if allocator is out of memory.
+    /// Neither is returned when the overflow policy managed to store the byte.
+    pub fn append(&mut self, b: u8) -> Result<(), ByteBufferError> {
+        match self.push_byte(b) {
+            Err(err @ (ByteBufferError::Full | ByteBufferError::AllocationFailed)) => {
+                self.overflow(b, err)
+            }
+            other => other,
+        }
+    }
+
+    /// Applies the buffer's overflow policy after `push_byte` failed with `err`
+    #[cfg_attr(not(feature = "bytebuffer-deque"), allow(unused_variables))]
+    fn overflow(&mut self, b: u8, err: ByteBufferError) -> Result<(), ByteBufferError> {
+        match self.buf.overflow {
+            OverflowPolicy::RejectNewest => {
+                self.buf.overruns = self.buf.overruns.saturating_add(1);
+                Err(err)
+            }
+            #[cfg(feature = "bytebuffer-deque")]
+            OverflowPolicy::DropOldest => {
+                let mut err = err;
+                loop {
+                    let evict = match err {
+                        ByteBufferError::Full => 1,
+                        _ => self.head_chunk_remaining(),
+                    };
+                    let evicted = self.buf.remove_prefix(self.arena, evict);
+                    if evicted == 0 {
+                        self.buf.overruns = self.buf.overruns.saturating_add(1);
+                        return Err(err);
+                    }
+                    self.buf.overruns = self.buf.overruns.saturating_add(evicted as BufLen);
+
+                    match self.push_byte(b) {
+                        Err(e @ (ByteBufferError::Full | ByteBufferError::AllocationFailed)) => {
+                            err = e
+                        }
+                        other => return other,
+                    }
+                }
+            }
+            #[cfg(feature = "bytebuffer-deque")]
+            OverflowPolicy::Overwrite => {
+                self.add_overruns(1);
+                if self.buf.is_empty() {
+                    return Err(err);
+                }
+                // The oldest byte makes way for the new one
+                self.buf.read_offset += 1;
+                if self.head_chunk_remaining() == 0
+                    || (self.buf.tail_room(self.arena) == 0 && !self.ring_may_grow())
+                {
+                    self.recycle_head()?;
+                }
+
+                match self.push_byte(b) {
+                    Err(ByteBufferError::AllocationFailed | ByteBufferError::Full) => {
+                        self.recycle_head()?;
+                        self.push_byte(b)
+                    }
+                    other => other,
+                }
+            }
+        }
+    }
+
+    /// Adds `n` lost bytes to the overrun counter, saturating
+    fn add_overruns(&mut self, n: usize) {
+        let n = n.min(BufLen::MAX as usize) as BufLen;
+        self.buf.overruns = self.buf.overruns.saturating_add(n);
+    }
+
+    /// Whether an `Overwrite` buffer may allocate another chunk
+    ///
+    /// A bounded ring stops at one chunk more than `max_len` needs, enough to
+    /// keep writing while the head chunk is drained byte by byte.
+    #[cfg(feature = "bytebuffer-deque")]
+    fn ring_may_grow(&self) -> bool {
+        let Some(max) = self.buf.max_len else {
+            return true;
+        };
+        let layout = self.arena.bit_layout();
+        let cap = self
+            .arena
+            .block_size()
+            .saturating_sub(layout.header_len())
+            .max(1);
+        let mut chunks = 0;
+        let mut next = self.buf.head;
+        while let Some(h) = next {
+            chunks += 1;
+            next = self
+                .arena
+                .get(h)
+                .and_then(|block| get_next_handle(&*self.arena, block, &layout));
+        }
+        chunks < (max as usize).div_ceil(cap) + 1
+    }
+
+    /// Empties the head chunk and reuses it as the tail, counting its unread
+    /// bytes as overruns
+    ///
+    /// A single-chunk buffer is reset in place.
+    #[cfg(feature = "bytebuffer-deque")]
+    fn recycle_head(&mut self) -> Result<(), ByteBufferError> {
+        let layout = self.arena.bit_layout();
+        let head = self.buf.head.ok_or(ByteBufferError::AllocationFailed)?;
+        let block = self.arena.get(head).ok_or(ByteBufferError::Uninitialized)?;
+        let chunk_len = get_len(block, &layout) as BufLen;
+        let next = get_next_handle(&*self.arena, block, &layout);
+
+        if self.buf.tail != Some(head) {
+            let tail = self.buf.tail.ok_or(ByteBufferError::Uninitialized)?;
+            let block = self
+                .arena
+                .get_mut(tail)
+                .ok_or(ByteBufferError::Uninitialized)?;
+            set_next_handle(block, Some(head), &layout);
+            self.buf.head = next;
+            self.buf.tail = Some(head);
+        }
+        let block = self
+            .arena
+            .get_mut(head)
+            .ok_or(ByteBufferError::Uninitialized)?;
+        set_len(block, 0, &layout);
+        set_next_handle(block, None, &layout);
+
+        self.add_overruns(chunk_len.saturating_sub(self.buf.read_offset) as usize);
+        self.buf.len -= chunk_len;
+        self.buf.read_offset = 0;
+        Ok(())
+    }
+
+    /// Number of unread bytes left in the head chunk
+    #[cfg(feature = "bytebuffer-deque")]
+    fn head_chunk_remaining(&self) -> usize {
+        let layout = self.arena.bit_layout();
+        self.buf
+            .head
+            .and_then(|h| self.arena.get(h))
+            .map(|block| (get_len(block, &layout) as BufLen).saturating_sub(self.buf.read_offset))
+            .unwrap_or(0) as usize
+    }
+
+    /// Appends a byte without applying the overflow policy
+    fn push_byte(&mut self, b: u8) -> Result<(), ByteBufferError> {
+        if self.room() == 0 {
+            return Err(ByteBufferError::Full);
+        }
+        self.write_tail(&[b]).map(|_| ())
+    }
+
+    /// Bytes that can still be added before hitting `max_len` or the length type's limit
+    fn room(&self) -> usize {
+        self.buf.room()
+    }
+
+    /// Copies as much of `data` as fits into the tail chunk, allocating a new
+    /// tail first if the current one is full
+    ///
+    /// Returns the number of bytes written. Does not check `max_len`.
+    fn write_tail(&mut self, data: &[u8]) -> Result<usize, ByteBufferError> {
+        let layout = self.arena.bit_layout();
+
+        let tail = match self.buf.tail {
+            Some(tail) => {
+                let block = self.arena.get(tail).ok_or(ByteBufferError::Uninitialized)?;
+                if (get_len(block, &layout) as usize) < data_capacity(block, &layout) {
+                    tail
+                } else {
+                    self.grow()?
+                }
+            }
+            None => self.grow()?,
+        };
+
+        let block = self
+            .arena
+            .get_mut(tail)
+            .ok_or(ByteBufferError::Uninitialized)?;
+        let len = get_len(block, &layout) as usize;
+        let n = data.len().min(data_capacity(block, &layout) - len);
+        get_data_mut(block, &layout)[len..len + n].copy_from_slice(&data[..n]);
+        set_len(block, (len + n) as ChunkLen, &layout);
+
+        self.buf.len += n as BufLen;
+        Ok(n)
+    }
+
+    /// Allocates an empty chunk and links it after the current tail
+    fn grow(&mut self) -> Result<Handle, ByteBufferError> {
+        let layout = self.arena.bit_layout();
+        // Request allocator's block size
+        let block_size = self.arena.block_size();
+        let new = self.buf.alloc_node(self.arena, block_size)?;
+
+        match self.buf.tail {
+            Some(tail) => {
+                // Update the old tail to point to new node
+                let Some(block) = self.arena.get_mut(tail) else {
+                    self.arena.free(new);
+                    return Err(ByteBufferError::Uninitialized);
+                };
+                set_next_handle(block, Some(new), &layout);
+            }
+            None => self.buf.head = Some(new),
+        }
+
+        self.buf.tail = Some(new);
+        Ok(new)
+    }
+
+    /// Appends all of `data` or nothing
+    ///
+    /// Unlike [`extend`](Self::extend), the overflow policy does not apply:
+    /// if `data` does not fit, nothing is written, so encoded values are
+    /// never cut in half.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ByteBufferError::Full` if `data` would exceed `max_len` and
+    /// `ByteBufferError::AllocationFailed` if the allocator runs out; the
+    /// buffer is unchanged in both cases and all of `data` counts as overruns.
+    pub fn put_slice(&mut self, data: &[u8]) -> Result<(), ByteBufferError> {
+        if data.len() > self.room() {
+            self.add_overruns(data.len());
+            return Err(ByteBufferError::Full);
+        }
+        let before = self.buf.len() as usize;
+        if let Err(e) = self.extend_exact(data) {
+            self.buf.truncate(self.arena, before);
+            if e == ByteBufferError::AllocationFailed {
+                self.add_overruns(data.len());
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Appends all of `s` without applying the overflow policy
+    ///
+    /// Callers check `room()` first; only allocation can fail part way.
+    fn extend_exact(&mut self, s: &[u8]) -> Result<(), ByteBufferError> {
+        let mut rest = s;
+        while !rest.is_empty() {
+            let n = self.write_tail(rest)?;
+            rest = &rest[n..];
+        }
+        Ok(())
+    }
+
+    /// Appends a slice of bytes to the buffer
+    ///
+    /// Fills the tail chunk's remaining capacity with one copy per chunk. If the
+    /// buffer overflows part way, the remaining bytes go through [`append`](Self::append)
+    /// one at a time so the overflow policy applies exactly as for single bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ByteBufferError::Full` if maximum length would be exceeded.
+    /// Returns `ByteBufferError::AllocationFailed` if allocator is out of memory.
+    /// Bytes before the failing one have been appended; it and every byte after
+    /// it count as overruns.
+    pub fn extend(&mut self, s: &[u8]) -> Result<(), ByteBufferError> {
+        let mut rest = s;
+        while !rest.is_empty() {
+            let room = self.room();
+            let written = if room == 0 {
+                0
+            } else {
+                let take = rest.len().min(room);
+                match self.write_tail(&rest[..take]) {
+                    Ok(n) => n,
+                    Err(ByteBufferError::AllocationFailed) => 0,
+                    Err(e) => return Err(e),
+                }
+            };
+
+            if written == 0 {
+                if let Err(e) = self.append(rest[0]) {
+                    // The bytes after the rejected one are lost too
+                    self.add_overruns(rest.len() - 1);
+                    return Err(e);
+                }
+                rest = &rest[1..];
+            } else {
+                rest = &rest[written..];
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Frees `first` and every chunk linked after it, returning the number freed
+fn free_chain<A: Allocator>(arena: &mut A, first: Option<Handle>) -> usize {
+    let layout = arena.bit_layout();
+    let mut next = first;
+    let mut freed = 0;
+    while let Some(h) = next {
+        next = arena
+            .get(h)
+            .and_then(|block| get_next_handle(&*arena, block, &layout));
+        if arena.free(h) {
+            freed += 1;
+        }
+    }
+    freed
+}
+
+/// Scratch size for copies between two buffers sharing one allocator
+const COPY_SCRATCH: usize = 64;
+
+/// Read position inside a buffer's chunk list
+///
+/// Seeking skips whole chunks using their stored lengths, so positioning costs
+/// one header read per chunk instead of one step per byte.
+#[derive(Clone, Copy)]
+pub(super) struct ChunkCursor {
+    /// Chunk holding the next byte
+    pub(super) chunk: Option<Handle>,
+    /// Offset of the next byte within the chunk's data
+    pub(super) offset: usize,
+}
+
+impl ChunkCursor {
+    /// Positions a cursor `pos` bytes into the readable data of `buf`
+    pub(super) fn seek<A: Allocator>(arena: &A, buf: &ByteBuffer, pos: usize) -> Self {
+        let cursor = ChunkCursor {
+            chunk: buf.head,
+            offset: buf.start_offset(),
+        };
+        cursor.advance(arena, pos)
+    }
+
+    /// Moves the cursor `n` bytes forward
+    pub(super) fn advance<A: Allocator>(mut self, arena: &A, n: usize) -> Self {
+        let layout = arena.bit_layout();
+        self.offset += n;
+        while let Some(h) = self.chunk {
+            let Some(block) = arena.get(h) else { break };
+            let len = get_len(block, &layout) as usize;
+            if self.offset < len {
+                break;
+            }
+            match get_next_handle(arena, block, &layout) {
+                Some(next) => {
+                    self.offset -= len;
+                    self.chunk = Some(next);
+                }
+                None => break,
+            }
+        }
+        self
+    }
+
+    /// Copies bytes into `dest` and advances, returning the number copied
+    pub(super) fn read<A: Allocator>(&mut self, arena: &A, dest: &mut [u8]) -> usize {
+        let layout = arena.bit_layout();
+        let mut copied = 0;
+        while copied < dest.len() {
+            let Some(h) = self.chunk else { break };
+            let Some(block) = arena.get(h) else { break };
+            let data = get_data(block, &layout);
+            if self.offset >= data.len() {
+                match get_next_handle(arena, block, &layout) {
+                    Some(next) => {
+                        self.chunk = Some(next);
+                        self.offset = 0;
+                        continue;
+                    }
+                    None => break,
+                }
+            }
+            let n = (dest.len() - copied).min(data.len() - self.offset);
+            dest[copied..copied + n].copy_from_slice(&data[self.offset..self.offset + n]);
+            copied += n;
+            self.offset += n;
+        }
+        copied
+    }
+
+    /// Returns the next byte and advances, or `None` at the end of the chain
+    pub(super) fn next_byte<A: Allocator>(&mut self, arena: &A) -> Option<u8> {
+        let mut byte = [0u8];
+        (self.read(arena, &mut byte) == 1).then_some(byte[0])
+    }
+
+    /// Overwrites stored bytes with `src` and advances, returning the number written
+    ///
+    /// Chunk lengths never change: writing stops at the end of the last chunk.
+    pub(super) fn write<A: Allocator>(&mut self, arena: &mut A, src: &[u8]) -> usize {
+        let layout = arena.bit_layout();
+        let mut written = 0;
+        while written < src.len() {
+            let Some(h) = self.chunk else { break };
+            let Some(block) = arena.get(h) else { break };
+            let len = get_len(block, &layout) as usize;
+            if self.offset >= len {
+                match get_next_handle(&*arena, block, &layout) {
+                    Some(next) => {
+                        self.chunk = Some(next);
+                        self.offset = 0;
+                        continue;
+                    }
+                    None => break,
+                }
+            }
+            let n = (src.len() - written).min(len - self.offset);
+            let Some(block) = arena.get_mut(h) else { break };
+            get_data_mut(block, &layout)[self.offset..self.offset + n]
+                .copy_from_slice(&src[written..written + n]);
+            written += n;
+            self.offset += n;
+        }
+        written
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "tinyslab")]
+mod tests {
+    use super::*;
+    use crate::backend::tinyslab::TinySlabAllocator;
+
+    // Helper to collect the readable bytes into fixed array
+    fn collect_bytes<A: Allocator>(buf: &ByteBuffer, arena: &A) -> ([u8; 256], usize) {
+        let mut result = [0u8; 256];
+        let mut count = 0;
+        for (i, b) in buf.read(arena).bytes().enumerate() {
+            if i >= 256 {
+                break;
+            }
+            result[i] = b;
+            count += 1;
+        }
+        (result, count)
+    }
+
+    #[test]
+    fn test_bytebuffer_new() {
+        let buf = ByteBuffer::new();
+        assert_eq!(buf.len(), 0);
+        assert!(buf.is_empty());
+        assert_eq!(buf.max_len(), None);
+    }
+
+    #[test]
+    fn test_bytebuffer_with_max_len() {
+        let buf = ByteBuffer::with_max_len(100);
+        assert_eq!(buf.max_len(), Some(100));
+        assert_eq!(buf.len(), 0);
+    }
+
+    #[test]
+    fn test_bytebuffer_append_single_byte() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        buf.write(&mut arena).append(42).unwrap();
+
+        assert_eq!(buf.len(), 1);
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(count, 1);
+        assert_eq!(bytes[0], 42);
+    }
+
+    #[test]
+    fn test_bytebuffer_append_multiple_bytes() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        let data = b"Hello";
+        buf.write(&mut arena).extend(data).unwrap();
+
+        assert_eq!(buf.len(), 5);
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(count, 5);
+        assert_eq!(&bytes[..5], data);
+    }
+
+    #[test]
+    fn test_bytebuffer_append_exceeds_chunk() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        let data = b"Hello World!"; // 12 bytes
+        buf.write(&mut arena).extend(data).unwrap();
+
+        assert_eq!(buf.len(), 12);
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(count, 12);
+        assert_eq!(&bytes[..12], data);
+    }
+
+    #[test]
+    fn test_chunk_iter_new_walks_whole_chunks() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+        buf.write(&mut arena).extend(&[7u8; 70]).unwrap();
+
+        let mut lens = ByteChunkIter::new(&arena, buf.head).map(|c| c.len());
+        assert_eq!(lens.next(), Some(30));
+        assert_eq!(lens.next(), Some(30));
+        assert_eq!(lens.next(), Some(10));
+        assert_eq!(lens.next(), None);
+        assert_eq!(ByteChunkIter::new(&arena, None).count(), 0);
+    }
+
+    #[test]
+    fn test_bytebuffer_max_len_enforcement() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::with_max_len(5);
+
+        let result = buf.write(&mut arena).extend(b"Hello");
+        assert!(result.is_ok());
+        assert_eq!(buf.len(), 5);
+
+        // Try to append one more
+        let result = buf.write(&mut arena).append(b'!');
+        assert_eq!(result, Err(ByteBufferError::Full));
+        assert_eq!(buf.len(), 5);
+    }
+
+    #[test]
+    fn test_bytebuffer_clear() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        buf.write(&mut arena).extend(b"Hello World!").unwrap();
+        assert_eq!(buf.len(), 12);
+
+        buf.write(&mut arena).clear();
+        assert_eq!(buf.len(), 0);
+        assert!(buf.is_empty());
+
+        let (_, count) = collect_bytes(&buf, &arena);
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_bytebuffer_move_from() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut src = ByteBuffer::new();
+        let mut dst = ByteBuffer::new();
+
+        src.write(&mut arena).extend(b"Source data").unwrap();
+        assert_eq!(src.len(), 11);
+
+        dst.move_from(&mut src);
+
+        // dst should have the data
+        assert_eq!(dst.len(), 11);
+        let (bytes, count) = collect_bytes(&dst, &arena);
+        assert_eq!(count, 11);
+        assert_eq!(&bytes[..11], b"Source data");
+
+        // src should be empty
+        assert_eq!(src.len(), 0);
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn test_bytebuffer_drain() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        buf.write(&mut arena).extend(b"Drain this!").unwrap();
+        assert_eq!(buf.len(), 11);
+
+        // Drain to new buffer
+        let drained = buf.drain();
+
+        // Original should be empty
+        assert_eq!(buf.len(), 0);
+        assert!(buf.is_empty());
+
+        // Drained should have the data
+        assert_eq!(drained.len(), 11);
+        let (bytes, count) = collect_bytes(&drained, &arena);
+        assert_eq!(count, 11);
+        assert_eq!(&bytes[..11], b"Drain this!");
+    }
+
+    #[test]
+    fn test_bytebuffer_copy_from() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut src = ByteBuffer::new();
+        let mut dst = ByteBuffer::new();
+
+        src.write(&mut arena).extend(b"0123456789").unwrap();
+
+        // Copy middle range
+        dst.copy_from(&mut arena, &src, 3..7).unwrap();
+
+        assert_eq!(dst.len(), 4);
+        let (bytes, count) = collect_bytes(&dst, &arena);
+        assert_eq!(count, 4);
+        assert_eq!(&bytes[..4], b"3456");
+
+        // Source should be unchanged
+        assert_eq!(src.len(), 10);
+    }
+
+    #[test]
+    #[allow(clippy::needless_range_loop)]
+    fn test_bytebuffer_large_data() {
+        let mut arena = TinySlabAllocator::<4096, 128>::new();
+        let mut buf = ByteBuffer::new();
+
+        // Write 100 bytes
+        let mut data = [0u8; 100];
+        for i in 0..100 {
+            data[i] = (i % 256) as u8;
+        }
+        buf.write(&mut arena).extend(&data).unwrap();
+
+        assert_eq!(buf.len(), 100);
+
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(count, 100);
+        assert_eq!(&bytes[..100], &data[..]);
+    }
+
+    #[test]
+    fn test_bytebuffer_extend_fills_partial_tail() {
+        // 30-byte chunks
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        buf.write(&mut arena).extend(b"0123456789").unwrap();
+        let mut data = [0u8; 100];
+        for (i, b) in data.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        buf.write(&mut arena).extend(&data).unwrap();
+
+        // 110 bytes fill exactly four chunks, no partially used chunk in between
+        assert_eq!(buf.len(), 110);
+        assert_eq!(arena.len(), 4);
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(&bytes[..10], b"0123456789");
+        assert_eq!(&bytes[10..count], &data[..]);
+    }
+
+    #[test]
+    fn test_bytebuffer_extend_stops_at_max_len() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::with_max_len(40);
+
+        let result = buf.write(&mut arena).extend(&[7u8; 50]);
+        assert_eq!(result, Err(ByteBufferError::Full));
+        assert_eq!(buf.len(), 40);
+        assert_eq!(buf.overruns(), 10);
+    }
+
+    #[test]
+    fn test_bytebuffer_set_max_len() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        assert_eq!(buf.max_len(), None);
+
+        buf.set_max_len(Some(10));
+        assert_eq!(buf.max_len(), Some(10));
+
+        buf.write(&mut arena).extend(b"12345").unwrap();
+        assert_eq!(buf.len(), 5);
+
+        // Can add 5 more to reach limit
+        buf.write(&mut arena).extend(b"67890").unwrap();
+        assert_eq!(buf.len(), 10);
+
+        // Try to append one more - should fail
+        let result = buf.write(&mut arena).append(b'X');
+        assert_eq!(result, Err(ByteBufferError::Full));
+        assert_eq!(buf.len(), 10);
+
+        // Remove limit
+        buf.set_max_len(None);
+        buf.write(&mut arena).extend(b"ABCDEFGH").unwrap();
+        assert_eq!(buf.len(), 18);
+    }
+
+    #[test]
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_bytebuffer_pop_front() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        buf.write(&mut arena).extend(b"ABCDE").unwrap();
+        assert_eq!(buf.len(), 5);
+
+        // Pop bytes one by one
+        assert_eq!(buf.pop_front(&mut arena), Some(b'A'));
+        assert_eq!(buf.len(), 4);
+
+        assert_eq!(buf.pop_front(&mut arena), Some(b'B'));
+        assert_eq!(buf.len(), 3);
+
+        assert_eq!(buf.pop_front(&mut arena), Some(b'C'));
+        assert_eq!(buf.pop_front(&mut arena), Some(b'D'));
+        assert_eq!(buf.pop_front(&mut arena), Some(b'E'));
+        assert_eq!(buf.len(), 0);
+
+        // Pop from empty buffer
+        assert_eq!(buf.pop_front(&mut arena), None);
+    }
+
+    #[test]
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_bytebuffer_peek_front() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        buf.write(&mut arena).extend(b"XYZ").unwrap();
+
+        // Peek doesn't consume
+        assert_eq!(buf.peek_front(&arena), Some(b'X'));
+        assert_eq!(buf.len(), 3);
+        assert_eq!(buf.peek_front(&arena), Some(b'X'));
+
+        // Pop and peek again
+        buf.pop_front(&mut arena);
+        assert_eq!(buf.peek_front(&arena), Some(b'Y'));
+    }
+
+    #[test]
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_bytebuffer_remove_prefix() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        buf.write(&mut arena).extend(b"Hello, World!").unwrap();
+        assert_eq!(buf.len(), 13);
+
+        // Remove first 7 bytes ("Hello, ")
+        let removed = buf.remove_prefix(&mut arena, 7);
+        assert_eq!(removed, 7);
+        assert_eq!(buf.len(), 6);
+
+        // Verify remaining data
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(count, 6);
+        assert_eq!(&bytes[..6], b"World!");
+
+        // Try to remove more than available
+        let removed = buf.remove_prefix(&mut arena, 100);
+        assert_eq!(removed, 6); // Only 6 were available
+        assert_eq!(buf.len(), 0);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_bytebuffer_chunk_freeing() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        // Fill first chunk (block_size minus the chunk header)
+        let block_size = arena.block_size();
+        let usable = block_size - arena.bit_layout().header_len();
+
+        for i in 0..usable {
+            buf.write(&mut arena).append(i as u8).unwrap();
+        }
+
+        let initial_used = arena.len();
+        assert_eq!(initial_used, 1); // 1 chunk allocated
+
+        // Add one more byte to trigger second chunk
+        buf.write(&mut arena).append(99).unwrap();
+        assert_eq!(arena.len(), 2); // 2 chunks now
+
+        // Consume the entire first chunk
+        for _ in 0..usable {
+            buf.pop_front(&mut arena);
+        }
+
+        // First chunk should be freed automatically
+        assert_eq!(arena.len(), 1); // Back to 1 chunk
+        assert_eq!(buf.len(), 1); // Only the 99 remains
+        assert_eq!(buf.peek_front(&arena), Some(99));
     }
 
-    /// Check if the deque is empty
-    #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.with_mut(|buf| buf.is_empty())
+    #[test]
+    fn test_bytebuffer_len_type_limit() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+        buf.write(&mut arena).extend(b"abc").unwrap();
+
+        // A buffer at the length type's maximum refuses more bytes instead of wrapping
+        buf.len = BufLen::MAX;
+        assert_eq!(
+            buf.write(&mut arena).append(b'd'),
+            Err(ByteBufferError::Full)
+        );
+        assert_eq!(buf.len, BufLen::MAX);
     }
 
-    /// Clear all data using the global allocator
-    #[inline]
-    pub fn clear(&self) {
-        self.with_mut(|buf| crate::global::with_global_allocator(|alloc| buf.write(alloc).clear()))
-    }
-}
+    #[test]
+    #[cfg(feature = "bytebuffer-len-u32")]
+    fn test_bytebuffer_len_u32_limits() {
+        let mut buf = ByteBuffer::with_max_len(200 * 1024);
+        assert_eq!(buf.max_len(), Some(204_800));
 
-#[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
-impl Default for GlobalDeque {
-    fn default() -> Self {
-        Self::new()
+        buf.set_max_len(Some(u16::MAX as BufLen + 1));
+        assert_eq!(buf.max_len(), Some(65_536));
     }
-}
 
-/// Iterator over bytes in a ByteBuffer using the global allocator
-#[cfg(feature = "global-alloc")]
-pub struct ByteBufferGlobalIter<'a> {
-    buf: &'a ByteBuffer,
-    index: usize,
-}
+    #[test]
+    #[cfg(all(feature = "bytebuffer-len-u32", feature = "tinyslab-large-blocks"))]
+    fn test_bytebuffer_len_u32_stores_large_data() {
+        // 1 KiB blocks: 1020 data bytes per chunk, beyond the u8 chunk length
+        let mut arena = TinySlabAllocator::<{ 100 * 1024 }, 100>::new();
+        let mut buf = ByteBuffer::new();
+        let block: [u8; 1000] = core::array::from_fn(|i| i as u8);
 
-#[cfg(feature = "global-alloc")]
-impl<'a> Iterator for ByteBufferGlobalIter<'a> {
-    type Item = u8;
+        for _ in 0..70 {
+            buf.write(&mut arena).extend(&block).unwrap();
+        }
+        assert_eq!(buf.len(), 70_000);
+        assert_eq!(arena.len(), 69);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.index >= self.buf.len as usize {
-            return None;
+        let mut out = [0u8; 1000];
+        for i in 0..70 {
+            assert_eq!(buf.copy_to_slice(&arena, i * 1000, &mut out), 1000);
+            assert_eq!(out, block);
         }
+        assert_eq!(buf.copy_to_slice(&arena, 69_999, &mut out), 1);
+        assert_eq!(out[0], block[999]);
+    }
 
-        let byte = crate::global::with_global_allocator(|alloc| {
-            self.buf.read(alloc).bytes().nth(self.index)
-        });
+    #[test]
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_bytebuffer_copy_from_after_pop() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut src = ByteBuffer::new();
+        let mut dst = ByteBuffer::new();
 
-        self.index += 1;
-        byte
+        src.write(&mut arena)
+            .extend(b"abcdefghijklmnopqrstuvwxyz0123456789")
+            .unwrap();
+        src.remove_prefix(&mut arena, 3);
+
+        // Range is relative to the unread data and spans a chunk boundary
+        dst.copy_from(&mut arena, &src, 20..30).unwrap();
+        let (bytes, count) = collect_bytes(&dst, &arena);
+        assert_eq!(&bytes[..count], b"xyz0123456");
     }
-}
 
-// ============================================================================
+    #[test]
+    #[cfg(feature = "bytebuffer-stream")]
+    fn test_bytebuffer_split_off_front_multi_chunk() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
 
-/// Read-only view of a ByteBuffer with an allocator
-///
-/// Provides methods to read data without modifying the buffer.
-pub struct ByteBufferRead<'a, A: Allocator> {
-    /// Reference to the allocator
-    pub(crate) arena: &'a A,
-    /// Reference to the buffer
-    pub(crate) buf: &'a ByteBuffer,
-}
+        let mut data = [0u8; 100];
+        for (i, b) in data.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        buf.write(&mut arena).extend(&data).unwrap();
+        buf.remove_prefix(&mut arena, 5);
 
-impl<'a, A: Allocator> ByteBufferRead<'a, A> {
-    /// Returns the number of bytes in the buffer
-    pub fn len(&self) -> u16 {
-        self.buf.len
+        let front = buf.split_off_front(&mut arena, 70).unwrap();
+        let (bytes, count) = collect_bytes(&front, &arena);
+        assert_eq!(&bytes[..count], &data[5..75]);
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(&bytes[..count], &data[75..]);
+
+        // Out of range split leaves the buffer alone
+        assert_eq!(
+            buf.split_off_front(&mut arena, 26).err(),
+            Some(ByteBufferError::Full)
+        );
+        assert_eq!(buf.len(), 25);
     }
 
-    /// Returns true if the buffer is empty
-    pub fn is_empty(&self) -> bool {
-        self.buf.len == 0
+    #[test]
+    #[cfg(feature = "bytebuffer-stream")]
+    fn test_bytebuffer_drain_into_bounded_destination() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut src = ByteBuffer::new();
+        let mut dst = ByteBuffer::with_max_len(45);
+
+        src.write(&mut arena).extend(&[1u8; 80]).unwrap();
+        dst.write(&mut arena).extend(&[2u8; 5]).unwrap();
+
+        // Destination only takes 40 more bytes; the rest stays in the source
+        assert_eq!(src.drain_into(&mut arena, &mut dst, 80), 40);
+        assert_eq!(src.len(), 40);
+        assert_eq!(dst.len(), 45);
+        let (bytes, count) = collect_bytes(&dst, &arena);
+        assert_eq!(&bytes[..5], &[2u8; 5]);
+        assert!(bytes[5..count].iter().all(|&b| b == 1));
     }
 
-    /// Returns an iterator over the chunks in the buffer
-    pub fn chunks(&self) -> ByteChunkIter<'a, A> {
-        ByteChunkIter {
-            arena: self.arena,
-            next_handle: self.buf.head,
-            _marker: core::marker::PhantomData,
+    #[test]
+    fn test_bytebuffer_split_at_boundary_relinks() {
+        // 30-byte chunks
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+        let mut data = [0u8; 90];
+        for (i, b) in data.iter_mut().enumerate() {
+            *b = i as u8;
         }
-    }
+        buf.write(&mut arena).extend(&data).unwrap();
+        assert_eq!(arena.len(), 3);
 
-    /// Returns an iterator over individual bytes in the buffer
-    pub fn bytes(&self) -> impl Iterator<Item = u8> + 'a {
-        self.chunks().flat_map(|c| c.iter().copied())
+        let back = buf.split_at(&mut arena, 60).unwrap();
+        assert_eq!(arena.len(), 3); // No chunk copied or allocated
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(&bytes[..count], &data[..60]);
+        let (bytes, count) = collect_bytes(&back, &arena);
+        assert_eq!(&bytes[..count], &data[60..]);
     }
-}
-/// Iterator over chunks in a ByteBuffer
-///
-/// Each item is a slice representing one allocated chunk.
-pub struct ByteChunkIter<'a, A: Allocator> {
-    /// Reference to the allocator
-    pub arena: &'a A,
-    /// Handle to the next chunk
-    pub next_handle: Option<Handle>,
-    /// Phantom data for lifetime
-    pub _marker: core::marker::PhantomData<A>,
-}
 
-impl<'a, A: Allocator> Iterator for ByteChunkIter<'a, A> {
-    type Item = &'a [u8];
+    #[test]
+    fn test_bytebuffer_split_at_inside_chunk() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+        let mut data = [0u8; 90];
+        for (i, b) in data.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        buf.write(&mut arena).extend(&data).unwrap();
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let h = self.next_handle?;
-        let block = self.arena.get(h)?;
-        let layout = self.arena.bit_layout();
-        let data = get_data(block, &layout);
-        self.next_handle = get_next_handle(block, &layout);
-        Some(data)
+        let mut back = buf.split_at(&mut arena, 45).unwrap();
+        assert_eq!(arena.len(), 4); // Only the boundary chunk was split
+        assert_eq!(buf.len(), 45);
+        assert_eq!(back.len(), 45);
+
+        // Both halves keep working: the front refills its truncated tail chunk
+        buf.write(&mut arena).extend(b"xyz").unwrap();
+        back.write(&mut arena).append(0xFF).unwrap();
+        assert_eq!(arena.len(), 5);
+
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(&bytes[..45], &data[..45]);
+        assert_eq!(&bytes[45..count], b"xyz");
+        let (bytes, count) = collect_bytes(&back, &arena);
+        assert_eq!(&bytes[..45], &data[45..]);
+        assert_eq!(bytes[count - 1], 0xFF);
+
+        // Splitting at the ends needs no allocation
+        let empty = buf.split_at(&mut arena, 48).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(
+            buf.split_at(&mut arena, 49).err(),
+            Some(ByteBufferError::OutOfRange)
+        );
     }
-}
 
-/// Mutable view of a ByteBuffer with an allocator
-///
-/// Provides methods to modify the buffer contents.
-pub struct ByteBufferWrite<'a, A: Allocator> {
-    /// Mutable reference to the allocator
-    pub(crate) arena: &'a mut A,
-    /// Mutable reference to the buffer
-    pub(crate) buf: &'a mut ByteBuffer,
-}
+    #[test]
+    fn test_bytebuffer_append_buffer_links_chunks() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut a = ByteBuffer::new();
+        let mut b = ByteBuffer::new();
 
-impl<'a, A: Allocator> ByteBufferWrite<'a, A> {
-    /// Clears the buffer, freeing all allocated chunks
-    pub fn clear(&mut self) {
-        let layout = self.arena.bit_layout();
-        let mut cursor = self.buf.head;
-        while let Some(h) = cursor {
-            cursor = {
-                if let Some(block) = self.arena.get(h) {
-                    get_next_handle(block, &layout)
-                } else {
-                    None
-                }
-            };
-            let _ = self.arena.free(h);
-        }
-        self.buf.head = None;
-        self.buf.tail = None;
-        self.buf.len = 0;
+        a.write(&mut arena).extend(b"Hello, ").unwrap();
+        b.write(&mut arena).extend(&[b'!'; 40]).unwrap();
+        assert_eq!(arena.len(), 3);
+
+        a.append_buffer(&mut arena, &mut b).unwrap();
+        assert_eq!(arena.len(), 3);
+        assert_eq!(a.len(), 47);
+        assert!(b.is_empty());
+
+        let (bytes, count) = collect_bytes(&a, &arena);
+        assert_eq!(&bytes[..7], b"Hello, ");
+        assert_eq!(&bytes[7..count], &[b'!'; 40]);
+
+        // Appending after the join continues in the last chunk
+        a.write(&mut arena).append(b'.').unwrap();
+        assert_eq!(a.len(), 48);
     }
 
-    /// Appends a single byte to the buffer
-    ///
-    /// # Errors
-    ///
-    /// Returns `ByteBufferError::Full` if maximum length is reached.
-    /// Returns `ByteBufferError::AllocationFailed` if allocator is out of memory.
-    pub fn append(&mut self, b: u8) -> Result<(), ByteBufferError> {
-        if let Some(max) = self.buf.max_len
-            && self.buf.len >= max
-        {
-            return Err(ByteBufferError::Full);
-        }
+    #[test]
+    fn test_bytebuffer_append_buffer_respects_max_len() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut a = ByteBuffer::with_max_len(10);
+        let mut b = ByteBuffer::new();
+
+        a.write(&mut arena).extend(b"12345").unwrap();
+        b.write(&mut arena).extend(b"678901").unwrap();
+
+        assert_eq!(
+            a.append_buffer(&mut arena, &mut b),
+            Err(ByteBufferError::Full)
+        );
+        assert_eq!(a.len(), 5);
+        assert_eq!(b.len(), 6);
+    }
 
-        let layout = self.arena.bit_layout();
+    #[test]
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_bytebuffer_append_buffer_after_pop() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut a = ByteBuffer::new();
+        let mut b = ByteBuffer::new();
 
-        if self.buf.tail.is_none() {
-            // Request allocator's block size
-            let block_size = self.arena.block_size();
-            let h = self.buf.alloc_node(self.arena, block_size)?;
-            self.buf.head = Some(h);
-            self.buf.tail = Some(h);
-        }
+        a.write(&mut arena).extend(b"ab").unwrap();
+        b.write(&mut arena).extend(b"__cd").unwrap();
+        b.remove_prefix(&mut arena, 2);
 
-        let tail = self.buf.tail.unwrap();
+        a.append_buffer(&mut arena, &mut b).unwrap();
+        assert_eq!(a.len(), 4);
+        let (bytes, count) = collect_bytes(&a, &arena);
+        assert_eq!(&bytes[..count], b"abcd");
 
-        // Check if we need a new node first
-        let needs_new_node = {
-            let block = self
-                .arena
-                .get_mut(tail)
-                .ok_or(ByteBufferError::Uninitialized)?;
-            let len = get_len(block, &layout) as usize;
-            let capacity = data_capacity(block);
-            len >= capacity
-        };
+        // Consuming across the joined chunks frees both
+        assert_eq!(a.remove_prefix(&mut arena, 4), 4);
+        assert_eq!(arena.len(), 0);
+    }
 
-        if needs_new_node {
-            let block_size = self.arena.block_size();
-            let new = self.buf.alloc_node(self.arena, block_size)?;
+    #[test]
+    #[cfg(feature = "bytebuffer-stream")]
+    fn test_bytebuffer_drain_into_relinks() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut src = ByteBuffer::new();
+        let mut dst = ByteBuffer::new();
 
-            // Now update the old tail to point to new node
-            let block = self
-                .arena
-                .get_mut(tail)
-                .ok_or(ByteBufferError::Uninitialized)?;
-            set_next_slot(block, new.slot as u8, &layout);
-            set_next_gen(block, new.generation, &layout);
+        src.write(&mut arena).extend(&[3u8; 60]).unwrap();
+        dst.write(&mut arena).extend(b"dst").unwrap();
+        assert_eq!(arena.len(), 3);
 
-            self.buf.tail = Some(new);
+        assert_eq!(src.drain_into(&mut arena, &mut dst, 30), 30);
+        assert_eq!(arena.len(), 3);
+        assert_eq!(src.len(), 30);
+        assert_eq!(dst.len(), 33);
+    }
 
-            let block = self
-                .arena
-                .get_mut(new)
-                .ok_or(ByteBufferError::Uninitialized)?;
-            let data = get_data_mut(block);
-            data[0] = b;
-            set_len(block, 1, &layout);
-        } else {
-            let block = self
-                .arena
-                .get_mut(tail)
-                .ok_or(ByteBufferError::Uninitialized)?;
-            let len = get_len(block, &layout) as usize;
-            let data = get_data_mut(block);
-            data[len] = b;
-            set_len(block, (len + 1) as u8, &layout);
+    #[test]
+    #[cfg(feature = "bytebuffer-stream")]
+    fn test_small_moves_share_chunks() {
+        let mut arena = TinySlabAllocator::<512, 16>::new();
+        let mut src = ByteBuffer::new();
+        let mut dst = ByteBuffer::new();
+        let data: [u8; 30] = core::array::from_fn(|i| i as u8);
+        src.write(&mut arena).extend(&data).unwrap();
+
+        // Small drains fill the destination's tail chunk
+        for _ in 0..6 {
+            assert_eq!(src.drain_into(&mut arena, &mut dst, 5), 5);
         }
+        assert!(src.is_empty());
+        assert_eq!(arena.len(), 1);
+        let (bytes, count) = collect_bytes(&dst, &arena);
+        assert_eq!(&bytes[..count], &data);
+
+        // Small fronts are copied out, the source keeps its chunks
+        dst.write(&mut arena).extend(&data).unwrap();
+        let front = dst.split_off_front(&mut arena, 4).unwrap();
+        assert_eq!(collect_bytes(&front, &arena).0[..4], [0, 1, 2, 3]);
+        assert_eq!(arena.len(), 3);
+        free_chain(&mut arena, dst.head);
+        free_chain(&mut arena, front.head);
+        assert_eq!(arena.len(), 0);
+
+        // 2-byte messages appended one by one use 8 chunks, not 120
+        let mut log = ByteBuffer::new();
+        for i in 0..120u8 {
+            let mut msg = ByteBuffer::new();
+            msg.write(&mut arena).extend(&[i, i]).unwrap();
+            log.append_buffer(&mut arena, &mut msg).unwrap();
+        }
+        assert_eq!(log.len(), 240);
+        assert_eq!(arena.len(), 8);
+        assert_eq!(arena.capacity() - arena.len(), 8);
+        assert_eq!(log.get(&arena, 239), Some(119));
+    }
 
-        self.buf.len += 1;
-        Ok(())
+    #[test]
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_read_view_skips_popped_bytes() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        buf.write(&mut arena)
+            .extend(b"0123456789abcdefghijklmnopqrstuvwxyz")
+            .unwrap();
+        assert_eq!(buf.pop_front(&mut arena), Some(b'0'));
+        assert_eq!(buf.pop_front(&mut arena), Some(b'1'));
+
+        let read = buf.read(&arena);
+        assert_eq!(read.len(), 34);
+        assert_eq!(read.len(), buf.len());
+        assert_eq!(read.bytes().next(), Some(b'2'));
+        assert_eq!(read.position(|b| b == b'a'), Some(8));
+        assert_eq!(buf.position(&arena, |b| b == b'0'), None);
+
+        // Chunk slices are trimmed to the window and add up to len()
+        let mut chunks = read.chunks();
+        assert_eq!(chunks.next().unwrap()[0], b'2');
+        assert_eq!(read.chunks().map(|c| c.len()).sum::<usize>(), 34);
+
+        // Reading and popping interleave consistently
+        assert_eq!(buf.read(&arena).bytes().nth(3), Some(b'5'));
+        buf.remove_prefix(&mut arena, 30);
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(&bytes[..count], b"wxyz");
+        assert_eq!(buf.read(&arena).len(), 4);
     }
 
-    /// Appends a slice of bytes to the buffer
-    ///
-    /// # Errors
-    ///
-    /// Returns `ByteBufferError::Full` if maximum length would be exceeded.
-    /// Returns `ByteBufferError::AllocationFailed` if allocator is out of memory.
-    pub fn extend(&mut self, s: &[u8]) -> Result<(), ByteBufferError> {
-        for &b in s {
-            self.append(b)?;
-        }
-        Ok(())
+    #[test]
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_view_from_past_first_chunk() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+        let data: [u8; 100] = core::array::from_fn(|i| i as u8);
+        buf.write(&mut arena).extend(&data).unwrap();
+        buf.remove_prefix(&mut arena, 3);
+
+        // Starts 8 bytes into the second chunk
+        let view = buf.view_from(&arena, 35);
+        assert_eq!(view.start_offset(), 8);
+        assert_eq!(view.len(), 62);
+        assert_eq!(view.get(&arena, 0), Some(38));
+        assert_eq!(view.read(&arena).chunks().next().map(|c| c.len()), Some(22));
+        let (bytes, count) = collect_bytes(&view, &arena);
+        assert_eq!(&bytes[..count], &data[38..]);
+
+        // Nested views and ranges keep addressing the right bytes
+        let nested = view.view_from(&arena, 40);
+        assert_eq!(nested.start_offset(), 18);
+        assert_eq!(nested.get(&arena, 0), Some(78));
+        let mut out = [0u8; 5];
+        assert_eq!(
+            view.take(30)
+                .view_from(&arena, 27)
+                .copy_to_slice(&arena, 0, &mut out),
+            3
+        );
+        assert_eq!(out[..3], [65, 66, 67]);
+        let slice = view.slice(&arena, 50..60).unwrap();
+        assert_eq!(slice.bytes(&arena).next(), Some(88));
+        assert!(buf.view_from(&arena, 97).is_empty());
+
+        // The source buffer is untouched and still grows normally
+        buf.write(&mut arena).extend(b"end").unwrap();
+        assert!(buf.push_front(&mut arena, 2).is_ok());
+        assert_eq!(buf.len(), 101);
+        assert_eq!(buf.get(&arena, 0), Some(2));
     }
-}
 
-#[cfg(test)]
-#[cfg(feature = "tinyslab")]
-mod tests {
-    use super::*;
-    use crate::backend::tinyslab::TinySlabAllocator;
+    #[test]
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_take_view_stops_at_len() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
 
-    // Helper to collect bytes from iterator into fixed array
-    #[cfg(not(feature = "bytebuffer-deque"))]
-    fn collect_bytes<A: Allocator>(buf: &ByteBuffer, arena: &A) -> ([u8; 256], usize) {
-        let mut result = [0u8; 256];
-        let mut count = 0;
-        for (i, b) in buf.read(arena).bytes().enumerate() {
-            if i >= 256 {
-                break;
-            }
-            result[i] = b;
-            count += 1;
-        }
-        (result, count)
+        buf.write(&mut arena)
+            .extend(b"0123456789abcdefghijklmnopqrstuvwxyz")
+            .unwrap();
+        buf.remove_prefix(&mut arena, 5);
+
+        let view = buf.take(28);
+        assert_eq!(view.len(), 28);
+        let (bytes, count) = collect_bytes(&view, &arena);
+        assert_eq!(&bytes[..count], b"56789abcdefghijklmnopqrstuvw");
+        assert_eq!(
+            view.read(&arena).chunks().map(|c| c.len()).sum::<usize>(),
+            28
+        );
+        assert_eq!(view.position(&arena, |b| b == b'z'), None);
+
+        // A view of a view narrows further
+        let inner = view.take(3);
+        let (bytes, count) = collect_bytes(&inner, &arena);
+        assert_eq!(&bytes[..count], b"567");
+    }
+
+    #[test]
+    fn test_slice_across_chunks() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+        let data: [u8; 200] = core::array::from_fn(|i| i as u8);
+        buf.write(&mut arena).extend(&data).unwrap();
+
+        let slice = buf.slice(&arena, 50..180).unwrap();
+        assert_eq!(slice.len(), 130);
+        assert!(slice.bytes(&arena).eq(50..180u8));
+        assert!(slice.chunks(&arena).count() > 1);
+        assert_eq!(slice.position(&arena, |b| b == 100), Some(50));
+
+        // Nested slices are relative to their parent
+        let inner = slice.slice(&arena, 10..=19).unwrap();
+        assert!(inner.bytes(&arena).eq(60..70u8));
+        let empty = inner.slice(&arena, 10..).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.bytes(&arena).next(), None);
+
+        // copy_to_slice stops at the shorter of view and destination
+        let mut out = [0u8; 16];
+        assert_eq!(inner.copy_to_slice(&arena, &mut out), 10);
+        assert_eq!(&out[..10], &data[60..70]);
+        assert_eq!(slice.copy_to_slice(&arena, &mut out), 16);
+        assert_eq!(&out, &data[50..66]);
+
+        assert_eq!(buf.slice(&arena, ..).unwrap().len(), 200);
+        assert_eq!(
+            buf.slice(&arena, 150..201).err(),
+            Some(ByteBufferError::OutOfRange)
+        );
+        let (from, to) = (20, 10);
+        assert_eq!(
+            slice.slice(&arena, from..to).err(),
+            Some(ByteBufferError::OutOfRange)
+        );
     }
 
-    // Helper to collect bytes from iterator into fixed array (deque version - skips read_offset)
+    #[test]
     #[cfg(feature = "bytebuffer-deque")]
-    fn collect_bytes<A: Allocator>(buf: &ByteBuffer, arena: &A) -> ([u8; 256], usize) {
-        let mut result = [0u8; 256];
-        let mut count = 0;
-        for (i, b) in buf
-            .read(arena)
-            .bytes()
-            .skip(buf.read_offset as usize)
-            .enumerate()
-        {
-            if i >= 256 {
-                break;
-            }
-            result[i] = b;
-            count += 1;
-        }
-        (result, count)
+    fn test_slice_and_view_from_after_pop() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+        buf.write(&mut arena)
+            .extend(b"0123456789abcdefghijklmnopqrstuvwxyz")
+            .unwrap();
+        buf.remove_prefix(&mut arena, 3);
+
+        let slice = buf.slice(&arena, 0..4).unwrap();
+        assert!(slice.bytes(&arena).eq(b"3456".iter().copied()));
+
+        // Views skip bytes, even past a chunk
+        let rest = buf.view_from(&arena, 30);
+        assert_eq!(rest.len(), 3);
+        let (bytes, count) = collect_bytes(&rest, &arena);
+        assert_eq!(&bytes[..count], b"xyz");
+        assert_eq!(rest.peek_front(&arena), Some(b'x'));
+
+        let (rest, head) = (buf.view_from(&arena, 5), buf.take(5));
+        let (bytes, count) = collect_bytes(&head, &arena);
+        assert_eq!(&bytes[..count], b"34567");
+        assert_eq!(rest.len(), 28);
+        assert_eq!(
+            rest.slice(&arena, ..2).unwrap().bytes(&arena).nth(1),
+            Some(b'9')
+        );
+        assert!(buf.view_from(&arena, 33).is_empty());
+
+        // Chunks that don't resolve are never handed back whole
+        let mut other = TinySlabAllocator::<2048, 64>::new();
+        other.alloc(b"x").unwrap();
+        other.clear();
+        assert!(buf.view_from(&other, 5).is_empty());
     }
 
-    #[test]
-    fn test_bytebuffer_new() {
-        let buf = ByteBuffer::new();
-        assert_eq!(buf.len(), 0);
-        assert!(buf.is_empty());
-        assert_eq!(buf.max_len(), None);
+    #[test]
+    fn test_random_access_across_chunks() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+        let data: [u8; 100] = core::array::from_fn(|i| i as u8);
+        buf.write(&mut arena).extend(&data).unwrap();
+
+        assert_eq!(buf.get(&arena, 0), Some(0));
+        assert_eq!(buf.get(&arena, 31), Some(31));
+        assert_eq!(buf.get(&arena, 99), Some(99));
+        assert_eq!(buf.get(&arena, 100), None);
+
+        // Writes spanning a chunk boundary land in both chunks
+        buf.write_at(&mut arena, 27, &[0xAA; 6]).unwrap();
+        buf.set(&mut arena, 99, 0xBB).unwrap();
+        let mut out = [0u8; 8];
+        assert_eq!(buf.copy_to_slice(&arena, 26, &mut out), 8);
+        assert_eq!(out, [26, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 33]);
+        assert_eq!(buf.copy_to_slice(&arena, 96, &mut out), 4);
+        assert_eq!(&out[..4], &[96, 97, 98, 0xBB]);
+        assert_eq!(buf.copy_to_slice(&arena, 120, &mut out), 0);
+
+        // Out-of-range writes fail and leave the buffer untouched
+        assert_eq!(
+            buf.write_at(&mut arena, 98, &[1, 2, 3]),
+            Err(ByteBufferError::OutOfRange)
+        );
+        assert_eq!(
+            buf.set(&mut arena, 100, 1),
+            Err(ByteBufferError::OutOfRange)
+        );
+        assert_eq!(buf.get(&arena, 98), Some(98));
+        assert_eq!(buf.len(), 100);
+
+        // A chain ending before the recorded length is out of range too
+        let mut short = ByteBuffer { len: 110, ..buf };
+        assert_eq!(
+            short.write_at(&mut arena, 99, &[1, 2]),
+            Err(ByteBufferError::OutOfRange)
+        );
     }
 
     #[test]
-    fn test_bytebuffer_with_max_len() {
-        let buf = ByteBuffer::with_max_len(100);
-        assert_eq!(buf.max_len(), Some(100));
-        assert_eq!(buf.len(), 0);
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_random_access_after_pop() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+        buf.write(&mut arena).extend(b"xxHELLO").unwrap();
+        buf.remove_prefix(&mut arena, 2);
+
+        // Indices are relative to the unread data
+        assert_eq!(buf.get(&arena, 0), Some(b'H'));
+        buf.set(&mut arena, 0, b'J').unwrap();
+        let mut out = [0u8; 5];
+        assert_eq!(buf.copy_to_slice(&arena, 0, &mut out), 5);
+        assert_eq!(&out, b"JELLO");
     }
 
     #[test]
-    fn test_bytebuffer_append_single_byte() {
+    fn test_truncate_and_pop_back_free_chunks() {
         let mut arena = TinySlabAllocator::<2048, 64>::new();
         let mut buf = ByteBuffer::new();
+        let data: [u8; 100] = core::array::from_fn(|i| i as u8);
+        buf.write(&mut arena).extend(&data).unwrap();
+        assert_eq!(arena.len(), 4);
 
-        buf.write(&mut arena).append(42).unwrap();
+        buf.truncate(&mut arena, 31);
+        assert_eq!(buf.len(), 31);
+        assert_eq!(arena.len(), 2);
+        assert_eq!(buf.pop_back(&mut arena), Some(30));
+        assert_eq!(arena.len(), 1);
 
-        assert_eq!(buf.len(), 1);
+        // The tail is valid again: appends continue after the cut
+        buf.write(&mut arena).extend(&[0xEE; 3]).unwrap();
         let (bytes, count) = collect_bytes(&buf, &arena);
-        assert_eq!(count, 1);
-        assert_eq!(bytes[0], 42);
+        assert_eq!(count, 33);
+        assert_eq!(&bytes[28..33], &[28, 29, 0xEE, 0xEE, 0xEE]);
+
+        buf.truncate(&mut arena, 50);
+        assert_eq!(buf.len(), 33);
+        buf.truncate(&mut arena, 0);
+        assert!(buf.is_empty());
+        assert_eq!(arena.len(), 0);
+        assert_eq!(buf.pop_back(&mut arena), None);
     }
 
     #[test]
-    fn test_bytebuffer_append_multiple_bytes() {
+    fn test_insert_middle_and_front() {
         let mut arena = TinySlabAllocator::<2048, 64>::new();
         let mut buf = ByteBuffer::new();
+        let data: [u8; 60] = core::array::from_fn(|i| i as u8);
+        buf.write(&mut arena).extend(&data).unwrap();
 
-        let data = b"Hello";
-        buf.write(&mut arena).extend(data).unwrap();
+        buf.insert(&mut arena, 45, &[0xAA; 40]).unwrap();
+        buf.push_front(&mut arena, 0xFF).unwrap();
+        buf.insert(&mut arena, 101, b"end").unwrap();
+        assert_eq!(buf.len(), 104);
 
-        assert_eq!(buf.len(), 5);
         let (bytes, count) = collect_bytes(&buf, &arena);
-        assert_eq!(count, 5);
-        assert_eq!(&bytes[..5], data);
+        assert_eq!(count, 104);
+        assert_eq!(bytes[0], 0xFF);
+        assert_eq!(&bytes[1..46], &data[..45]);
+        assert!(bytes[46..86].iter().all(|&b| b == 0xAA));
+        assert_eq!(&bytes[86..101], &data[45..]);
+        assert_eq!(&bytes[101..104], b"end");
+
+        assert_eq!(
+            buf.insert(&mut arena, 105, b"x"),
+            Err(ByteBufferError::OutOfRange)
+        );
     }
 
     #[test]
-    fn test_bytebuffer_append_exceeds_chunk() {
-        let mut arena = TinySlabAllocator::<2048, 64>::new();
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_push_front_fills_head_chunk() {
+        let mut arena = TinySlabAllocator::<512, 16>::new();
         let mut buf = ByteBuffer::new();
 
-        let data = b"Hello World!"; // 12 bytes
-        buf.write(&mut arena).extend(data).unwrap();
-
-        assert_eq!(buf.len(), 12);
+        // Prepends share chunks: 45 bytes take two, not 45
+        for i in 0..45u8 {
+            buf.push_front(&mut arena, i).unwrap();
+        }
+        assert_eq!(arena.len(), 2);
+        assert_eq!(buf.len(), 45);
+        assert_eq!(buf.get(&arena, 0), Some(44));
+        assert_eq!(buf.get(&arena, 44), Some(0));
+
+        // Appends and pops still see one ordered sequence
+        buf.write(&mut arena).extend(b"tail").unwrap();
+        assert_eq!(buf.pop_front(&mut arena), Some(44));
+        assert_eq!(buf.pop_back(&mut arena), Some(b'l'));
         let (bytes, count) = collect_bytes(&buf, &arena);
-        assert_eq!(count, 12);
-        assert_eq!(&bytes[..12], data);
+        assert_eq!(count, 47);
+        assert!(bytes[..44].iter().rev().copied().eq(0..44));
+        assert_eq!(&bytes[44..47], b"tai");
+
+        buf.remove_prefix(&mut arena, 47);
+        assert_eq!(arena.len(), 0);
     }
 
     #[test]
-    fn test_bytebuffer_max_len_enforcement() {
+    fn test_insert_respects_max_len() {
         let mut arena = TinySlabAllocator::<2048, 64>::new();
-        let mut buf = ByteBuffer::with_max_len(5);
+        let mut buf = ByteBuffer::with_max_len(8);
+        buf.write(&mut arena).extend(b"abcdef").unwrap();
 
-        let result = buf.write(&mut arena).extend(b"Hello");
-        assert!(result.is_ok());
-        assert_eq!(buf.len(), 5);
+        assert_eq!(
+            buf.insert(&mut arena, 2, b"xyz"),
+            Err(ByteBufferError::Full)
+        );
+        buf.insert(&mut arena, 2, b"xy").unwrap();
+        assert_eq!(buf.push_front(&mut arena, b'!'), Err(ByteBufferError::Full));
 
-        // Try to append one more
-        let result = buf.write(&mut arena).append(b'!');
-        assert_eq!(result, Err(ByteBufferError::Full));
-        assert_eq!(buf.len(), 5);
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(&bytes[..count], b"abxycdef");
     }
 
     #[test]
-    fn test_bytebuffer_clear() {
+    fn test_remove_ranges() {
         let mut arena = TinySlabAllocator::<2048, 64>::new();
         let mut buf = ByteBuffer::new();
+        let data: [u8; 120] = core::array::from_fn(|i| i as u8);
+        buf.write(&mut arena).extend(&data).unwrap();
+        assert_eq!(arena.len(), 4);
+
+        // Within one chunk
+        assert_eq!(buf.remove(&mut arena, 2..5), Ok(3));
+        // Spanning chunks: the ones in between are freed
+        assert_eq!(buf.remove(&mut arena, 10..80), Ok(70));
+        assert_eq!(arena.len(), 3);
+        // A range starting on a chunk's first byte drops that chunk too
+        assert_eq!(buf.remove(&mut arena, 0..12), Ok(12));
+        assert_eq!(buf.remove(&mut arena, 3..3), Ok(0));
+        assert_eq!(
+            buf.remove(&mut arena, 30..40),
+            Err(ByteBufferError::OutOfRange)
+        );
 
-        buf.write(&mut arena).extend(b"Hello World!").unwrap();
-        assert_eq!(buf.len(), 12);
-
-        buf.write(&mut arena).clear();
-        assert_eq!(buf.len(), 0);
-        assert!(buf.is_empty());
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(count, 35);
+        assert_eq!(&bytes[..count], &data[85..]);
+        assert_eq!(arena.len(), 2);
 
-        let (_, count) = collect_bytes(&buf, &arena);
-        assert_eq!(count, 0);
+        // Removing the end works like truncate
+        assert_eq!(buf.remove(&mut arena, 5..), Ok(30));
+        buf.write(&mut arena).append(0xEE).unwrap();
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(&bytes[..count], &[85, 86, 87, 88, 89, 0xEE]);
     }
 
     #[test]
-    fn test_bytebuffer_move_from() {
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_edits_after_pop() {
         let mut arena = TinySlabAllocator::<2048, 64>::new();
-        let mut src = ByteBuffer::new();
-        let mut dst = ByteBuffer::new();
-
-        src.write(&mut arena).extend(b"Source data").unwrap();
-        assert_eq!(src.len(), 11);
-
-        dst.move_from(&mut src);
+        let mut buf = ByteBuffer::new();
+        buf.write(&mut arena).extend(b"..hello world").unwrap();
+        buf.remove_prefix(&mut arena, 2);
 
-        // dst should have the data
-        assert_eq!(dst.len(), 11);
-        let (bytes, count) = collect_bytes(&dst, &arena);
-        assert_eq!(count, 11);
-        assert_eq!(&bytes[..11], b"Source data");
+        // push_front reuses the popped space without allocating
+        buf.push_front(&mut arena, b'>').unwrap();
+        assert_eq!(arena.len(), 1);
+        assert_eq!(buf.remove(&mut arena, 6..7), Ok(1));
+        buf.insert(&mut arena, 6, b", ").unwrap();
+        buf.truncate(&mut arena, 11);
 
-        // src should be empty
-        assert_eq!(src.len(), 0);
-        assert!(src.is_empty());
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(&bytes[..count], b">hello, wor");
+        assert_eq!(buf.pop_front(&mut arena), Some(b'>'));
     }
 
     #[test]
-    fn test_bytebuffer_drain() {
+    fn test_compact_underfilled_chain() {
         let mut arena = TinySlabAllocator::<2048, 64>::new();
         let mut buf = ByteBuffer::new();
+        let data: [u8; 150] = core::array::from_fn(|i| i as u8);
+        buf.write(&mut arena).extend(&data).unwrap();
+        assert_eq!(arena.len(), 5);
 
-        buf.write(&mut arena).extend(b"Drain this!").unwrap();
-        assert_eq!(buf.len(), 11);
-
-        // Drain to new buffer
-        let drained = buf.drain();
-
-        // Original should be empty
-        assert_eq!(buf.len(), 0);
-        assert!(buf.is_empty());
+        // Punch a hole in every chunk
+        for chunk in (0..5).rev() {
+            buf.remove(&mut arena, chunk * 30 + 5..chunk * 30 + 25)
+                .unwrap();
+        }
+        assert_eq!(buf.len(), 50);
+        assert_eq!(arena.len(), 5);
 
-        // Drained should have the data
-        assert_eq!(drained.len(), 11);
-        let (bytes, count) = collect_bytes(&drained, &arena);
-        assert_eq!(count, 11);
-        assert_eq!(&bytes[..11], b"Drain this!");
+        assert_eq!(buf.compact(&mut arena), 3);
+        assert_eq!(arena.len(), 2);
+        assert_eq!(buf.len(), 50);
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        let expected = data
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !(5..25).contains(&(i % 30)))
+            .map(|(_, &b)| b);
+        assert!(bytes[..count].iter().copied().eq(expected));
+
+        // Already compact: nothing to reclaim, appends continue at the tail
+        assert_eq!(buf.compact(&mut arena), 0);
+        buf.write(&mut arena).extend(&[0xEE; 10]).unwrap();
+        assert_eq!(arena.len(), 2);
+        assert_eq!(buf.get(&arena, 59), Some(0xEE));
     }
 
     #[test]
-    fn test_bytebuffer_copy_from() {
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_compact_after_pops() {
         let mut arena = TinySlabAllocator::<2048, 64>::new();
-        let mut src = ByteBuffer::new();
-        let mut dst = ByteBuffer::new();
-
-        src.write(&mut arena).extend(b"0123456789").unwrap();
-
-        // Copy middle range
-        dst.copy_from(&mut arena, &src, 3..7).unwrap();
+        let mut buf = ByteBuffer::new();
+        let data: [u8; 85] = core::array::from_fn(|i| i as u8);
+        buf.write(&mut arena).extend(&data).unwrap();
+        buf.remove_prefix(&mut arena, 29);
+        assert_eq!(arena.len(), 3);
 
-        assert_eq!(dst.len(), 4);
-        let (bytes, count) = collect_bytes(&dst, &arena);
-        assert_eq!(count, 4);
-        assert_eq!(&bytes[..4], b"3456");
+        assert_eq!(buf.compact(&mut arena), 1);
+        assert_eq!(arena.len(), 2);
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(&bytes[..count], &data[29..]);
+        assert_eq!(buf.pop_front(&mut arena), Some(29));
 
-        // Source should be unchanged
-        assert_eq!(src.len(), 10);
+        // Fully consumed buffers release everything
+        buf.remove_prefix(&mut arena, 55);
+        assert_eq!(buf.compact(&mut arena), 0);
+        assert!(buf.is_empty());
+        assert_eq!(arena.len(), 0);
     }
 
     #[test]
-    fn test_bytebuffer_large_data() {
-        let mut arena = TinySlabAllocator::<4096, 128>::new();
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_compact_broken_chain_is_untouched() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
         let mut buf = ByteBuffer::new();
-
-        // Write 100 bytes
-        let mut data = [0u8; 100];
-        for i in 0..100 {
-            data[i] = (i % 256) as u8;
-        }
+        let data: [u8; 85] = core::array::from_fn(|i| i as u8);
         buf.write(&mut arena).extend(&data).unwrap();
+        buf.remove_prefix(&mut arena, 29);
+        let before = buf;
 
-        assert_eq!(buf.len(), 100);
-
+        // Freeing the last chunk behind the buffer's back breaks the chain
+        let layout = arena.bit_layout();
+        let head = arena.get(buf.head.unwrap()).unwrap();
+        let second = get_next_handle(&arena, head, &layout).unwrap();
+        let second_block = arena.get(second).unwrap();
+        let third = get_next_handle(&arena, second_block, &layout).unwrap();
+        let saved: [u8; 32] = arena.get(third).unwrap().try_into().unwrap();
+        arena.free(third);
+
+        assert_eq!(buf.compact(&mut arena), 0);
+        assert_eq!(buf.len(), before.len());
+        assert_eq!(buf.start_offset(), 29);
+        assert_eq!(arena.len(), 2);
+
+        // Nothing was moved: restoring the chunk restores the buffer
+        let (h, block) = arena.alloc_uninit(32).unwrap();
+        block.copy_from_slice(&saved);
+        let second_block = arena.get_mut(second).unwrap();
+        set_next_handle(second_block, Some(h), &layout);
+        buf.tail = Some(h);
         let (bytes, count) = collect_bytes(&buf, &arena);
-        assert_eq!(count, 100);
-        assert_eq!(&bytes[..100], &data[..]);
+        assert_eq!(&bytes[..count], &data[29..]);
+        assert_eq!(buf.compact(&mut arena), 1);
     }
 
     #[test]
-    fn test_bytebuffer_set_max_len() {
+    fn test_contiguous_access() {
         let mut arena = TinySlabAllocator::<2048, 64>::new();
         let mut buf = ByteBuffer::new();
+        assert_eq!(buf.first_chunk(&arena), None);
+        assert_eq!(buf.as_single_slice(&arena), Some(&[][..]));
 
-        assert_eq!(buf.max_len(), None);
-
-        buf.set_max_len(Some(10));
-        assert_eq!(buf.max_len(), Some(10));
-
-        buf.write(&mut arena).extend(b"12345").unwrap();
-        assert_eq!(buf.len(), 5);
-
-        // Can add 5 more to reach limit
-        buf.write(&mut arena).extend(b"67890").unwrap();
-        assert_eq!(buf.len(), 10);
-
-        // Try to append one more - should fail
-        let result = buf.write(&mut arena).append(b'X');
-        assert_eq!(result, Err(ByteBufferError::Full));
-        assert_eq!(buf.len(), 10);
+        // Single chunk: borrowed, the scratch is not needed
+        buf.write(&mut arena).extend(b"short").unwrap();
+        assert_eq!(buf.as_single_slice(&arena), Some(&b"short"[..]));
+        let sum = buf.with_contiguous(&arena, &mut [], |bytes| bytes.len());
+        assert_eq!(sum, Ok(5));
 
-        // Remove limit
-        buf.set_max_len(None);
-        buf.write(&mut arena).extend(b"ABCDEFGH").unwrap();
-        assert_eq!(buf.len(), 18);
+        // Several chunks: copied into the scratch
+        let data: [u8; 70] = core::array::from_fn(|i| i as u8);
+        buf.write(&mut arena).extend(&data).unwrap();
+        assert_eq!(buf.as_single_slice(&arena), None);
+        assert_eq!(buf.first_chunk(&arena).map(|c| c.len()), Some(30));
+        let mut scratch = [0u8; 80];
+        let ok = buf.with_contiguous(&arena, &mut scratch, |bytes| {
+            bytes.len() == 75 && bytes[..5] == *b"short" && bytes[5..] == data
+        });
+        assert_eq!(ok, Ok(true));
+        assert_eq!(
+            buf.with_contiguous(&arena, &mut [0u8; 74], |_| ()),
+            Err(ByteBufferError::Full)
+        );
+
+        // Too long for one chunk
+        assert_eq!(buf.make_contiguous(&mut arena), Err(ByteBufferError::Full));
+        buf.truncate(&mut arena, 28);
+        assert_eq!(arena.len(), 1);
     }
 
     #[test]
     #[cfg(feature = "bytebuffer-deque")]
-    fn test_bytebuffer_pop_front() {
+    fn test_make_contiguous_after_pops() {
         let mut arena = TinySlabAllocator::<2048, 64>::new();
         let mut buf = ByteBuffer::new();
+        let data: [u8; 50] = core::array::from_fn(|i| i as u8);
+        buf.write(&mut arena).extend(&data).unwrap();
+        buf.remove_prefix(&mut arena, 25);
+        assert_eq!(buf.first_chunk(&arena), Some(&data[25..30]));
+        assert_eq!(buf.as_single_slice(&arena), None);
 
-        buf.write(&mut arena).extend(b"ABCDE").unwrap();
-        assert_eq!(buf.len(), 5);
-
-        // Pop bytes one by one
-        assert_eq!(buf.pop_front(&mut arena), Some(b'A'));
-        assert_eq!(buf.len(), 4);
+        assert_eq!(buf.make_contiguous(&mut arena), Ok(&data[25..]));
+        assert_eq!(arena.len(), 1);
+        assert_eq!(buf.as_single_slice(&arena), Some(&data[25..]));
+    }
 
-        assert_eq!(buf.pop_front(&mut arena), Some(b'B'));
+    #[test]
+    fn test_overflow_reject_counts_overruns() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::with_max_len(3);
+
+        assert_eq!(
+            buf.write(&mut arena).extend(b"ABCD"),
+            Err(ByteBufferError::Full)
+        );
+        assert_eq!(
+            buf.write(&mut arena).append(b'E'),
+            Err(ByteBufferError::Full)
+        );
         assert_eq!(buf.len(), 3);
+        assert_eq!(buf.overruns(), 2);
 
-        assert_eq!(buf.pop_front(&mut arena), Some(b'C'));
-        assert_eq!(buf.pop_front(&mut arena), Some(b'D'));
-        assert_eq!(buf.pop_front(&mut arena), Some(b'E'));
-        assert_eq!(buf.len(), 0);
+        assert_eq!(buf.reset_overruns(), 2);
+        assert_eq!(buf.overruns(), 0);
+    }
 
-        // Pop from empty buffer
-        assert_eq!(buf.pop_front(&mut arena), None);
+    #[test]
+    fn test_chain_survives_generation_wrap() {
+        // Headers keep 4 generation bits here; reuse slots well past 16 frees
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        assert_eq!(arena.bit_layout().gen_bits, 4);
+        let data: [u8; 100] = core::array::from_fn(|i| i as u8);
+
+        for round in 0..40 {
+            let mut buf = ByteBuffer::new();
+            buf.write(&mut arena).extend(&data[round..]).unwrap();
+            let (bytes, count) = collect_bytes(&buf, &arena);
+            assert_eq!(&bytes[..count], &data[round..]);
+            buf.write(&mut arena).clear();
+            assert_eq!(arena.len(), 0);
+        }
     }
 
     #[test]
-    #[cfg(feature = "bytebuffer-deque")]
-    fn test_bytebuffer_peek_front() {
+    fn test_overflow_reject_counts_whole_slice() {
         let mut arena = TinySlabAllocator::<2048, 64>::new();
-        let mut buf = ByteBuffer::new();
+        let mut buf = ByteBuffer::with_max_len(3);
+        buf.write(&mut arena).extend(b"ABC").unwrap();
+
+        assert_eq!(
+            buf.write(&mut arena).extend(b"0123456789"),
+            Err(ByteBufferError::Full)
+        );
+        assert_eq!(buf.overruns(), 10);
+        assert_eq!(
+            buf.write(&mut arena).put_slice(b"0123456789"),
+            Err(ByteBufferError::Full)
+        );
+        assert_eq!(buf.overruns(), 20);
 
-        buf.write(&mut arena).extend(b"XYZ").unwrap();
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(&bytes[..count], b"ABC");
+    }
 
-        // Peek doesn't consume
-        assert_eq!(buf.peek_front(&arena), Some(b'X'));
-        assert_eq!(buf.len(), 3);
-        assert_eq!(buf.peek_front(&arena), Some(b'X'));
+    #[test]
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_overflow_overwrite_oldest() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::with_overflow_policy(4, OverflowPolicy::Overwrite);
 
-        // Pop and peek again
-        buf.pop_front(&mut arena);
-        assert_eq!(buf.peek_front(&arena), Some(b'Y'));
+        buf.write(&mut arena).extend(b"ABCDEF").unwrap();
+        assert_eq!(buf.len(), 4);
+        assert_eq!(buf.overruns(), 2);
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(&bytes[..count], b"CDEF");
+
+        // Wrapping well past one 30-byte chunk keeps the newest bytes
+        for i in 0..100u8 {
+            buf.write(&mut arena).append(i).unwrap();
+        }
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(&bytes[..count], &[96, 97, 98, 99]);
+        assert_eq!(buf.overruns(), 102);
+        assert!(arena.len() <= 2);
     }
 
     #[test]
     #[cfg(feature = "bytebuffer-deque")]
-    fn test_bytebuffer_remove_prefix() {
+    fn test_overflow_overwrite_ring_keeps_its_chunks() {
+        // 30-byte chunks; a 60-byte ring needs three to roll byte by byte
         let mut arena = TinySlabAllocator::<2048, 64>::new();
-        let mut buf = ByteBuffer::new();
+        let mut ring = ByteBuffer::with_overflow_policy(60, OverflowPolicy::Overwrite);
+        for i in 0..100u8 {
+            ring.write(&mut arena).append(i).unwrap();
+        }
+        assert_eq!(arena.len(), 3);
+
+        // Another buffer drains the allocator
+        let mut other = ByteBuffer::new();
+        while other.write(&mut arena).append(0).is_ok() {}
+        assert_eq!(arena.len(), arena.capacity());
+
+        // Every new byte still replaces exactly the oldest one
+        for i in 100..=255u8 {
+            ring.write(&mut arena).append(i).unwrap();
+            assert_eq!(ring.len(), 60);
+            assert_eq!(ring.overruns() as usize, i as usize - 59);
+        }
+        let (bytes, count) = collect_bytes(&ring, &arena);
+        assert_eq!(count, 60);
+        assert!(bytes[..count].iter().copied().eq(196..=255u8));
+    }
 
-        buf.write(&mut arena).extend(b"Hello, World!").unwrap();
-        assert_eq!(buf.len(), 13);
+    #[test]
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_overflow_overwrite_reuses_chunks() {
+        // Two 14-byte chunks
+        let mut arena = TinySlabAllocator::<32, 2>::new();
+        let mut buf = ByteBuffer::new();
+        buf.set_overflow_policy(OverflowPolicy::Overwrite);
+        let data: [u8; 100] = core::array::from_fn(|i| i as u8);
 
-        // Remove first 7 bytes ("Hello, ")
-        let removed = buf.remove_prefix(&mut arena, 7);
-        assert_eq!(removed, 7);
-        assert_eq!(buf.len(), 6);
+        buf.write(&mut arena).extend(&data).unwrap();
 
-        // Verify remaining data
+        // The same two chunks keep cycling; the oldest data is overwritten
+        assert_eq!(arena.len(), 2);
+        assert_eq!(buf.len() as usize + buf.overruns() as usize, 100);
         let (bytes, count) = collect_bytes(&buf, &arena);
-        assert_eq!(count, 6);
-        assert_eq!(&bytes[..6], b"World!");
+        assert_eq!(&bytes[..count], &data[100 - count..]);
+        assert!(count > 14);
 
-        // Try to remove more than available
-        let removed = buf.remove_prefix(&mut arena, 100);
-        assert_eq!(removed, 6); // Only 6 were available
-        assert_eq!(buf.len(), 0);
-        assert!(buf.is_empty());
+        // Draining frees the recycled chunks as usual
+        buf.remove_prefix(&mut arena, count);
+        assert_eq!(arena.len(), 0);
     }
 
     #[test]
     #[cfg(feature = "bytebuffer-deque")]
-    fn test_bytebuffer_chunk_freeing() {
-        let mut arena = TinySlabAllocator::<2048, 64>::new();
+    fn test_overflow_overwrite_single_chunk() {
+        // Single 14-byte chunk
+        let mut arena = TinySlabAllocator::<16, 1>::new();
         let mut buf = ByteBuffer::new();
+        buf.set_overflow_policy(OverflowPolicy::Overwrite);
 
-        // Fill first chunk (block_size - 2 bytes of metadata)
-        let block_size = arena.block_size();
-        let usable = block_size - 2; // Subtract 2-byte metadata
+        buf.write(&mut arena).extend(b"0123456789ABCDXY").unwrap();
+        assert_eq!(buf.overruns() as usize + buf.len() as usize, 16);
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(&bytes[..count], b"XY");
+        assert_eq!(arena.len(), 1);
+    }
 
-        for i in 0..usable {
-            buf.write(&mut arena).append(i as u8).unwrap();
+    #[test]
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_overflow_drop_oldest_max_len() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::with_overflow_policy(5, OverflowPolicy::DropOldest);
+
+        // Many more bytes than fit in one chunk, so evictions free chunks
+        for i in 0..200u8 {
+            buf.write(&mut arena).append(i).unwrap();
         }
 
-        let initial_used = arena.len();
-        assert_eq!(initial_used, 1); // 1 chunk allocated
+        assert_eq!(buf.len(), 5);
+        assert_eq!(buf.overruns(), 195);
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(&bytes[..count], &[195, 196, 197, 198, 199]);
+        assert!(arena.len() <= 2);
+    }
 
-        // Add one more byte to trigger second chunk
-        buf.write(&mut arena).append(99).unwrap();
-        assert_eq!(arena.len(), 2); // 2 chunks now
+    #[test]
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_overflow_drop_oldest_allocator_exhausted() {
+        // Four 14-byte chunks
+        let mut arena = TinySlabAllocator::<64, 4>::new();
+        let mut buf = ByteBuffer::new();
+        buf.set_overflow_policy(OverflowPolicy::DropOldest);
 
-        // Consume the entire first chunk
-        for _ in 0..usable {
-            buf.pop_front(&mut arena);
+        for i in 0..=255u8 {
+            buf.write(&mut arena).append(i).unwrap();
         }
 
-        // First chunk should be freed automatically
-        assert_eq!(arena.len(), 1); // Back to 1 chunk
-        assert_eq!(buf.len(), 1); // Only the 99 remains
-        assert_eq!(buf.peek_front(&arena), Some(99));
+        // Oldest whole chunks were evicted to keep accepting bytes
+        assert_eq!(arena.len(), 4);
+        assert_eq!(buf.len() as usize + buf.overruns() as usize, 256);
+        let (bytes, count) = collect_bytes(&buf, &arena);
+        assert_eq!(bytes[count - 1], 255);
+        assert_eq!(bytes[0] as usize, 256 - count);
+
+        // The buffer keeps draining normally afterwards
+        assert_eq!(buf.pop_front(&mut arena), Some(bytes[0]));
     }
 }
@@ -0,0 +1,458 @@
+//! CRC and checksum computation over ByteBuffer chunks
+//!
+//! Every algorithm implements [`Checksum`], an incremental state fed one slice
+//! at a time. [`ByteBufferRead::checksum`], [`ByteBuffer::checksum`] and
+//! [`ByteSlice::checksum`] fold a state over the chunks of a buffer, one slice
+//! per chunk, instead of walking the chain byte by byte.
+//!
+//! | Type | Algorithm | Output |
+//! |------|-----------|--------|
+//! | [`Crc8`] | CRC-8 ([`CRC8`], [`CRC8_MAXIM`]) | `u8` |
+//! | [`Crc16`] | CRC-16 ([`CRC16_CCITT_FALSE`], [`CRC16_XMODEM`], [`CRC16_KERMIT`], [`CRC16_X25`], [`CRC16_MODBUS`]) | `u16` |
+//! | [`Crc32`] | CRC-32 ([`CRC32`], [`CRC32C`]) | `u32` |
+//! | [`Fletcher16`] | Fletcher-16 | `u16` |
+//! | [`Adler32`] | Adler-32 (zlib) | `u32` |
+//! | [`Xor8`] | XOR of all bytes | `u8` |
+//! | [`Sum8`] | Sum of all bytes, modulo 256 | `u8` |
+//!
+//! Custom CRCs are described by a [`CrcSpec`] built with `CrcSpec::new`.
+//!
+//! # Feature Flags
+//!
+//! This module is available when the `bytebuffer` feature is enabled.
+//!
+//! CRCs are computed bit by bit by default, which needs no tables and keeps
+//! code small. The `checksum-tables` feature switches to one 256-entry lookup
+//! table per `CrcSpec` (256, 512 or 1024 bytes of flash for 8, 16 and 32-bit
+//! CRCs), roughly eight times faster. Results are identical either way.
+//!
+//! # Examples
+//!
+//! ```
+//! use tinyalloc::prelude::*;
+//!
+//! let mut alloc = TinySlabAllocator::<1024, 32>::new();
+//! let mut buf = ByteBuffer::new();
+//! buf.write(&mut alloc).extend(b"123456789").unwrap();
+//!
+//! assert_eq!(buf.checksum(&alloc, Crc32::new(&CRC32)), 0xCBF4_3926);
+//! assert_eq!(buf.checksum(&alloc, Crc16::new(&CRC16_MODBUS)), 0x4B37);
+//! assert_eq!(buf.checksum(&alloc, Adler32::new()), 0x091E_01DE);
+//!
+//! // Incremental: continue a state across several buffers or slices
+//! let mut crc = Crc32::new(&CRC32);
+//! crc.update(b"1234");
+//! crc.update(b"56789");
+//! assert_eq!(crc.finish(), 0xCBF4_3926);
+//! ```
+
+use crate::Allocator;
+use crate::utils::bytebuffer::{ByteBuffer, ByteBufferRead, ByteSlice};
+
+/// Incremental checksum state
+///
+/// Feed data with [`update`](Self::update) in as many pieces as convenient;
+/// the result only depends on the concatenated bytes.
+pub trait Checksum {
+    /// Checksum value type
+    type Output;
+
+    /// Adds `data` to the checksum
+    fn update(&mut self, data: &[u8]);
+
+    /// Returns the checksum of everything fed so far
+    ///
+    /// The state is not reset; more data may still be added.
+    fn finish(&self) -> Self::Output;
+}
+
+/// Parameters of a CRC algorithm
+///
+/// Follows the usual Rocksoft model with input and output reflection tied
+/// together, which covers the common CRCs. With the `checksum-tables` feature
+/// the lookup table is computed at compile time by [`CrcSpec::new`], so specs
+/// should live in `static`s.
+pub struct CrcSpec<T> {
+    /// Generator polynomial, normal (MSB-first) form, top bit implied
+    #[cfg(not(feature = "checksum-tables"))]
+    poly: T,
+    /// Register value before the first byte
+    init: T,
+    /// Process bits LSB-first and reflect the result
+    reflected: bool,
+    /// Value XORed into the register to produce the result
+    xorout: T,
+    /// Register update for each value of the byte shifted in
+    #[cfg(feature = "checksum-tables")]
+    table: [T; 256],
+}
+
+macro_rules! crc_width {
+    ($name:ident, $ty:ty, $bits:expr) => {
+        impl CrcSpec<$ty> {
+            /// Describes a CRC by polynomial, initial value, reflection and final XOR
+            ///
+            /// `init` is given as in published parameter tables; it is never
+            /// reflected.
+            pub const fn new(poly: $ty, init: $ty, reflected: bool, xorout: $ty) -> Self {
+                let init = if reflected { init.reverse_bits() } else { init };
+                Self {
+                    #[cfg(not(feature = "checksum-tables"))]
+                    poly,
+                    init,
+                    reflected,
+                    xorout,
+                    #[cfg(feature = "checksum-tables")]
+                    table: {
+                        let mut table = [0; 256];
+                        let mut i = 0;
+                        while i < 256 {
+                            table[i] = Self::shift_byte(poly, reflected, 0, i as u8);
+                            i += 1;
+                        }
+                        table
+                    },
+                }
+            }
+
+            /// Shifts one byte through `crc`, bit by bit
+            #[inline]
+            const fn shift_byte(poly: $ty, reflected: bool, crc: $ty, byte: u8) -> $ty {
+                let mut crc = crc;
+                let mut bit = 0;
+                if reflected {
+                    let poly = poly.reverse_bits();
+                    crc ^= byte as $ty;
+                    while bit < 8 {
+                        crc = if crc & 1 != 0 {
+                            (crc >> 1) ^ poly
+                        } else {
+                            crc >> 1
+                        };
+                        bit += 1;
+                    }
+                } else {
+                    crc ^= (byte as $ty) << ($bits - 8);
+                    while bit < 8 {
+                        crc = if crc >> ($bits - 1) != 0 {
+                            (crc << 1) ^ poly
+                        } else {
+                            crc << 1
+                        };
+                        bit += 1;
+                    }
+                }
+                crc
+            }
+        }
+
+        #[doc = concat!("Incremental ", stringify!($bits), "-bit CRC state for a [`CrcSpec`]")]
+        #[derive(Clone, Copy)]
+        pub struct $name {
+            spec: &'static CrcSpec<$ty>,
+            /// Register, reflected when the spec is
+            crc: $ty,
+        }
+
+        impl $name {
+            /// Starts a CRC computation
+            pub const fn new(spec: &'static CrcSpec<$ty>) -> Self {
+                Self {
+                    spec,
+                    crc: spec.init,
+                }
+            }
+        }
+
+        impl Checksum for $name {
+            type Output = $ty;
+
+            #[cfg(not(feature = "checksum-tables"))]
+            fn update(&mut self, data: &[u8]) {
+                let spec = self.spec;
+                for &b in data {
+                    self.crc = CrcSpec::<$ty>::shift_byte(spec.poly, spec.reflected, self.crc, b);
+                }
+            }
+
+            #[cfg(feature = "checksum-tables")]
+            fn update(&mut self, data: &[u8]) {
+                let spec = self.spec;
+                for &b in data {
+                    self.crc = if spec.reflected {
+                        let index = (self.crc as u8) ^ b;
+                        spec.table[index as usize] ^ self.crc.checked_shr(8).unwrap_or(0)
+                    } else {
+                        let index = ((self.crc >> ($bits - 8)) as u8) ^ b;
+                        spec.table[index as usize] ^ self.crc.checked_shl(8).unwrap_or(0)
+                    };
+                }
+            }
+
+            fn finish(&self) -> $ty {
+                // A reflected register already holds the reflected result
+                self.crc ^ self.spec.xorout
+            }
+        }
+    };
+}
+
+crc_width!(Crc8, u8, 8);
+crc_width!(Crc16, u16, 16);
+crc_width!(Crc32, u32, 32);
+
+/// CRC-8 (SMBus): poly 0x07, init 0x00
+pub static CRC8: CrcSpec<u8> = CrcSpec::<u8>::new(0x07, 0x00, false, 0x00);
+/// CRC-8/MAXIM (Dallas 1-Wire): poly 0x31, init 0x00, reflected
+pub static CRC8_MAXIM: CrcSpec<u8> = CrcSpec::<u8>::new(0x31, 0x00, true, 0x00);
+
+/// CRC-16/CCITT-FALSE: poly 0x1021, init 0xFFFF
+pub static CRC16_CCITT_FALSE: CrcSpec<u16> = CrcSpec::<u16>::new(0x1021, 0xFFFF, false, 0x0000);
+/// CRC-16/XMODEM: poly 0x1021, init 0x0000
+pub static CRC16_XMODEM: CrcSpec<u16> = CrcSpec::<u16>::new(0x1021, 0x0000, false, 0x0000);
+/// CRC-16/KERMIT: poly 0x1021, init 0x0000, reflected
+pub static CRC16_KERMIT: CrcSpec<u16> = CrcSpec::<u16>::new(0x1021, 0x0000, true, 0x0000);
+/// CRC-16/X-25, the HDLC and PPP FCS: poly 0x1021, init 0xFFFF, reflected,
+/// complemented
+pub static CRC16_X25: CrcSpec<u16> = CrcSpec::<u16>::new(0x1021, 0xFFFF, true, 0xFFFF);
+/// CRC-16/MODBUS: poly 0x8005, init 0xFFFF, reflected
+pub static CRC16_MODBUS: CrcSpec<u16> = CrcSpec::<u16>::new(0x8005, 0xFFFF, true, 0x0000);
+
+/// CRC-32 (Ethernet, zlib): poly 0x04C11DB7, init 0xFFFFFFFF, reflected,
+/// complemented
+pub static CRC32: CrcSpec<u32> = CrcSpec::<u32>::new(0x04C1_1DB7, 0xFFFF_FFFF, true, 0xFFFF_FFFF);
+/// CRC-32C (Castagnoli): poly 0x1EDC6F41, init 0xFFFFFFFF, reflected,
+/// complemented
+pub static CRC32C: CrcSpec<u32> = CrcSpec::<u32>::new(0x1EDC_6F41, 0xFFFF_FFFF, true, 0xFFFF_FFFF);
+
+/// Fletcher-16 checksum state
+///
+/// The result is `sum2 << 8 | sum1`.
+#[derive(Clone, Copy, Default)]
+pub struct Fletcher16 {
+    sum1: u16,
+    sum2: u16,
+}
+
+impl Fletcher16 {
+    /// Starts a Fletcher-16 computation
+    pub const fn new() -> Self {
+        Self { sum1: 0, sum2: 0 }
+    }
+}
+
+impl Checksum for Fletcher16 {
+    type Output = u16;
+
+    fn update(&mut self, data: &[u8]) {
+        for &b in data {
+            self.sum1 = (self.sum1 + u16::from(b)) % 255;
+            self.sum2 = (self.sum2 + self.sum1) % 255;
+        }
+    }
+
+    fn finish(&self) -> u16 {
+        (self.sum2 << 8) | self.sum1
+    }
+}
+
+/// Adler-32 checksum state, as used by zlib
+#[derive(Clone, Copy)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+/// Largest prime below 2^16
+const ADLER_MOD: u32 = 65521;
+
+impl Adler32 {
+    /// Starts an Adler-32 computation
+    pub const fn new() -> Self {
+        Self { a: 1, b: 0 }
+    }
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Adler32 {
+    type Output = u32;
+
+    fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.a = (self.a + u32::from(byte)) % ADLER_MOD;
+            self.b = (self.b + self.a) % ADLER_MOD;
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+}
+
+/// XOR of all bytes (longitudinal redundancy check as used by NMEA)
+#[derive(Clone, Copy, Default)]
+pub struct Xor8(u8);
+
+impl Xor8 {
+    /// Starts an XOR checksum
+    pub const fn new() -> Self {
+        Self(0)
+    }
+}
+
+impl Checksum for Xor8 {
+    type Output = u8;
+
+    fn update(&mut self, data: &[u8]) {
+        self.0 = data.iter().fold(self.0, |acc, &b| acc ^ b);
+    }
+
+    fn finish(&self) -> u8 {
+        self.0
+    }
+}
+
+/// Sum of all bytes, modulo 256
+#[derive(Clone, Copy, Default)]
+pub struct Sum8(u8);
+
+impl Sum8 {
+    /// Starts an additive checksum
+    pub const fn new() -> Self {
+        Self(0)
+    }
+}
+
+impl Checksum for Sum8 {
+    type Output = u8;
+
+    fn update(&mut self, data: &[u8]) {
+        self.0 = data.iter().fold(self.0, |acc, &b| acc.wrapping_add(b));
+    }
+
+    fn finish(&self) -> u8 {
+        self.0
+    }
+}
+
+impl<A: Allocator> ByteBufferRead<'_, A> {
+    /// Feeds the bytes in the view into `state`, one chunk at a time
+    pub fn update_checksum<C: Checksum>(&self, state: &mut C) {
+        for chunk in self.chunks() {
+            state.update(chunk);
+        }
+    }
+
+    /// Computes a checksum of the view, starting from `state`
+    pub fn checksum<C: Checksum>(&self, mut state: C) -> C::Output {
+        self.update_checksum(&mut state);
+        state.finish()
+    }
+}
+
+impl ByteBuffer {
+    /// Computes a checksum of the unread bytes, starting from `state`
+    ///
+    /// Use [`slice`](Self::slice) to checksum part of a buffer, for example
+    /// a payload without its trailing CRC.
+    pub fn checksum<A: Allocator, C: Checksum>(&self, arena: &A, state: C) -> C::Output {
+        self.read(arena).checksum(state)
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl ByteBuffer {
+    /// Compute a checksum of the unread bytes using global allocator
+    pub fn checksum_global<C: Checksum>(&self, state: C) -> C::Output {
+        crate::global::with_global_allocator(|alloc| self.checksum(alloc, state))
+    }
+}
+
+impl ByteSlice {
+    /// Computes a checksum of the bytes in the view, starting from `state`
+    pub fn checksum<A: Allocator, C: Checksum>(&self, arena: &A, mut state: C) -> C::Output {
+        for chunk in self.chunks(arena) {
+            state.update(chunk);
+        }
+        state.finish()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "tinyslab")]
+mod tests {
+    use super::*;
+    use crate::backend::tinyslab::TinySlabAllocator;
+
+    const CHECK: &[u8] = b"123456789";
+
+    fn check<C: Checksum>(mut state: C) -> C::Output {
+        state.update(CHECK);
+        state.finish()
+    }
+
+    #[test]
+    fn test_check_values() {
+        assert_eq!(check(Crc8::new(&CRC8)), 0xF4);
+        assert_eq!(check(Crc8::new(&CRC8_MAXIM)), 0xA1);
+        assert_eq!(check(Crc16::new(&CRC16_CCITT_FALSE)), 0x29B1);
+        assert_eq!(check(Crc16::new(&CRC16_XMODEM)), 0x31C3);
+        assert_eq!(check(Crc16::new(&CRC16_KERMIT)), 0x2189);
+        assert_eq!(check(Crc16::new(&CRC16_X25)), 0x906E);
+        assert_eq!(check(Crc16::new(&CRC16_MODBUS)), 0x4B37);
+        assert_eq!(check(Crc32::new(&CRC32)), 0xCBF4_3926);
+        assert_eq!(check(Crc32::new(&CRC32C)), 0xE306_9283);
+        assert_eq!(check(Adler32::new()), 0x091E_01DE);
+        assert_eq!(check(Xor8::new()), 0x31);
+        assert_eq!(check(Sum8::new()), 0xDD);
+
+        let mut fletcher = Fletcher16::new();
+        fletcher.update(b"abcde");
+        assert_eq!(fletcher.finish(), 0xC8F0);
+    }
+
+    #[test]
+    fn test_checksum_across_chunks() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let data: [u8; 200] = core::array::from_fn(|i| (i * 7) as u8);
+        let mut buf = ByteBuffer::new();
+        buf.write(&mut arena).extend(&data).unwrap();
+
+        assert_eq!(
+            buf.checksum(&arena, Crc32::new(&CRC32)),
+            check_slice(Crc32::new(&CRC32), &data)
+        );
+        assert_eq!(
+            buf.checksum(&arena, Fletcher16::new()),
+            check_slice(Fletcher16::new(), &data)
+        );
+
+        // Views and slices only cover their window
+        let slice = buf.slice(&arena, 45..170).unwrap();
+        assert_eq!(
+            slice.checksum(&arena, Crc16::new(&CRC16_X25)),
+            check_slice(Crc16::new(&CRC16_X25), &data[45..170])
+        );
+        #[cfg(feature = "bytebuffer-deque")]
+        {
+            buf.remove_prefix(&mut arena, 33);
+            assert_eq!(
+                buf.take(100).read(&arena).checksum(Crc8::new(&CRC8)),
+                check_slice(Crc8::new(&CRC8), &data[33..133])
+            );
+        }
+    }
+
+    fn check_slice<C: Checksum>(mut state: C, data: &[u8]) -> C::Output {
+        // One byte at a time, independent of chunk boundaries
+        for b in data.chunks(1) {
+            state.update(b);
+        }
+        state.finish()
+    }
+}
@@ -0,0 +1,300 @@
+//! Comparing, hashing and printing ByteBuffer contents
+//!
+//! A `ByteBuffer` is a handle to chunks in an allocator, so the usual traits
+//! cannot look at its bytes on their own. This module adds allocator-aware
+//! helpers that work chunk by chunk, whatever the chunk boundaries of each
+//! side:
+//!
+//! - `eq_bytes(arena, other)` / `eq_slice(arena, slice)` - content equality
+//! - `cmp_bytes(arena, other)` - lexicographic order, as for `[u8]`
+//! - `hash_bytes(arena, hasher)` - feeds a `core::hash::Hasher`; equal
+//!   contents hash equally however they are chunked
+//! - `display(arena)` - adapter printing the bytes as an escaped `b"..."`
+//!   string with `{:?}` (or without the quotes with `{}`)
+//!
+//! With the `global-alloc` feature, `ByteBuffer` also implements `PartialEq`,
+//! `Eq`, `PartialOrd`, `Ord`, `Hash` and `Debug` on its contents using the
+//! global allocator, so it can be used with `assert_eq!`, sorted or used as a
+//! map key.
+//!
+//! # Feature Flag
+//!
+//! This module is available when the `bytebuffer` feature is enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! use tinyalloc::prelude::*;
+//!
+//! let mut alloc = TinySlabAllocator::<1024, 32>::new();
+//! let mut a = ByteBuffer::new();
+//! let mut b = ByteBuffer::new();
+//! a.write(&mut alloc).extend(b"abc\n").unwrap();
+//! b.write(&mut alloc).extend(b"abd").unwrap();
+//!
+//! assert!(a.eq_slice(&alloc, b"abc\n"));
+//! assert!(!a.eq_bytes(&alloc, &b));
+//! assert_eq!(a.cmp_bytes(&alloc, &b), core::cmp::Ordering::Less);
+//! assert_eq!(format!("{:?}", a.display(&alloc)), r#"b"abc\n""#);
+//! ```
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::Hasher;
+
+use crate::Allocator;
+use crate::utils::bytebuffer::{ByteBuffer, ChunkCursor};
+
+/// Bytes fed to the hasher per `write` call
+const HASH_BLOCK: usize = 32;
+
+/// Compares two byte sequences given as chunks, independent of chunk boundaries
+fn cmp_chunks<'a, 'b>(
+    mut a: impl Iterator<Item = &'a [u8]>,
+    mut b: impl Iterator<Item = &'b [u8]>,
+) -> Ordering {
+    let (mut x, mut y): (&[u8], &[u8]) = (&[], &[]);
+    loop {
+        while x.is_empty() {
+            match a.next() {
+                Some(chunk) => x = chunk,
+                None => break,
+            }
+        }
+        while y.is_empty() {
+            match b.next() {
+                Some(chunk) => y = chunk,
+                None => break,
+            }
+        }
+        match (x.is_empty(), y.is_empty()) {
+            (true, true) => return Ordering::Equal,
+            (true, false) => return Ordering::Less,
+            (false, true) => return Ordering::Greater,
+            (false, false) => {}
+        }
+        let n = x.len().min(y.len());
+        match x[..n].cmp(&y[..n]) {
+            Ordering::Equal => {}
+            ord => return ord,
+        }
+        x = &x[n..];
+        y = &y[n..];
+    }
+}
+
+impl ByteBuffer {
+    /// Returns true if both buffers hold the same unread bytes
+    ///
+    /// Both buffers must live in `arena`.
+    pub fn eq_bytes<A: Allocator>(&self, arena: &A, other: &ByteBuffer) -> bool {
+        self.len() == other.len() && self.cmp_bytes(arena, other) == Ordering::Equal
+    }
+
+    /// Returns true if the unread bytes equal `data`
+    pub fn eq_slice<A: Allocator>(&self, arena: &A, data: &[u8]) -> bool {
+        self.len() as usize == data.len()
+            && cmp_chunks(self.read(arena).chunks(), core::iter::once(data)) == Ordering::Equal
+    }
+
+    /// Compares the unread bytes of both buffers lexicographically, as `[u8]` does
+    ///
+    /// Both buffers must live in `arena`.
+    pub fn cmp_bytes<A: Allocator>(&self, arena: &A, other: &ByteBuffer) -> Ordering {
+        cmp_chunks(self.read(arena).chunks(), other.read(arena).chunks())
+    }
+
+    /// Feeds the length and unread bytes into `state`
+    ///
+    /// Bytes are written in fixed-size blocks, so buffers with equal contents
+    /// produce the same hash however their chunks are laid out. The result
+    /// differs from hashing the same bytes as a `[u8]`.
+    pub fn hash_bytes<A: Allocator, H: Hasher>(&self, arena: &A, state: &mut H) {
+        let len = self.len() as usize;
+        state.write_usize(len);
+        let mut cursor = ChunkCursor::seek(arena, self, 0);
+        let mut block = [0u8; HASH_BLOCK];
+        let mut left = len;
+        while left > 0 {
+            let n = cursor.read(arena, &mut block[..left.min(HASH_BLOCK)]);
+            if n == 0 {
+                break;
+            }
+            state.write(&block[..n]);
+            left -= n;
+        }
+    }
+
+    /// Returns an adapter that formats the unread bytes
+    ///
+    /// `{:?}` prints a byte string literal such as `b"OK\r\n\xff"`; `{}`
+    /// prints the same escaped text without the `b"` and `"`.
+    pub fn display<'a, A: Allocator>(&'a self, arena: &'a A) -> ByteBufferDisplay<'a, A> {
+        ByteBufferDisplay { buf: self, arena }
+    }
+}
+
+/// Formatting adapter returned by [`ByteBuffer::display`]
+pub struct ByteBufferDisplay<'a, A: Allocator> {
+    buf: &'a ByteBuffer,
+    arena: &'a A,
+}
+
+impl<A: Allocator> fmt::Display for ByteBufferDisplay<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chunk in self.buf.read(self.arena).chunks() {
+            write!(f, "{}", chunk.escape_ascii())?;
+        }
+        Ok(())
+    }
+}
+
+impl<A: Allocator> fmt::Debug for ByteBufferDisplay<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "b\"{self}\"")
+    }
+}
+
+// ============================================================================
+// Trait implementations using the global allocator (feature = "global-alloc")
+// ============================================================================
+
+#[cfg(feature = "global-alloc")]
+impl PartialEq for ByteBuffer {
+    fn eq(&self, other: &Self) -> bool {
+        crate::global::with_global_allocator(|alloc| self.eq_bytes(alloc, other))
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl Eq for ByteBuffer {}
+
+#[cfg(feature = "global-alloc")]
+impl PartialEq<[u8]> for ByteBuffer {
+    fn eq(&self, other: &[u8]) -> bool {
+        crate::global::with_global_allocator(|alloc| self.eq_slice(alloc, other))
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl PartialOrd for ByteBuffer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl Ord for ByteBuffer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        crate::global::with_global_allocator(|alloc| self.cmp_bytes(alloc, other))
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl core::hash::Hash for ByteBuffer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        crate::global::with_global_allocator(|alloc| self.hash_bytes(alloc, state))
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl fmt::Debug for ByteBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        crate::global::with_global_allocator(|alloc| fmt::Debug::fmt(&self.display(alloc), f))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "tinyslab")]
+mod tests {
+    use super::*;
+    use crate::backend::tinyslab::TinySlabAllocator;
+    use core::fmt::Write;
+
+    /// FNV-1a, enough to compare hashes in tests
+    struct Fnv(u64);
+
+    impl Hasher for Fnv {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            for &b in bytes {
+                self.0 = (self.0 ^ u64::from(b)).wrapping_mul(0x100_0000_01B3);
+            }
+        }
+    }
+
+    fn hash<A: Allocator>(arena: &A, buf: &ByteBuffer) -> u64 {
+        let mut state = Fnv(0xCBF2_9CE4_8422_2325);
+        buf.hash_bytes(arena, &mut state);
+        state.finish()
+    }
+
+    #[test]
+    fn test_compare_different_layouts() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let data: [u8; 100] = core::array::from_fn(|i| i as u8);
+
+        // Same contents, chunk boundaries at different offsets
+        let mut a = ByteBuffer::new();
+        a.write(&mut arena).extend(&data).unwrap();
+        let mut b = ByteBuffer::new();
+        b.write(&mut arena).extend(&[0xEE; 7]).unwrap();
+        b.write(&mut arena).extend(&data).unwrap();
+        b.remove(&mut arena, 0..7).unwrap();
+
+        assert!(a.eq_bytes(&arena, &b));
+        assert!(a.eq_slice(&arena, &data));
+        assert!(!a.eq_slice(&arena, &data[..99]));
+        assert_eq!(a.cmp_bytes(&arena, &b), Ordering::Equal);
+        assert_eq!(hash(&arena, &a), hash(&arena, &b));
+
+        b.set(&mut arena, 60, 0).unwrap();
+        assert!(!a.eq_bytes(&arena, &b));
+        assert_eq!(a.cmp_bytes(&arena, &b), Ordering::Greater);
+        assert_ne!(hash(&arena, &a), hash(&arena, &b));
+
+        // A prefix sorts first
+        b.write(&mut arena).clear();
+        b.write(&mut arena).extend(&data[..50]).unwrap();
+        assert_eq!(b.cmp_bytes(&arena, &a), Ordering::Less);
+        assert_eq!(ByteBuffer::new().cmp_bytes(&arena, &b), Ordering::Less);
+    }
+
+    #[test]
+    fn test_display_escapes_bytes() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+        buf.write(&mut arena).extend(b"OK \"x\"\r\n\xFF").unwrap();
+
+        let mut out_arena = TinySlabAllocator::<1024, 32>::new();
+        let mut out = ByteBuffer::new();
+        write!(out.write(&mut out_arena), "{:?}", buf.display(&arena)).unwrap();
+        assert!(out.eq_slice(&out_arena, br#"b"OK \"x\"\r\n\xff""#));
+    }
+
+    #[test]
+    #[cfg(feature = "global-alloc")]
+    fn test_global_trait_impls() {
+        use crate::global::GlobalAllocatorConfig;
+
+        GlobalAllocatorConfig::Slab1K32.init();
+        let mut a = ByteBuffer::new();
+        let mut b = ByteBuffer::new();
+        a.extend(b"alpha").unwrap();
+        b.extend(b"beta").unwrap();
+
+        assert_ne!(a, b);
+        assert!(a < b);
+        assert_eq!(a.cmp(&b), Ordering::Less);
+        assert!(a == *b"alpha".as_slice());
+
+        b.clear();
+        b.extend(b"alpha").unwrap();
+        assert_eq!(a, b);
+        a.clear();
+        b.clear();
+    }
+}
@@ -0,0 +1,251 @@
+//! `embedded-io` trait implementations
+//!
+//! Lets drivers and protocol code written against `embedded_io` read from and
+//! write to tinyalloc buffers:
+//!
+//! - [`ByteBufferIo`] (from [`ByteBuffer::io`]) implements `Read`, `BufRead`
+//!   and `Write` for any allocator
+//! - `&GlobalDeque` implements `Read` and `Write` with the global allocator
+//! - [`GlobalDequeReader`] (from [`GlobalDeque::reader`]) implements `Read`
+//!   and `BufRead`; see [`ioadapter`](crate::utils::ioadapter) for why the
+//!   deque itself has no `BufRead`
+//!
+//! `fill_buf` returns the unread part of the head chunk, so a `BufRead`
+//! consumer sees the data in place, one chunk at a time. `read` returns
+//! `Ok(0)` once the buffer is empty: a buffer has no way to wait for data.
+//! `write` stores as much as fits and fails with [`ByteBufferError::Full`] or
+//! [`ByteBufferError::AllocationFailed`] only when nothing does.
+//!
+//! # Feature Flag
+//!
+//! This module is available when the `embedded-io` feature is enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "embedded-io")]
+//! # {
+//! use embedded_io::{BufRead, Read, Write};
+//! use tinyalloc::prelude::*;
+//!
+//! let mut alloc = TinySlabAllocator::<1024, 32>::new();
+//! let mut buf = ByteBuffer::new();
+//!
+//! let mut io = buf.io(&mut alloc);
+//! io.write_all(b"AT+OK\r\n").unwrap();
+//! assert_eq!(io.fill_buf().unwrap(), b"AT+OK\r\n");
+//! io.consume(3);
+//!
+//! let mut reply = [0u8; 2];
+//! io.read_exact(&mut reply).unwrap();
+//! assert_eq!(&reply, b"OK");
+//! # }
+//! ```
+
+use embedded_io::{BufRead, ErrorKind, ErrorType, Read, Write};
+
+use crate::Allocator;
+#[cfg(doc)]
+use crate::utils::bytebuffer::ByteBuffer;
+use crate::utils::bytebuffer::ByteBufferError;
+#[cfg(feature = "global-alloc")]
+use crate::utils::bytebuffer::GlobalDeque;
+use crate::utils::ioadapter::ByteBufferIo;
+#[cfg(feature = "global-alloc")]
+use crate::utils::ioadapter::GlobalDequeReader;
+
+impl embedded_io::Error for ByteBufferError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            ByteBufferError::AllocationFailed => ErrorKind::OutOfMemory,
+            ByteBufferError::Full => ErrorKind::WriteZero,
+            ByteBufferError::Malformed | ByteBufferError::InvalidByte { .. } => {
+                ErrorKind::InvalidData
+            }
+            ByteBufferError::Overflow | ByteBufferError::OutOfRange => ErrorKind::InvalidInput,
+            ByteBufferError::Uninitialized | ByteBufferError::Incomplete => ErrorKind::Other,
+        }
+    }
+}
+
+impl<A: Allocator> ErrorType for ByteBufferIo<'_, A> {
+    type Error = ByteBufferError;
+}
+
+impl<A: Allocator> Read for ByteBufferIo<'_, A> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(self.read_front(buf))
+    }
+}
+
+impl<A: Allocator> BufRead for ByteBufferIo<'_, A> {
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        Ok(self.head_chunk())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.consume_front(amt);
+    }
+}
+
+impl<A: Allocator> Write for ByteBufferIo<'_, A> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.write_back(buf)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl ErrorType for &GlobalDeque {
+    type Error = ByteBufferError;
+}
+
+#[cfg(feature = "global-alloc")]
+impl Read for &GlobalDeque {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(self.read_front(buf))
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl Write for &GlobalDeque {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.write_back(buf)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl<const N: usize> ErrorType for GlobalDequeReader<'_, N> {
+    type Error = ByteBufferError;
+}
+
+#[cfg(feature = "global-alloc")]
+impl<const N: usize> Read for GlobalDequeReader<'_, N> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(self.read_buffered(buf))
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl<const N: usize> BufRead for GlobalDequeReader<'_, N> {
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        Ok(self.fill())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.consume_buffered(amt);
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "tinyslab")]
+mod tests {
+    use super::*;
+    use crate::backend::tinyslab::TinySlabAllocator;
+    use crate::utils::bytebuffer::ByteBuffer;
+
+    #[test]
+    fn test_fill_buf_walks_chunks() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+        let data: [u8; 100] = core::array::from_fn(|i| i as u8);
+
+        let mut io = buf.io(&mut arena);
+        io.write_all(&data).unwrap();
+        io.consume(5);
+
+        // Each fill_buf hands out the rest of one chunk, in place
+        let mut seen = 5;
+        loop {
+            let chunk = io.fill_buf().unwrap();
+            if chunk.is_empty() {
+                break;
+            }
+            assert!(chunk.len() <= 30);
+            assert_eq!(chunk, &data[seen..seen + chunk.len()]);
+            let n = chunk.len();
+            seen += n;
+            io.consume(n);
+        }
+        assert_eq!(seen, 100);
+        assert!(io.buffer().is_empty());
+    }
+
+    #[test]
+    fn test_read_and_partial_write() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::with_max_len(10);
+
+        let mut io = buf.io(&mut arena);
+        assert_eq!(io.write(b"0123456789abc"), Ok(10));
+        let err = io.write(b"x").unwrap_err();
+        assert_eq!(err, ByteBufferError::Full);
+        assert_eq!(embedded_io::Error::kind(&err), ErrorKind::WriteZero);
+
+        let mut out = [0u8; 4];
+        assert_eq!(io.read(&mut out), Ok(4));
+        assert_eq!(&out, b"0123");
+        assert_eq!(io.write(b"wxyz!"), Ok(4));
+
+        let mut rest = [0u8; 16];
+        assert_eq!(io.read(&mut rest), Ok(10));
+        assert_eq!(&rest[..10], b"456789wxyz");
+        assert_eq!(io.read(&mut rest), Ok(0));
+    }
+
+    #[test]
+    #[cfg(feature = "global-alloc")]
+    fn test_global_deque_stream() {
+        use crate::global::GlobalAllocatorConfig;
+
+        static QUEUE: GlobalDeque = GlobalDeque::new();
+        GlobalAllocatorConfig::Slab1K32.init();
+
+        let mut q = &QUEUE;
+        q.write_all(b"$GPGGA,1*00\r\n").unwrap();
+        let mut tag = [0u8; 7];
+        q.read_exact(&mut tag).unwrap();
+        assert_eq!(&tag, b"$GPGGA,");
+
+        let mut out = [0u8; 8];
+        assert_eq!(q.read(&mut out), Ok(6));
+        assert_eq!(&out[..6], b"1*00\r\n");
+        assert_eq!(q.read(&mut out), Ok(0));
+        assert!(QUEUE.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "global-alloc")]
+    fn test_global_deque_reader() {
+        use crate::global::GlobalAllocatorConfig;
+
+        static QUEUE: GlobalDeque = GlobalDeque::new();
+        GlobalAllocatorConfig::Slab1K32.init();
+
+        let mut q = &QUEUE;
+        q.write_all(b"AT+CSQ\r\n+CSQ: 21,0\r\n").unwrap();
+
+        // Buffered bytes stay valid while the deque keeps changing
+        let mut reader = GlobalDequeReader::<8>::new(&QUEUE);
+        assert_eq!(reader.fill_buf().unwrap(), b"AT+CSQ\r\n");
+        q.write_all(b"OK\r\n").unwrap();
+        assert_eq!(reader.fill_buf().unwrap(), b"AT+CSQ\r\n");
+        reader.consume(8);
+
+        let mut reply = [0u8; 16];
+        reader.read_exact(&mut reply[..12]).unwrap();
+        assert_eq!(&reply[..12], b"+CSQ: 21,0\r\n");
+        let mut rest = QUEUE.reader();
+        assert_eq!(rest.fill_buf().unwrap(), b"OK\r\n");
+        rest.consume(4);
+        assert_eq!(rest.fill_buf().unwrap(), b"");
+        assert!(QUEUE.is_empty());
+    }
+}
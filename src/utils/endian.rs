@@ -0,0 +1,414 @@
+//! Endian-aware integer and float access for ByteBuffer
+//!
+//! Typed writers on [`ByteBufferWrite`] and readers on [`ByteBuffer`], in the
+//! spirit of the `bytes` crate's `BufMut`/`Buf`:
+//!
+//! - `put_u16(v)` / `put_u16_le(v)` - append big/little-endian
+//! - `peek_u16(arena)` / `peek_u16_le(arena)` - read from the front, keep the bytes
+//! - `get_u16(arena)` / `get_u16_le(arena)` - read and consume (`bytebuffer-deque`)
+//!
+//! The same methods exist for `u8`, `i8`, `u16`, `i16`, `u32`, `i32`, `u64`,
+//! `i64`, `f32` and `f64` (single-byte types have no `_le` variant). Without a
+//! suffix the byte order is big-endian (network order).
+//!
+//! Values are written all at once or not at all: a value never ends up half
+//! stored because the buffer filled up. Readers return
+//! `ByteBufferError::Incomplete` and leave the buffer untouched when fewer
+//! bytes are stored than the value needs. Values may straddle chunks.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "bytebuffer-deque")]
+//! # {
+//! use tinyalloc::prelude::*;
+//!
+//! let mut alloc = TinySlabAllocator::<1024, 32>::new();
+//! let mut buf = ByteBuffer::new();
+//!
+//! let mut w = buf.write(&mut alloc);
+//! w.put_u8(0x01).unwrap();
+//! w.put_u16(0x0203).unwrap();
+//! w.put_f32_le(1.5).unwrap();
+//!
+//! assert_eq!(buf.peek_u8(&alloc), Ok(0x01));
+//! assert_eq!(buf.get_u8(&mut alloc), Ok(0x01));
+//! assert_eq!(buf.get_u16(&mut alloc), Ok(0x0203));
+//! assert_eq!(buf.get_f32_le(&mut alloc), Ok(1.5));
+//! assert_eq!(buf.get_u32(&mut alloc), Err(ByteBufferError::Incomplete));
+//! # }
+//! ```
+
+use crate::Allocator;
+use crate::utils::bytebuffer::{ByteBuffer, ByteBufferError, ByteBufferWrite};
+
+impl ByteBuffer {
+    /// Copies the first `N` bytes without consuming them
+    fn peek_array<const N: usize, A: Allocator>(
+        &self,
+        arena: &A,
+    ) -> Result<[u8; N], ByteBufferError> {
+        let mut out = [0u8; N];
+        if self.copy_to_slice(arena, 0, &mut out) < N {
+            return Err(ByteBufferError::Incomplete);
+        }
+        Ok(out)
+    }
+
+    /// Copies and consumes the first `N` bytes
+    #[cfg(feature = "bytebuffer-deque")]
+    fn get_array<const N: usize, A: Allocator>(
+        &mut self,
+        arena: &mut A,
+    ) -> Result<[u8; N], ByteBufferError> {
+        let out = self.peek_array(arena)?;
+        self.remove_prefix(arena, N);
+        Ok(out)
+    }
+}
+
+/// Generates `put_*`, `peek_*` and `get_*` methods (plus global variants)
+/// for one type and byte order
+macro_rules! endian_methods {
+    ($ty:ident, $order:literal, $to:ident, $from:ident, $put:ident, $peek:ident, $get:ident, $peek_global:ident, $get_global:ident) => {
+        impl<A: Allocator> ByteBufferWrite<'_, A> {
+            #[doc = concat!("Appends a `", stringify!($ty), "` in ", $order, " byte order")]
+            ///
+            /// # Errors
+            ///
+            /// Returns `ByteBufferError::Full` or `ByteBufferError::AllocationFailed`
+            /// without writing anything if the value does not fit.
+            pub fn $put(&mut self, value: $ty) -> Result<(), ByteBufferError> {
+                self.put_slice(&value.$to())
+            }
+        }
+
+        impl ByteBuffer {
+            #[doc = concat!("Reads a `", stringify!($ty), "` in ", $order, " byte order from the front without consuming it")]
+            ///
+            /// Returns `ByteBufferError::Incomplete` if too few bytes are stored.
+            pub fn $peek<A: Allocator>(&self, arena: &A) -> Result<$ty, ByteBufferError> {
+                self.peek_array(arena).map($ty::$from)
+            }
+
+            #[doc = concat!("Reads and consumes a `", stringify!($ty), "` in ", $order, " byte order from the front")]
+            ///
+            /// Returns `ByteBufferError::Incomplete` and consumes nothing if too
+            /// few bytes are stored.
+            #[cfg(feature = "bytebuffer-deque")]
+            pub fn $get<A: Allocator>(&mut self, arena: &mut A) -> Result<$ty, ByteBufferError> {
+                self.get_array(arena).map($ty::$from)
+            }
+
+            #[doc = concat!("Reads a `", stringify!($ty), "` in ", $order, " byte order without consuming it using global allocator")]
+            #[cfg(feature = "global-alloc")]
+            pub fn $peek_global(&self) -> Result<$ty, ByteBufferError> {
+                crate::global::with_global_allocator(|alloc| self.$peek(alloc))
+            }
+
+            #[doc = concat!("Reads and consumes a `", stringify!($ty), "` in ", $order, " byte order using global allocator")]
+            #[cfg(all(feature = "bytebuffer-deque", feature = "global-alloc"))]
+            pub fn $get_global(&mut self) -> Result<$ty, ByteBufferError> {
+                crate::global::with_global_allocator(|alloc| self.$get(alloc))
+            }
+        }
+
+        impl ByteBuffer {
+            #[doc = concat!("Appends a `", stringify!($ty), "` in ", $order, " byte order using global allocator")]
+            #[cfg(feature = "global-alloc")]
+            pub fn $put(&mut self, value: $ty) -> Result<(), ByteBufferError> {
+                crate::global::with_global_allocator(|alloc| self.write(alloc).$put(value))
+            }
+        }
+    };
+}
+
+endian_methods!(
+    u8,
+    "big-endian",
+    to_be_bytes,
+    from_be_bytes,
+    put_u8,
+    peek_u8,
+    get_u8,
+    peek_u8_global,
+    get_u8_global
+);
+endian_methods!(
+    i8,
+    "big-endian",
+    to_be_bytes,
+    from_be_bytes,
+    put_i8,
+    peek_i8,
+    get_i8,
+    peek_i8_global,
+    get_i8_global
+);
+
+endian_methods!(
+    u16,
+    "big-endian",
+    to_be_bytes,
+    from_be_bytes,
+    put_u16,
+    peek_u16,
+    get_u16,
+    peek_u16_global,
+    get_u16_global
+);
+endian_methods!(
+    u16,
+    "little-endian",
+    to_le_bytes,
+    from_le_bytes,
+    put_u16_le,
+    peek_u16_le,
+    get_u16_le,
+    peek_u16_le_global,
+    get_u16_le_global
+);
+endian_methods!(
+    i16,
+    "big-endian",
+    to_be_bytes,
+    from_be_bytes,
+    put_i16,
+    peek_i16,
+    get_i16,
+    peek_i16_global,
+    get_i16_global
+);
+endian_methods!(
+    i16,
+    "little-endian",
+    to_le_bytes,
+    from_le_bytes,
+    put_i16_le,
+    peek_i16_le,
+    get_i16_le,
+    peek_i16_le_global,
+    get_i16_le_global
+);
+
+endian_methods!(
+    u32,
+    "big-endian",
+    to_be_bytes,
+    from_be_bytes,
+    put_u32,
+    peek_u32,
+    get_u32,
+    peek_u32_global,
+    get_u32_global
+);
+endian_methods!(
+    u32,
+    "little-endian",
+    to_le_bytes,
+    from_le_bytes,
+    put_u32_le,
+    peek_u32_le,
+    get_u32_le,
+    peek_u32_le_global,
+    get_u32_le_global
+);
+endian_methods!(
+    i32,
+    "big-endian",
+    to_be_bytes,
+    from_be_bytes,
+    put_i32,
+    peek_i32,
+    get_i32,
+    peek_i32_global,
+    get_i32_global
+);
+endian_methods!(
+    i32,
+    "little-endian",
+    to_le_bytes,
+    from_le_bytes,
+    put_i32_le,
+    peek_i32_le,
+    get_i32_le,
+    peek_i32_le_global,
+    get_i32_le_global
+);
+
+endian_methods!(
+    u64,
+    "big-endian",
+    to_be_bytes,
+    from_be_bytes,
+    put_u64,
+    peek_u64,
+    get_u64,
+    peek_u64_global,
+    get_u64_global
+);
+endian_methods!(
+    u64,
+    "little-endian",
+    to_le_bytes,
+    from_le_bytes,
+    put_u64_le,
+    peek_u64_le,
+    get_u64_le,
+    peek_u64_le_global,
+    get_u64_le_global
+);
+endian_methods!(
+    i64,
+    "big-endian",
+    to_be_bytes,
+    from_be_bytes,
+    put_i64,
+    peek_i64,
+    get_i64,
+    peek_i64_global,
+    get_i64_global
+);
+endian_methods!(
+    i64,
+    "little-endian",
+    to_le_bytes,
+    from_le_bytes,
+    put_i64_le,
+    peek_i64_le,
+    get_i64_le,
+    peek_i64_le_global,
+    get_i64_le_global
+);
+
+endian_methods!(
+    f32,
+    "big-endian",
+    to_be_bytes,
+    from_be_bytes,
+    put_f32,
+    peek_f32,
+    get_f32,
+    peek_f32_global,
+    get_f32_global
+);
+endian_methods!(
+    f32,
+    "little-endian",
+    to_le_bytes,
+    from_le_bytes,
+    put_f32_le,
+    peek_f32_le,
+    get_f32_le,
+    peek_f32_le_global,
+    get_f32_le_global
+);
+endian_methods!(
+    f64,
+    "big-endian",
+    to_be_bytes,
+    from_be_bytes,
+    put_f64,
+    peek_f64,
+    get_f64,
+    peek_f64_global,
+    get_f64_global
+);
+endian_methods!(
+    f64,
+    "little-endian",
+    to_le_bytes,
+    from_le_bytes,
+    put_f64_le,
+    peek_f64_le,
+    get_f64_le,
+    peek_f64_le_global,
+    get_f64_le_global
+);
+
+#[cfg(test)]
+#[cfg(feature = "tinyslab")]
+mod tests {
+    use super::*;
+    use crate::backend::tinyslab::TinySlabAllocator;
+
+    #[test]
+    fn test_put_and_peek_byte_order() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        let mut w = buf.write(&mut arena);
+        w.put_u32(0x0102_0304).unwrap();
+        w.put_u32_le(0x0102_0304).unwrap();
+        w.put_i16(-2).unwrap();
+
+        let mut out = [0u8; 10];
+        assert_eq!(buf.copy_to_slice(&arena, 0, &mut out), 10);
+        assert_eq!(out, [1, 2, 3, 4, 4, 3, 2, 1, 0xFF, 0xFE]);
+
+        // Peeking reads the front and leaves the buffer as it is
+        assert_eq!(buf.peek_u32(&arena), Ok(0x0102_0304));
+        assert_eq!(buf.peek_u32_le(&arena), Ok(0x0403_0201));
+        assert_eq!(buf.peek_u64(&arena), Ok(0x0102_0304_0403_0201));
+        assert_eq!(
+            buf.peek_f64(&arena).map(f64::to_bits),
+            Ok(0x0102_0304_0403_0201)
+        );
+        assert_eq!(buf.len(), 10);
+
+        let mut short = ByteBuffer::new();
+        short.write(&mut arena).put_u8(7).unwrap();
+        assert_eq!(short.peek_u16(&arena), Err(ByteBufferError::Incomplete));
+        assert_eq!(short.peek_i8(&arena), Ok(7));
+    }
+
+    #[test]
+    fn test_put_is_all_or_nothing() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::with_max_len(6);
+
+        let mut w = buf.write(&mut arena);
+        w.put_u32(1).unwrap();
+        assert_eq!(w.put_u32(2), Err(ByteBufferError::Full));
+        w.put_u16_le(3).unwrap();
+        assert_eq!(buf.len(), 6);
+        assert_eq!(buf.overruns(), 4);
+
+        // Allocator exhaustion part way leaves the buffer as it was
+        let mut small = TinySlabAllocator::<64, 2>::new();
+        let mut buf = ByteBuffer::new();
+        buf.write(&mut small).extend(&[0; 56]).unwrap();
+        assert_eq!(
+            buf.write(&mut small).put_u64(u64::MAX),
+            Err(ByteBufferError::AllocationFailed)
+        );
+        assert_eq!(buf.len(), 56);
+        assert_eq!(buf.get(&small, 55), Some(0));
+        assert_eq!(small.len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_get_across_chunks() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        // 27 filler bytes put the next values across a chunk boundary
+        buf.write(&mut arena).extend(&[0; 27]).unwrap();
+        let mut w = buf.write(&mut arena);
+        w.put_u64_le(0x1122_3344_5566_7788).unwrap();
+        w.put_f32(-0.25).unwrap();
+        w.put_i64(i64::MIN).unwrap();
+        w.put_u16(0xBEEF).unwrap();
+        buf.remove_prefix(&mut arena, 27);
+
+        assert_eq!(buf.get_u64_le(&mut arena), Ok(0x1122_3344_5566_7788));
+        assert_eq!(buf.get_f32(&mut arena), Ok(-0.25));
+        assert_eq!(buf.get_i64(&mut arena), Ok(i64::MIN));
+        assert_eq!(buf.get_u32(&mut arena), Err(ByteBufferError::Incomplete));
+        assert_eq!(buf.len(), 2);
+        assert_eq!(buf.get_u16(&mut arena), Ok(0xBEEF));
+        assert_eq!(buf.get_u8(&mut arena), Err(ByteBufferError::Incomplete));
+        assert!(buf.is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+//! `core::fmt::Write` for ByteBuffer
+//!
+//! Lets `write!` and `writeln!` format straight into a buffer, with no fixed
+//! array in between:
+//!
+//! - [`ByteBufferWrite`] implements `fmt::Write` for any allocator
+//! - [`ByteBuffer`] implements it with the global allocator (`global-alloc`)
+//!
+//! Text is appended like [`extend`](ByteBufferWrite::extend), so the buffer's
+//! overflow policy applies: a `DropOldest` log keeps the newest output, while
+//! the default policy stops at `max_len`.
+//!
+//! `fmt::Error` carries no detail, so the `ByteBufferError` that caused it is
+//! recorded and can be fetched afterwards with
+//! [`ByteBufferWrite::take_fmt_error`] or [`ByteBuffer::take_fmt_error`]. Text
+//! appended before the failure is kept.
+//!
+//! # Feature Flag
+//!
+//! This module is available when the `bytebuffer` feature is enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! use core::fmt::Write;
+//! use tinyalloc::prelude::*;
+//!
+//! let mut alloc = TinySlabAllocator::<1024, 32>::new();
+//! let mut buf = ByteBuffer::with_max_len(16);
+//!
+//! let mut w = buf.write(&mut alloc);
+//! write!(w, "temp={} C", 21).unwrap();
+//! assert!(write!(w, ", humidity={}%", 40).is_err());
+//! assert_eq!(w.take_fmt_error(), Some(ByteBufferError::Full));
+//! assert_eq!(buf.len(), 16);
+//! ```
+
+use core::fmt;
+
+use crate::Allocator;
+#[cfg(feature = "global-alloc")]
+use crate::utils::bytebuffer::ByteBuffer;
+use crate::utils::bytebuffer::{ByteBufferError, ByteBufferWrite};
+
+impl<A: Allocator> ByteBufferWrite<'_, A> {
+    /// Returns and clears the error behind the last `fmt::Error`
+    ///
+    /// `None` if no formatted write has failed on this writer, or if the
+    /// failure came from a `Display` implementation rather than the buffer.
+    pub fn take_fmt_error(&mut self) -> Option<ByteBufferError> {
+        self.fmt_error.take()
+    }
+}
+
+impl<A: Allocator> fmt::Write for ByteBufferWrite<'_, A> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.extend(s.as_bytes()).map_err(|e| {
+            self.fmt_error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+/// Error behind the last `fmt::Error` from a global-allocator buffer
+///
+/// Shared by all buffers, like the global allocator itself: single-threaded
+/// use only.
+#[cfg(feature = "global-alloc")]
+struct FmtErrorSlot(core::cell::Cell<Option<ByteBufferError>>);
+
+#[cfg(feature = "global-alloc")]
+unsafe impl Sync for FmtErrorSlot {}
+
+#[cfg(feature = "global-alloc")]
+static LAST_FMT_ERROR: FmtErrorSlot = FmtErrorSlot(core::cell::Cell::new(None));
+
+#[cfg(feature = "global-alloc")]
+impl ByteBuffer {
+    /// Return and clear the error behind the last `fmt::Error` returned by a
+    /// global-allocator buffer
+    ///
+    /// A `ByteBuffer` has no room to store it, so the error is kept in one
+    /// slot shared by all buffers; fetch it right after the failing `write!`.
+    ///
+    /// ```
+    /// use core::fmt::Write;
+    /// use tinyalloc::prelude::*;
+    ///
+    /// GlobalAllocatorConfig::Slab1K32.init();
+    /// let mut buf = ByteBuffer::with_max_len(4);
+    ///
+    /// assert!(write!(buf, "{}", 123456).is_err());
+    /// assert_eq!(ByteBuffer::take_fmt_error(), Some(ByteBufferError::Full));
+    /// # buf.clear();
+    /// ```
+    pub fn take_fmt_error() -> Option<ByteBufferError> {
+        LAST_FMT_ERROR.0.take()
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl fmt::Write for ByteBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.extend(s.as_bytes()).map_err(|e| {
+            LAST_FMT_ERROR.0.set(Some(e));
+            fmt::Error
+        })
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "tinyslab")]
+mod tests {
+    use super::*;
+    use crate::backend::tinyslab::TinySlabAllocator;
+    use crate::utils::bytebuffer::ByteBuffer;
+    use core::fmt::Write;
+
+    #[test]
+    fn test_write_formatted_across_chunks() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        let mut w = buf.write(&mut arena);
+        for i in 0..10 {
+            writeln!(w, "sample {:>3}: {:.2}", i, i as f32 / 3.0).unwrap();
+        }
+        assert_eq!(w.take_fmt_error(), None);
+        assert_eq!(buf.len(), 10 * 17);
+
+        let mut out = [0u8; 34];
+        buf.copy_to_slice(&arena, 0, &mut out);
+        assert_eq!(&out, b"sample   0: 0.00\nsample   1: 0.33\n");
+    }
+
+    #[test]
+    fn test_write_records_allocation_failure() {
+        let mut arena = TinySlabAllocator::<256, 4>::new();
+        let mut buf = ByteBuffer::new();
+
+        let mut w = buf.write(&mut arena);
+        let long = "0123456789";
+        assert!(w.write_str(long).is_ok());
+        let mut failed = false;
+        for _ in 0..30 {
+            if w.write_str(long).is_err() {
+                failed = true;
+                break;
+            }
+        }
+        assert!(failed);
+        assert_eq!(w.take_fmt_error(), Some(ByteBufferError::AllocationFailed));
+        assert_eq!(w.take_fmt_error(), None);
+        w.clear();
+    }
+
+    #[test]
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_write_honours_overflow_policy() {
+        use crate::utils::bytebuffer::OverflowPolicy;
+
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut log = ByteBuffer::with_overflow_policy(8, OverflowPolicy::DropOldest);
+        let (id, name) = (1, "second");
+        write!(log.write(&mut arena), "first{id}-{name}").unwrap();
+
+        let mut out = [0u8; 8];
+        log.copy_to_slice(&arena, 0, &mut out);
+        assert_eq!(&out, b"1-second");
+    }
+}
@@ -0,0 +1,811 @@
+//! Packet framing codecs over ByteBuffer
+//!
+//! Serial links carry packets as byte streams with a delimiter between them.
+//! This module encodes packets into frames appended to a `ByteBuffer` and pulls
+//! complete, decoded frames back out of a receive buffer as bytes arrive.
+//!
+//! Supported formats ([`Framing`]):
+//!
+//! - **COBS** - Consistent Overhead Byte Stuffing. Frames end with `0x00`, which
+//!   never appears inside a frame; overhead is one byte per 254.
+//! - **SLIP** - RFC 1055. Frames are wrapped in `0xC0`, with `0xC0` and `0xDB`
+//!   escaped inside.
+//! - **HDLC** - HDLC-like framing as in RFC 1662. Frames are wrapped in `0x7E`
+//!   flags, `0x7E`/`0x7D` are escaped as `0x7D, b ^ 0x20`, and a CRC-16/CCITT
+//!   frame check sequence (FCS, the X.25 variant) trails the payload.
+//!
+//! # Receiving
+//!
+//! [`ByteBuffer::pop_frame`] and [`ByteBuffer::read_frame`] look for the next
+//! delimiter. Until one has arrived they return `ByteBufferError::Incomplete`
+//! and consume nothing, so they can be retried after every push. Empty frames
+//! (back-to-back delimiters, as used to flush line noise) are skipped. A
+//! corrupt frame is consumed and reported as `ByteBufferError::Malformed`;
+//! the next call continues with the following frame.
+//!
+//! HDLC frames are checked as they are decoded: the FCS is updated byte by
+//! byte and the two trailing FCS bytes are held back, so a frame with a bad
+//! FCS is reported as `Malformed` and only the payload is ever returned.
+//!
+//! `pop_frame` decodes in place: the frame is split off the receive buffer
+//! and decoded within its own chunks, with no scratch buffer. Frames at least
+//! a chunk long keep their chunks; shorter ones are copied into a fresh
+//! chunk, so popping a frame may allocate.
+//!
+//! # Feature Flag
+//!
+//! This module is only available when the `bytebuffer-stream` feature is enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! use tinyalloc::prelude::*;
+//!
+//! let mut alloc = TinySlabAllocator::<1024, 32>::new();
+//! let mut link = ByteBuffer::new();
+//!
+//! // Sender
+//! link.write(&mut alloc).put_frame(Framing::Cobs, &[0x11, 0x00, 0x22]).unwrap();
+//! assert_eq!(link.len(), 5); // 0x02 0x11 0x02 0x22 0x00
+//!
+//! // Receiver
+//! let packet = link.pop_frame(&mut alloc, Framing::Cobs).unwrap();
+//! let mut out = [0u8; 3];
+//! assert_eq!(packet.copy_to_slice(&alloc, 0, &mut out), 3);
+//! assert_eq!(out, [0x11, 0x00, 0x22]);
+//! assert_eq!(link.pop_frame(&mut alloc, Framing::Cobs).err(), Some(ByteBufferError::Incomplete));
+//! ```
+
+use crate::Allocator;
+#[cfg(feature = "global-alloc")]
+use crate::utils::bytebuffer::GlobalDeque;
+use crate::utils::bytebuffer::{ByteBuffer, ByteBufferError, ByteBufferWrite, ChunkCursor};
+use crate::utils::checksum::{CRC16_X25, Checksum, Crc16};
+
+/// SLIP frame delimiter
+const SLIP_END: u8 = 0xC0;
+/// SLIP escape byte
+const SLIP_ESC: u8 = 0xDB;
+/// Escaped `SLIP_END`
+const SLIP_ESC_END: u8 = 0xDC;
+/// Escaped `SLIP_ESC`
+const SLIP_ESC_ESC: u8 = 0xDD;
+
+/// HDLC flag sequence, opens and closes frames
+const HDLC_FLAG: u8 = 0x7E;
+/// HDLC control escape
+const HDLC_ESC: u8 = 0x7D;
+/// Bit flipped in a byte following `HDLC_ESC`
+const HDLC_XOR: u8 = 0x20;
+
+/// Longest run of non-zero bytes in one COBS block
+const COBS_MAX_RUN: usize = 254;
+
+/// Frame format used by the framing methods
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Framing {
+    /// Consistent Overhead Byte Stuffing, frames ended by `0x00`
+    Cobs,
+    /// Serial Line IP (RFC 1055), frames delimited by `0xC0`
+    Slip,
+    /// HDLC-like framing (RFC 1662), frames delimited by `0x7E` and checked by
+    /// a CRC-16/CCITT FCS
+    Hdlc,
+}
+
+impl Framing {
+    /// Byte that separates frames on the wire
+    pub const fn delimiter(self) -> u8 {
+        match self {
+            Framing::Cobs => 0x00,
+            Framing::Slip => SLIP_END,
+            Framing::Hdlc => HDLC_FLAG,
+        }
+    }
+
+    /// Escape byte of the byte-stuffing formats
+    const fn escape_byte(self) -> u8 {
+        match self {
+            Framing::Hdlc => HDLC_ESC,
+            _ => SLIP_ESC,
+        }
+    }
+
+    /// Byte sent after the escape byte in place of `b`, if `b` must be escaped
+    fn escaped(self, b: u8) -> Option<u8> {
+        match self {
+            Framing::Cobs => None,
+            Framing::Slip => match b {
+                SLIP_END => Some(SLIP_ESC_END),
+                SLIP_ESC => Some(SLIP_ESC_ESC),
+                _ => None,
+            },
+            Framing::Hdlc => matches!(b, HDLC_FLAG | HDLC_ESC).then_some(b ^ HDLC_XOR),
+        }
+    }
+}
+
+/// Incremental frame encoder writing into a buffer
+struct Encoder {
+    framing: Framing,
+    /// Position of the current COBS code byte in the output
+    code_at: usize,
+    /// Data bytes in the current COBS block
+    run: usize,
+    /// Running HDLC FCS
+    fcs: Crc16,
+}
+
+impl Encoder {
+    /// Starts a frame at the end of `out`
+    fn begin<A: Allocator>(
+        framing: Framing,
+        arena: &mut A,
+        out: &mut ByteBuffer,
+    ) -> Result<Self, ByteBufferError> {
+        let mut encoder = Encoder {
+            framing,
+            code_at: 0,
+            run: 0,
+            fcs: Crc16::new(&CRC16_X25),
+        };
+        match framing {
+            Framing::Cobs => encoder.open_block(arena, out)?,
+            // A leading delimiter flushes any line noise received before the frame
+            Framing::Slip | Framing::Hdlc => out.write(arena).put_slice(&[framing.delimiter()])?,
+        }
+        Ok(encoder)
+    }
+
+    /// Encodes `data` into the frame
+    fn push<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        out: &mut ByteBuffer,
+        data: &[u8],
+    ) -> Result<(), ByteBufferError> {
+        if self.framing == Framing::Hdlc {
+            self.fcs.update(data);
+        }
+        let mut rest = data;
+        while !rest.is_empty() {
+            match self.framing {
+                Framing::Cobs => {
+                    let limit = rest.len().min(COBS_MAX_RUN - self.run);
+                    match rest[..limit].iter().position(|&b| b == 0) {
+                        Some(zero) => {
+                            out.write(arena).put_slice(&rest[..zero])?;
+                            self.run += zero;
+                            self.close_block(arena, out)?;
+                            self.open_block(arena, out)?;
+                            rest = &rest[zero + 1..];
+                        }
+                        None => {
+                            out.write(arena).put_slice(&rest[..limit])?;
+                            self.run += limit;
+                            if self.run == COBS_MAX_RUN {
+                                self.close_block(arena, out)?;
+                                self.open_block(arena, out)?;
+                            }
+                            rest = &rest[limit..];
+                        }
+                    }
+                }
+                Framing::Slip | Framing::Hdlc => rest = self.stuff(arena, out, rest)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the run of `data` up to and including its first special byte,
+    /// escaped, and returns the rest
+    fn stuff<'d, A: Allocator>(
+        &self,
+        arena: &mut A,
+        out: &mut ByteBuffer,
+        data: &'d [u8],
+    ) -> Result<&'d [u8], ByteBufferError> {
+        let framing = self.framing;
+        let plain = data
+            .iter()
+            .position(|&b| framing.escaped(b).is_some())
+            .unwrap_or(data.len());
+        out.write(arena).put_slice(&data[..plain])?;
+        match data.get(plain).and_then(|&b| framing.escaped(b)) {
+            Some(escaped) => {
+                out.write(arena)
+                    .put_slice(&[framing.escape_byte(), escaped])?;
+                Ok(&data[plain + 1..])
+            }
+            None => Ok(&[]),
+        }
+    }
+
+    /// Completes the frame with its delimiter
+    fn finish<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        out: &mut ByteBuffer,
+    ) -> Result<(), ByteBufferError> {
+        match self.framing {
+            Framing::Cobs => self.close_block(arena, out)?,
+            Framing::Slip => {}
+            Framing::Hdlc => {
+                // FCS is sent least significant byte first
+                let mut trailer: &[u8] = &self.fcs.finish().to_le_bytes();
+                while !trailer.is_empty() {
+                    trailer = self.stuff(arena, out, trailer)?;
+                }
+            }
+        }
+        out.write(arena).put_slice(&[self.framing.delimiter()])
+    }
+
+    /// Reserves the code byte of a new COBS block
+    fn open_block<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        out: &mut ByteBuffer,
+    ) -> Result<(), ByteBufferError> {
+        self.code_at = out.len() as usize;
+        self.run = 0;
+        out.write(arena).put_slice(&[0])
+    }
+
+    /// Back-patches the code byte of the current COBS block
+    fn close_block<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        out: &mut ByteBuffer,
+    ) -> Result<(), ByteBufferError> {
+        out.set(arena, self.code_at, (self.run + 1) as u8)
+    }
+}
+
+/// Incremental frame decoder fed one wire byte at a time
+struct Decoder {
+    framing: Framing,
+    /// COBS: data bytes left in the current block
+    remaining: u8,
+    /// COBS: a zero is owed before the next block
+    zero_pending: bool,
+    /// SLIP, HDLC: the previous byte was an escape
+    escaped: bool,
+    /// HDLC: running FCS over the bytes released as payload
+    fcs: Crc16,
+    /// HDLC: the last two decoded bytes, which are the FCS if the frame ends
+    held: [u8; 2],
+    /// HDLC: number of bytes in `held`
+    held_len: usize,
+}
+
+impl Decoder {
+    fn new(framing: Framing) -> Self {
+        Decoder {
+            framing,
+            remaining: 0,
+            zero_pending: false,
+            escaped: false,
+            fcs: Crc16::new(&CRC16_X25),
+            held: [0; 2],
+            held_len: 0,
+        }
+    }
+
+    /// Consumes one wire byte, returning the decoded byte it yields, if any
+    fn feed(&mut self, b: u8) -> Result<Option<u8>, ByteBufferError> {
+        match self.framing {
+            Framing::Cobs => {
+                if b == 0 {
+                    return Err(ByteBufferError::Malformed);
+                }
+                if self.remaining > 0 {
+                    self.remaining -= 1;
+                    return Ok(Some(b));
+                }
+                // Code byte: starts a new block
+                let out = self.zero_pending.then_some(0);
+                self.zero_pending = b != 0xFF;
+                self.remaining = b - 1;
+                Ok(out)
+            }
+            Framing::Slip => {
+                if self.escaped {
+                    self.escaped = false;
+                    return match b {
+                        SLIP_ESC_END => Ok(Some(SLIP_END)),
+                        SLIP_ESC_ESC => Ok(Some(SLIP_ESC)),
+                        _ => Err(ByteBufferError::Malformed),
+                    };
+                }
+                if b == SLIP_ESC {
+                    self.escaped = true;
+                    return Ok(None);
+                }
+                Ok(Some(b))
+            }
+            Framing::Hdlc => {
+                let b = if self.escaped {
+                    self.escaped = false;
+                    b ^ HDLC_XOR
+                } else if b == HDLC_ESC {
+                    self.escaped = true;
+                    return Ok(None);
+                } else {
+                    b
+                };
+                if self.held_len < self.held.len() {
+                    self.held[self.held_len] = b;
+                    self.held_len += 1;
+                    return Ok(None);
+                }
+                let out = self.held[0];
+                self.held = [self.held[1], b];
+                self.fcs.update(&[out]);
+                Ok(Some(out))
+            }
+        }
+    }
+
+    /// Checks that the frame did not end part way through an element and,
+    /// for HDLC, that it carried a correct FCS
+    fn finish(&self) -> Result<(), ByteBufferError> {
+        if self.remaining > 0 || self.escaped {
+            return Err(ByteBufferError::Malformed);
+        }
+        if self.framing == Framing::Hdlc
+            && (self.held_len < self.held.len()
+                || self.fcs.finish() != u16::from_le_bytes(self.held))
+        {
+            return Err(ByteBufferError::Malformed);
+        }
+        Ok(())
+    }
+}
+
+impl<A: Allocator> ByteBufferWrite<'_, A> {
+    /// Appends `data` as one complete frame, delimiter included
+    ///
+    /// # Errors
+    ///
+    /// Returns `ByteBufferError::Full` or `ByteBufferError::AllocationFailed`
+    /// if the frame does not fit; nothing is appended.
+    pub fn put_frame(&mut self, framing: Framing, data: &[u8]) -> Result<(), ByteBufferError> {
+        let start = self.buf.len() as usize;
+        let result = Encoder::begin(framing, self.arena, self.buf).and_then(|mut encoder| {
+            encoder.push(self.arena, self.buf, data)?;
+            encoder.finish(self.arena, self.buf)
+        });
+        if result.is_err() {
+            self.buf.truncate(self.arena, start);
+        }
+        result
+    }
+}
+
+impl ByteBuffer {
+    /// Appends the unread bytes of this buffer to `out` as one frame
+    ///
+    /// This buffer is not modified. Bytes are read a chunk-sized piece at a
+    /// time, so the payload is never gathered into one slice.
+    ///
+    /// # Errors
+    ///
+    /// Same as `ByteBufferWrite::put_frame`; `out` is unchanged on error.
+    pub fn encode_frame<A: Allocator>(
+        &self,
+        arena: &mut A,
+        framing: Framing,
+        out: &mut ByteBuffer,
+    ) -> Result<(), ByteBufferError> {
+        let start = out.len() as usize;
+        let result = (|| {
+            let mut encoder = Encoder::begin(framing, arena, out)?;
+            let mut cursor = ChunkCursor::seek(arena, self, 0);
+            let mut left = self.len() as usize;
+            let mut scratch = [0u8; 64];
+            while left > 0 {
+                let want = left.min(scratch.len());
+                let n = cursor.read(arena, &mut scratch[..want]);
+                if n == 0 {
+                    return Err(ByteBufferError::Uninitialized);
+                }
+                encoder.push(arena, out, &scratch[..n])?;
+                left -= n;
+            }
+            encoder.finish(arena, out)
+        })();
+        if result.is_err() {
+            out.truncate(arena, start);
+        }
+        result
+    }
+
+    /// Removes the next complete frame and returns its decoded payload
+    ///
+    /// The frame is split off with
+    /// [`split_off_front`](ByteBuffer::split_off_front) and decoded in place.
+    /// Frames at least a chunk long have their chunks relinked into the
+    /// returned buffer; shorter ones are copied into a fresh chunk. Either way
+    /// may allocate.
+    ///
+    /// # Errors
+    ///
+    /// - `ByteBufferError::Incomplete` - no complete frame yet; nothing consumed
+    /// - `ByteBufferError::Malformed` - the frame is corrupt; it is consumed
+    /// - `ByteBufferError::AllocationFailed` - no chunk to split the frame off;
+    ///   nothing consumed
+    pub fn pop_frame<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        framing: Framing,
+    ) -> Result<ByteBuffer, ByteBufferError> {
+        let end = self.next_frame_end(arena, framing)?;
+        let mut frame = self.split_off_front(arena, end + 1)?;
+
+        // Decoded bytes never outrun encoded ones, so writing behind the read
+        // cursor only overwrites bytes already decoded
+        let mut decoder = Decoder::new(framing);
+        let mut reader = ChunkCursor::seek(arena, &frame, 0);
+        let mut writer = reader;
+        let mut len = 0;
+        let mut result = Ok(());
+        for _ in 0..end {
+            let Some(b) = reader.next_byte(arena) else {
+                result = Err(ByteBufferError::Uninitialized);
+                break;
+            };
+            match decoder.feed(b) {
+                Ok(Some(out)) => {
+                    writer.write(arena, &[out]);
+                    len += 1;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        if let Err(e) = result.and_then(|_| decoder.finish()) {
+            frame.write(arena).clear();
+            return Err(e);
+        }
+        frame.truncate(arena, len);
+        Ok(frame)
+    }
+
+    /// Removes the next complete frame and decodes it into `dest`
+    ///
+    /// Returns the payload length.
+    ///
+    /// # Errors
+    ///
+    /// As [`pop_frame`](Self::pop_frame), plus `ByteBufferError::Overflow` if
+    /// the payload is longer than `dest`; the frame is consumed.
+    pub fn read_frame<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        framing: Framing,
+        dest: &mut [u8],
+    ) -> Result<usize, ByteBufferError> {
+        let end = self.next_frame_end(arena, framing)?;
+
+        let mut decoder = Decoder::new(framing);
+        let mut reader = ChunkCursor::seek(arena, self, 0);
+        let mut len = 0;
+        let mut result = Ok(());
+        for _ in 0..end {
+            let Some(b) = reader.next_byte(arena) else {
+                result = Err(ByteBufferError::Uninitialized);
+                break;
+            };
+            match decoder.feed(b) {
+                Ok(Some(out)) if len < dest.len() => {
+                    dest[len] = out;
+                    len += 1;
+                }
+                Ok(Some(_)) => result = result.and(Err(ByteBufferError::Overflow)),
+                Ok(None) => {}
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        self.remove_prefix(arena, end + 1);
+        result.and_then(|_| decoder.finish()).map(|_| len)
+    }
+
+    /// Skips empty frames and returns the position of the next frame's delimiter
+    fn next_frame_end<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        framing: Framing,
+    ) -> Result<usize, ByteBufferError> {
+        let delimiter = framing.delimiter();
+        loop {
+            let end = self
+                .position(arena, |b| b == delimiter)
+                .ok_or(ByteBufferError::Incomplete)?;
+            if end > 0 {
+                return Ok(end);
+            }
+            self.remove_prefix(arena, 1);
+        }
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl ByteBuffer {
+    /// Append `data` as one frame using the global allocator
+    pub fn put_frame(&mut self, framing: Framing, data: &[u8]) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.write(alloc).put_frame(framing, data))
+    }
+
+    /// Append this buffer to `out` as one frame (global allocator version)
+    pub fn encode_frame_global(
+        &self,
+        framing: Framing,
+        out: &mut ByteBuffer,
+    ) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.encode_frame(alloc, framing, out))
+    }
+
+    /// Remove and decode the next complete frame (global allocator version)
+    pub fn pop_frame_global(&mut self, framing: Framing) -> Result<ByteBuffer, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.pop_frame(alloc, framing))
+    }
+
+    /// Remove the next complete frame, decoding into `dest` (global allocator version)
+    pub fn read_frame_global(
+        &mut self,
+        framing: Framing,
+        dest: &mut [u8],
+    ) -> Result<usize, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.read_frame(alloc, framing, dest))
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl GlobalDeque {
+    /// Remove and decode the next complete frame
+    ///
+    /// See [`ByteBuffer::pop_frame`].
+    pub fn pop_frame(&self, framing: Framing) -> Result<ByteBuffer, ByteBufferError> {
+        self.with_mut(|buf| buf.pop_frame_global(framing))
+    }
+
+    /// Remove the next complete frame, decoding it into `dest`
+    ///
+    /// See [`ByteBuffer::read_frame`].
+    pub fn read_frame(&self, framing: Framing, dest: &mut [u8]) -> Result<usize, ByteBufferError> {
+        self.with_mut(|buf| buf.read_frame_global(framing, dest))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "tinyslab")]
+mod tests {
+    use super::*;
+    use crate::backend::tinyslab::TinySlabAllocator;
+
+    fn encoded<A: Allocator>(arena: &mut A, framing: Framing, data: &[u8]) -> ([u8; 600], usize) {
+        let mut buf = ByteBuffer::new();
+        buf.write(arena).put_frame(framing, data).unwrap();
+        let mut out = [0u8; 600];
+        let n = buf.copy_to_slice(arena, 0, &mut out);
+        buf.write(arena).clear();
+        (out, n)
+    }
+
+    #[test]
+    fn test_cobs_encoding_vectors() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+
+        let (out, n) = encoded(&mut arena, Framing::Cobs, &[]);
+        assert_eq!(&out[..n], &[0x01, 0x00]);
+        let (out, n) = encoded(&mut arena, Framing::Cobs, &[0x00]);
+        assert_eq!(&out[..n], &[0x01, 0x01, 0x00]);
+        let (out, n) = encoded(&mut arena, Framing::Cobs, &[0x11, 0x22, 0x00, 0x33]);
+        assert_eq!(&out[..n], &[0x03, 0x11, 0x22, 0x02, 0x33, 0x00]);
+
+        // 254 non-zero bytes fill a block; the code byte is 0xFF
+        let data: [u8; 300] = core::array::from_fn(|i| (i % 255) as u8 + 1);
+        let (out, n) = encoded(&mut arena, Framing::Cobs, &data);
+        assert_eq!(out[0], 0xFF);
+        assert_eq!(&out[1..255], &data[..254]);
+        assert_eq!(out[255], 47);
+        assert_eq!(n, 1 + 254 + 1 + 46 + 1);
+        assert!(out[..n - 1].iter().all(|&b| b != 0));
+    }
+
+    #[test]
+    fn test_slip_encoding_vectors() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let (out, n) = encoded(&mut arena, Framing::Slip, &[0x01, 0xC0, 0xDB, 0x02]);
+        assert_eq!(&out[..n], &[0xC0, 0x01, 0xDB, 0xDC, 0xDB, 0xDD, 0x02, 0xC0]);
+    }
+
+    #[test]
+    fn test_hdlc_encoding() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let (out, n) = encoded(&mut arena, Framing::Hdlc, b"123456789");
+        assert_eq!(out[0], 0x7E);
+        assert_eq!(&out[1..10], b"123456789");
+        assert_eq!(&out[10..n], &[0x6E, 0x90, 0x7E]);
+
+        let (out, n) = encoded(&mut arena, Framing::Hdlc, &[0x7E, 0x01, 0x7D]);
+        assert_eq!(&out[..6], &[0x7E, 0x7D, 0x5E, 0x01, 0x7D, 0x5D]);
+        assert_eq!(out[n - 1], 0x7E);
+        assert!(out[1..n - 1].iter().all(|&b| b != 0x7E));
+    }
+
+    #[test]
+    fn test_hdlc_rejects_bad_fcs() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut rx = ByteBuffer::new();
+
+        // Flip one payload bit on the wire
+        rx.write(&mut arena)
+            .put_frame(Framing::Hdlc, b"modem")
+            .unwrap();
+        let b = rx.get(&arena, 2).unwrap();
+        rx.set(&mut arena, 2, b ^ 0x01).unwrap();
+        rx.write(&mut arena)
+            .put_frame(Framing::Hdlc, b"ok")
+            .unwrap();
+
+        assert_eq!(
+            rx.pop_frame(&mut arena, Framing::Hdlc).err(),
+            Some(ByteBufferError::Malformed)
+        );
+        // Only the payload fits the destination; the FCS is never copied
+        let mut out = [0u8; 2];
+        assert_eq!(rx.read_frame(&mut arena, Framing::Hdlc, &mut out), Ok(2));
+        assert_eq!(&out, b"ok");
+
+        // Too short to hold an FCS
+        rx.write(&mut arena).extend(&[0x7E, 0x01, 0x7E]).unwrap();
+        assert_eq!(
+            rx.read_frame(&mut arena, Framing::Hdlc, &mut out),
+            Err(ByteBufferError::Malformed)
+        );
+        assert!(rx.is_empty());
+        assert_eq!(arena.len(), 0);
+    }
+
+    #[test]
+    fn test_roundtrip_pop_and_read() {
+        let mut arena = TinySlabAllocator::<4096, 128>::new();
+        let data: [u8; 120] =
+            core::array::from_fn(|i| [0x00, 0xC0, 0xDB, 0x7E, 0x7D, i as u8][i % 6]);
+
+        for framing in [Framing::Cobs, Framing::Slip, Framing::Hdlc] {
+            let mut link = ByteBuffer::new();
+            link.write(&mut arena).put_frame(framing, &data).unwrap();
+            link.write(&mut arena)
+                .put_frame(framing, b"second")
+                .unwrap();
+
+            let frame = link.pop_frame(&mut arena, framing).unwrap();
+            assert_eq!(frame.len(), 120);
+            let mut out = [0u8; 120];
+            frame.copy_to_slice(&arena, 0, &mut out);
+            assert_eq!(out, data);
+
+            let mut small = [0u8; 8];
+            assert_eq!(link.read_frame(&mut arena, framing, &mut small), Ok(6));
+            assert_eq!(&small[..6], b"second");
+            assert!(link.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_incremental_receive() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut wire = ByteBuffer::new();
+        wire.write(&mut arena)
+            .put_frame(Framing::Slip, b"ping")
+            .unwrap();
+        let mut bytes = [0u8; 16];
+        let n = wire.copy_to_slice(&arena, 0, &mut bytes);
+
+        // Feed byte by byte: only the final delimiter completes the frame
+        let mut rx = ByteBuffer::new();
+        let mut out = [0u8; 8];
+        for (i, &b) in bytes[..n].iter().enumerate() {
+            rx.write(&mut arena).append(b).unwrap();
+            let result = rx.read_frame(&mut arena, Framing::Slip, &mut out);
+            if i + 1 < n {
+                assert_eq!(result, Err(ByteBufferError::Incomplete));
+            } else {
+                assert_eq!(result, Ok(4));
+            }
+        }
+        assert_eq!(&out[..4], b"ping");
+    }
+
+    #[test]
+    fn test_corrupt_frames_are_reported_and_skipped() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut rx = ByteBuffer::new();
+
+        // COBS block claims more bytes than the frame holds
+        rx.write(&mut arena).extend(&[0x05, 0x11, 0x00]).unwrap();
+        rx.write(&mut arena)
+            .put_frame(Framing::Cobs, b"ok")
+            .unwrap();
+        assert_eq!(
+            rx.pop_frame(&mut arena, Framing::Cobs).err(),
+            Some(ByteBufferError::Malformed)
+        );
+        let mut out = [0u8; 1];
+        assert_eq!(
+            rx.read_frame(&mut arena, Framing::Cobs, &mut out),
+            Err(ByteBufferError::Overflow)
+        );
+        assert!(rx.is_empty());
+        assert_eq!(arena.len(), 0);
+
+        // SLIP escape followed by an invalid byte
+        rx.write(&mut arena)
+            .extend(&[0xC0, 0x01, 0xDB, 0x01, 0xC0])
+            .unwrap();
+        assert_eq!(
+            rx.read_frame(&mut arena, Framing::Slip, &mut [0u8; 8]),
+            Err(ByteBufferError::Malformed)
+        );
+        assert!(rx.is_empty());
+    }
+
+    #[test]
+    fn test_encode_frame_from_buffer() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut payload = ByteBuffer::new();
+        let data: [u8; 70] = core::array::from_fn(|i| (i % 7) as u8);
+        payload.write(&mut arena).extend(&data).unwrap();
+
+        let mut link = ByteBuffer::new();
+        payload
+            .encode_frame(&mut arena, Framing::Cobs, &mut link)
+            .unwrap();
+        assert_eq!(payload.len(), 70);
+
+        let mut out = [0u8; 70];
+        assert_eq!(link.read_frame(&mut arena, Framing::Cobs, &mut out), Ok(70));
+        assert_eq!(out, data);
+
+        // A frame that does not fit leaves the output untouched
+        let mut bounded = ByteBuffer::with_max_len(40);
+        bounded.write(&mut arena).extend(b"keep").unwrap();
+        assert_eq!(
+            payload.encode_frame(&mut arena, Framing::Slip, &mut bounded),
+            Err(ByteBufferError::Full)
+        );
+        assert_eq!(bounded.len(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "global-alloc")]
+    fn test_global_deque_frames() {
+        use crate::global::GlobalAllocatorConfig;
+
+        static RX: GlobalDeque = GlobalDeque::new();
+        GlobalAllocatorConfig::Slab1K32.init();
+
+        let mut wire = ByteBuffer::new();
+        wire.put_frame(Framing::Cobs, &[1, 0, 2]).unwrap();
+        while let Some(b) = wire.pop_front_global() {
+            RX.push(b).unwrap();
+        }
+        let mut frame = RX.pop_frame(Framing::Cobs).unwrap();
+        assert_eq!(frame.len(), 3);
+        assert_eq!(frame.get_global(1), Some(0));
+        assert_eq!(
+            RX.read_frame(Framing::Cobs, &mut [0u8; 4]),
+            Err(ByteBufferError::Incomplete)
+        );
+        frame.clear();
+    }
+}
@@ -0,0 +1,184 @@
+//! Byte-stream adapter behind the I/O trait integrations
+//!
+//! I/O traits take `&mut self` and no allocator, so a `ByteBuffer` cannot
+//! implement them directly without the global allocator. [`ByteBufferIo`]
+//! pairs a buffer with its allocator and exposes it as a FIFO stream:
+//!
+//! - reads consume bytes from the front
+//! - writes append at the back, honouring `max_len` and the overflow policy
+//! - the buffered view is the unread part of the head chunk, so `BufRead`
+//!   consumers parse straight out of the allocator without copying
+//!
+//! `GlobalDeque` gets the reads and writes through `&GlobalDeque`, like `std`
+//! does for `&File`, so a `static` queue can be handed to I/O code. It has no
+//! buffered view: any `&GlobalDeque` call may free the head chunk, so a slice
+//! into it cannot be handed out safely. [`GlobalDequeReader`] (from
+//! [`GlobalDeque::reader`]) provides `BufRead` instead, by moving bytes out
+//! of the deque into a small buffer of its own.
+
+use crate::Allocator;
+#[cfg(feature = "global-alloc")]
+use crate::utils::bytebuffer::GlobalDeque;
+use crate::utils::bytebuffer::{ByteBuffer, ByteBufferError};
+
+/// A ByteBuffer and its allocator used as a byte stream
+///
+/// Created with [`ByteBuffer::io`].
+pub struct ByteBufferIo<'a, A: Allocator> {
+    arena: &'a mut A,
+    buf: &'a mut ByteBuffer,
+}
+
+impl ByteBuffer {
+    /// Stream context for I/O traits
+    ///
+    /// Reads consume from the front of the buffer and writes append to the
+    /// back.
+    pub fn io<'a, A: Allocator>(&'a mut self, arena: &'a mut A) -> ByteBufferIo<'a, A> {
+        ByteBufferIo { arena, buf: self }
+    }
+}
+
+impl<A: Allocator> ByteBufferIo<'_, A> {
+    /// Returns the underlying buffer
+    pub fn buffer(&self) -> &ByteBuffer {
+        self.buf
+    }
+
+    /// Moves up to `dest.len()` bytes from the front into `dest`
+    pub(crate) fn read_front(&mut self, dest: &mut [u8]) -> usize {
+        read_front(self.arena, self.buf, dest)
+    }
+
+    /// Appends as much of `data` as fits
+    pub(crate) fn write_back(&mut self, data: &[u8]) -> Result<usize, ByteBufferError> {
+        write_back(self.arena, self.buf, data)
+    }
+
+    /// Returns the unread bytes of the head chunk
+    pub(crate) fn head_chunk(&self) -> &[u8] {
+        head_chunk(&*self.arena, self.buf)
+    }
+
+    /// Drops `count` bytes from the front
+    pub(crate) fn consume_front(&mut self, count: usize) {
+        self.buf.remove_prefix(self.arena, count);
+    }
+}
+
+/// Buffered reader taking bytes out of a `GlobalDeque`
+///
+/// Created with [`GlobalDeque::reader`]. Each refill moves up to `N` bytes
+/// from the front of the deque into the reader, so the buffered view stays
+/// valid whatever else touches the deque. Like `std::io::BufReader`, bytes
+/// still buffered when the reader is dropped are lost.
+#[cfg(feature = "global-alloc")]
+pub struct GlobalDequeReader<'a, const N: usize = 32> {
+    deque: &'a GlobalDeque,
+    buf: [u8; N],
+    pos: usize,
+    filled: usize,
+}
+
+#[cfg(feature = "global-alloc")]
+impl<'a, const N: usize> GlobalDequeReader<'a, N> {
+    /// Creates a reader buffering up to `N` bytes at a time
+    pub const fn new(deque: &'a GlobalDeque) -> Self {
+        Self {
+            deque,
+            buf: [0; N],
+            pos: 0,
+            filled: 0,
+        }
+    }
+
+    /// Returns the underlying deque
+    pub fn deque(&self) -> &'a GlobalDeque {
+        self.deque
+    }
+
+    /// Returns the buffered bytes, refilling from the deque when empty
+    pub(crate) fn fill(&mut self) -> &[u8] {
+        if self.pos == self.filled {
+            self.filled = self.deque.read_front(&mut self.buf);
+            self.pos = 0;
+        }
+        &self.buf[self.pos..self.filled]
+    }
+
+    /// Drops `count` buffered bytes
+    pub(crate) fn consume_buffered(&mut self, count: usize) {
+        self.pos = (self.pos + count).min(self.filled);
+    }
+
+    /// Moves up to `dest.len()` bytes into `dest`, buffered bytes first
+    pub(crate) fn read_buffered(&mut self, dest: &mut [u8]) -> usize {
+        if self.pos == self.filled {
+            return self.deque.read_front(dest);
+        }
+        let n = dest.len().min(self.filled - self.pos);
+        dest[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+        self.pos += n;
+        n
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl GlobalDeque {
+    /// Buffered reader for `BufRead` consumers
+    ///
+    /// Uses a 32-byte buffer; call [`GlobalDequeReader::new`] for another
+    /// size.
+    pub fn reader(&self) -> GlobalDequeReader<'_> {
+        GlobalDequeReader::new(self)
+    }
+
+    /// Moves up to `dest.len()` bytes from the front into `dest`
+    pub(crate) fn read_front(&self, dest: &mut [u8]) -> usize {
+        self.with_mut(|buf| {
+            crate::global::with_global_allocator(|alloc| read_front(alloc, buf, dest))
+        })
+    }
+
+    /// Appends as much of `data` as fits, waking a waiting consumer
+    pub(crate) fn write_back(&self, data: &[u8]) -> Result<usize, ByteBufferError> {
+        let written = self.with_mut(|buf| {
+            crate::global::with_global_allocator(|alloc| write_back(alloc, buf, data))
+        })?;
+        #[cfg(feature = "deque-async")]
+        if written > 0 {
+            self.wake();
+        }
+        Ok(written)
+    }
+}
+
+fn read_front<A: Allocator>(arena: &mut A, buf: &mut ByteBuffer, dest: &mut [u8]) -> usize {
+    let n = buf.copy_to_slice(arena, 0, dest);
+    buf.remove_prefix(arena, n)
+}
+
+/// Appends `data`, reporting a partial write if the buffer fills part way
+///
+/// Fails only when not a single byte could be stored.
+fn write_back<A: Allocator>(
+    arena: &mut A,
+    buf: &mut ByteBuffer,
+    data: &[u8],
+) -> Result<usize, ByteBufferError> {
+    let before = buf.len();
+    match buf.write(arena).extend(data) {
+        Ok(()) => Ok(data.len()),
+        Err(e) => match buf.len().saturating_sub(before) as usize {
+            0 => Err(e),
+            written => Ok(written),
+        },
+    }
+}
+
+fn head_chunk<'a, A: Allocator>(arena: &'a A, buf: &'a ByteBuffer) -> &'a [u8] {
+    buf.read(arena)
+        .chunks()
+        .find(|chunk| !chunk.is_empty())
+        .unwrap_or(&[])
+}
@@ -0,0 +1,304 @@
+//! Line-oriented reading for text protocols
+//!
+//! AT-command replies, NMEA sentences and similar protocols are sequences of
+//! lines ended by `\r\n` or a bare `\n`. The readers here take one complete
+//! line off the front of a buffer and strip its terminator:
+//!
+//! - [`ByteBuffer::read_line`] copies the line into a caller-provided slice,
+//!   whose length is the maximum line length
+//! - [`ByteBuffer::next_line`] splits the line off into its own buffer, up to
+//!   a given maximum line length; see
+//!   [`split_off_front`](ByteBuffer::split_off_front) for when chunks are
+//!   relinked and when a short line is copied
+//!
+//! Both are also available on [`GlobalDeque`], so an interrupt handler can
+//! push bytes while the main loop pulls lines.
+//!
+//! # Feature Flag
+//!
+//! This module is only available when the `bytebuffer-stream` feature is enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! use tinyalloc::prelude::*;
+//!
+//! let mut alloc = TinySlabAllocator::<1024, 32>::new();
+//! let mut buf = ByteBuffer::new();
+//! buf.write(&mut alloc).extend(b"+CSQ: 21,0\r\nOK\r\n$GP").unwrap();
+//!
+//! let mut line = [0u8; 16];
+//! assert_eq!(buf.read_line(&mut alloc, &mut line), Ok(10));
+//! assert_eq!(&line[..10], b"+CSQ: 21,0");
+//! assert_eq!(buf.read_line(&mut alloc, &mut line), Ok(2));
+//! assert_eq!(buf.read_line(&mut alloc, &mut line), Err(ByteBufferError::Incomplete));
+//! ```
+
+use crate::Allocator;
+#[cfg(feature = "global-alloc")]
+use crate::utils::bytebuffer::GlobalDeque;
+use crate::utils::bytebuffer::{ByteBuffer, ByteBufferError};
+
+impl ByteBuffer {
+    /// Copies the next complete line into `dest` and consumes it
+    ///
+    /// The terminator (`\n`, with a preceding `\r` if present) is consumed
+    /// but not copied. Returns the line length.
+    ///
+    /// # Errors
+    ///
+    /// - `ByteBufferError::Incomplete` - no terminator buffered yet; nothing
+    ///   is consumed
+    /// - `ByteBufferError::Overflow` - the line is longer than `dest`. A
+    ///   complete line is consumed and dropped, so the next call continues
+    ///   with the following line. If the terminator has not arrived yet
+    ///   nothing is consumed; skip the rest of the line with
+    ///   [`consume_until`](Self::consume_until) once it has.
+    pub fn read_line<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        dest: &mut [u8],
+    ) -> Result<usize, ByteBufferError> {
+        let len = self.len() as usize;
+        let Some(end) = self.position(arena, |b| b == b'\n') else {
+            // A trailing '\r' may still turn out to be part of the terminator
+            let pending = len - usize::from(self.ends_with(arena, b"\r"));
+            return Err(if pending > dest.len() {
+                ByteBufferError::Overflow
+            } else {
+                ByteBufferError::Incomplete
+            });
+        };
+
+        let line = self.line_len(arena, end);
+        let result = if line > dest.len() {
+            Err(ByteBufferError::Overflow)
+        } else {
+            Ok(self.copy_to_slice(arena, 0, &mut dest[..line]))
+        };
+        self.remove_prefix(arena, end + 1);
+        result
+    }
+
+    /// Splits the next complete line off into its own buffer
+    ///
+    /// The terminator is consumed and not included. Lines are split off with
+    /// [`split_off_front`](Self::split_off_front), so long lines move their
+    /// chunks and short ones are copied into a fresh chunk.
+    ///
+    /// # Errors
+    ///
+    /// - `ByteBufferError::Incomplete` - no terminator buffered yet; nothing
+    ///   is consumed
+    /// - `ByteBufferError::Overflow` - the line is longer than `max`. Handled
+    ///   as in [`read_line`](Self::read_line): a complete line is consumed and
+    ///   dropped, an unterminated one is left in place.
+    /// - `ByteBufferError::AllocationFailed` - no chunk for the split; nothing
+    ///   is consumed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tinyalloc::prelude::*;
+    ///
+    /// let mut alloc = TinySlabAllocator::<1024, 32>::new();
+    /// let mut buf = ByteBuffer::new();
+    /// buf.write(&mut alloc).extend(b"$GPGGA,123519\nrest").unwrap();
+    ///
+    /// let line = buf.next_line(&mut alloc, 82).unwrap();
+    /// assert_eq!(line.len(), 13);
+    /// assert!(line.starts_with(&alloc, b"$GPGGA"));
+    /// assert_eq!(buf.next_line(&mut alloc, 82).err(), Some(ByteBufferError::Incomplete));
+    /// ```
+    pub fn next_line<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        max: usize,
+    ) -> Result<ByteBuffer, ByteBufferError> {
+        let len = self.len() as usize;
+        let Some(end) = self.position(arena, |b| b == b'\n') else {
+            let pending = len - usize::from(self.ends_with(arena, b"\r"));
+            return Err(if pending > max {
+                ByteBufferError::Overflow
+            } else {
+                ByteBufferError::Incomplete
+            });
+        };
+
+        let line = self.line_len(arena, end);
+        if line > max {
+            self.remove_prefix(arena, end + 1);
+            return Err(ByteBufferError::Overflow);
+        }
+        let mut front = self.split_off_front(arena, end + 1)?;
+        front.truncate(arena, line);
+        Ok(front)
+    }
+
+    /// Length of the line ending with the `\n` at `end`, without its terminator
+    fn line_len<A: Allocator>(&self, arena: &A, end: usize) -> usize {
+        match end.checked_sub(1) {
+            Some(cr) if self.get(arena, cr) == Some(b'\r') => cr,
+            _ => end,
+        }
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl ByteBuffer {
+    /// Copy the next complete line into `dest` (global allocator version)
+    pub fn read_line_global(&mut self, dest: &mut [u8]) -> Result<usize, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.read_line(alloc, dest))
+    }
+
+    /// Split the next complete line off (global allocator version)
+    pub fn next_line_global(&mut self, max: usize) -> Result<ByteBuffer, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.next_line(alloc, max))
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl GlobalDeque {
+    /// Copy the next complete line into `dest`
+    ///
+    /// See [`ByteBuffer::read_line`].
+    pub fn read_line(&self, dest: &mut [u8]) -> Result<usize, ByteBufferError> {
+        self.with_mut(|buf| buf.read_line_global(dest))
+    }
+
+    /// Split the next complete line off into a buffer backed by the global allocator
+    ///
+    /// See [`ByteBuffer::next_line`].
+    pub fn next_line(&self, max: usize) -> Result<ByteBuffer, ByteBufferError> {
+        self.with_mut(|buf| buf.next_line_global(max))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "tinyslab")]
+mod tests {
+    use super::*;
+    use crate::backend::tinyslab::TinySlabAllocator;
+
+    #[test]
+    fn test_read_line_terminators() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+        buf.write(&mut arena)
+            .extend(b"AT\r\nOK\n\r\n\nlast")
+            .unwrap();
+
+        let mut line = [0u8; 8];
+        assert_eq!(buf.read_line(&mut arena, &mut line), Ok(2));
+        assert_eq!(&line[..2], b"AT");
+        assert_eq!(buf.read_line(&mut arena, &mut line), Ok(2));
+        assert_eq!(&line[..2], b"OK");
+        assert_eq!(buf.read_line(&mut arena, &mut line), Ok(0));
+        assert_eq!(buf.read_line(&mut arena, &mut line), Ok(0));
+        assert_eq!(
+            buf.read_line(&mut arena, &mut line),
+            Err(ByteBufferError::Incomplete)
+        );
+        assert_eq!(buf.len(), 4);
+
+        buf.write(&mut arena).extend(b"\r").unwrap();
+        assert_eq!(
+            buf.read_line(&mut arena, &mut line[..4]),
+            Err(ByteBufferError::Incomplete)
+        );
+        buf.write(&mut arena).extend(b"\n").unwrap();
+        assert_eq!(buf.read_line(&mut arena, &mut line[..4]), Ok(4));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_read_line_overflow() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+        let mut line = [0u8; 4];
+
+        // Too long and still open: nothing consumed
+        buf.write(&mut arena).extend(b"TOOLONG").unwrap();
+        assert_eq!(
+            buf.read_line(&mut arena, &mut line),
+            Err(ByteBufferError::Overflow)
+        );
+        assert_eq!(buf.len(), 7);
+
+        // Once complete the long line is dropped and reading resumes
+        buf.write(&mut arena).extend(b"\r\nOK\r\n").unwrap();
+        assert_eq!(
+            buf.read_line(&mut arena, &mut line),
+            Err(ByteBufferError::Overflow)
+        );
+        assert_eq!(buf.read_line(&mut arena, &mut line), Ok(2));
+        assert_eq!(&line[..2], b"OK");
+    }
+
+    #[test]
+    fn test_next_line_across_chunks() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+        let sentence = b"$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M*47";
+        buf.write(&mut arena).extend(sentence).unwrap();
+        buf.write(&mut arena).extend(b"\r\n\n$GPRMC").unwrap();
+
+        let line = buf.next_line(&mut arena, 82).unwrap();
+        assert_eq!(line.len() as usize, sentence.len());
+        assert!(line.ends_with(&arena, b"*47"));
+        let empty = buf.next_line(&mut arena, 82).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(
+            buf.next_line(&mut arena, 82).err(),
+            Some(ByteBufferError::Incomplete)
+        );
+        assert!(buf.starts_with(&arena, b"$GPRMC"));
+    }
+
+    #[test]
+    fn test_next_line_overflow() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        // An unterminated flood is reported without being consumed
+        buf.write(&mut arena).extend(&[b'x'; 40]).unwrap();
+        assert_eq!(
+            buf.next_line(&mut arena, 16).err(),
+            Some(ByteBufferError::Overflow)
+        );
+        assert_eq!(buf.len(), 40);
+
+        // Once complete the long line is dropped and reading resumes
+        buf.write(&mut arena).extend(b"\r\nOK\r\n").unwrap();
+        assert_eq!(
+            buf.next_line(&mut arena, 16).err(),
+            Some(ByteBufferError::Overflow)
+        );
+        let line = buf.next_line(&mut arena, 16).unwrap();
+        assert!(line.starts_with(&arena, b"OK"));
+        assert_eq!(line.len(), 2);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "global-alloc")]
+    fn test_global_deque_lines() {
+        use crate::global::GlobalAllocatorConfig;
+
+        static RX: GlobalDeque = GlobalDeque::new();
+        GlobalAllocatorConfig::Slab1K32.init();
+
+        for &b in b"RING\r\n+CLIP: \"123\"\r\n" {
+            RX.push(b).unwrap();
+        }
+        let mut line = [0u8; 16];
+        assert_eq!(RX.read_line(&mut line), Ok(4));
+        assert_eq!(&line[..4], b"RING");
+
+        let mut clip = RX.next_line(16).unwrap();
+        assert_eq!(clip.len(), 12);
+        assert!(clip.starts_with_global(b"+CLIP"));
+        assert!(RX.is_empty());
+        clip.clear();
+    }
+}
@@ -5,5 +5,50 @@
 #[cfg(feature = "bytebuffer")]
 pub mod bytebuffer;
 
+#[cfg(feature = "bytebuffer")]
+pub mod endian;
+
+#[cfg(feature = "bytebuffer")]
+pub mod varint;
+
+#[cfg(feature = "bytebuffer")]
+pub mod search;
+
+#[cfg(feature = "bytebuffer")]
+pub mod checksum;
+
+#[cfg(feature = "bytebuffer")]
+pub mod textcodec;
+
+#[cfg(feature = "bytebuffer")]
+pub mod fmtwrite;
+
+#[cfg(feature = "bytebuffer")]
+pub mod textbuffer;
+
+#[cfg(feature = "bytebuffer")]
+pub mod compare;
+
+#[cfg(feature = "bytebuffer-stream")]
+pub mod lines;
+
+#[cfg(feature = "bytebuffer-stream")]
+pub mod framing;
+
+#[cfg(feature = "bytebuffer-stream")]
+pub mod msgqueue;
+
 #[cfg(feature = "nominput")]
 pub mod nominput;
+
+#[cfg(any(feature = "embedded-io", feature = "std"))]
+pub mod ioadapter;
+
+#[cfg(feature = "embedded-io")]
+pub mod embeddedio;
+
+#[cfg(feature = "std")]
+pub mod stdio;
+
+#[cfg(feature = "deque-async")]
+pub mod asyncdeque;
@@ -0,0 +1,462 @@
+//! Queue of variable-length messages stored in allocator chunks
+//!
+//! [`ByteBuffer`] and [`GlobalDeque`] queue bytes; [`MessageQueue`] queues
+//! discrete messages. Each message is stored as a LEB128 length header
+//! followed by its payload, all packed into one chunk chain, so short messages
+//! share chunks and a one-byte message costs two bytes of buffer space.
+//!
+//! - `push_message(arena, data)` appends a whole message or nothing
+//! - `peek_len(arena)` returns the length of the oldest message
+//! - `pop_message_into(arena, dest)` copies the oldest message out
+//! - `pop_message(arena)` hands the oldest message over as a `ByteBuffer`;
+//!   long payloads keep their chunks, messages shorter than a chunk are
+//!   copied into a fresh one
+//!
+//! [`GlobalMessageQueue`] wraps a queue for static, single-threaded use with
+//! the global allocator, like [`GlobalDeque`] does for bytes.
+//!
+//! # Feature Flag
+//!
+//! This module is only available when the `bytebuffer-stream` feature is enabled.
+//! `GlobalMessageQueue` also requires `global-alloc`.
+//!
+//! # Examples
+//!
+//! ```
+//! use tinyalloc::prelude::*;
+//!
+//! let mut alloc = TinySlabAllocator::<1024, 32>::new();
+//! let mut queue = MessageQueue::new();
+//!
+//! queue.push_message(&mut alloc, b"hello").unwrap();
+//! queue.push_message(&mut alloc, b"").unwrap();
+//! queue.push_message(&mut alloc, b"world!").unwrap();
+//! assert_eq!(queue.len(), 3);
+//!
+//! let mut out = [0u8; 8];
+//! assert_eq!(queue.peek_len(&alloc), Some(5));
+//! assert_eq!(queue.pop_message_into(&mut alloc, &mut out), Ok(5));
+//! assert_eq!(&out[..5], b"hello");
+//! assert_eq!(queue.pop_message_into(&mut alloc, &mut out), Ok(0));
+//!
+//! let msg = queue.pop_message(&mut alloc).unwrap();
+//! assert_eq!(msg.len(), 6);
+//! assert!(queue.is_empty());
+//! ```
+//!
+//! [`GlobalDeque`]: crate::utils::bytebuffer::GlobalDeque
+
+use crate::Allocator;
+use crate::utils::bytebuffer::{BufLen, ByteBuffer, ByteBufferError};
+use crate::utils::varint::encoded_len;
+
+/// FIFO of variable-length messages
+///
+/// Messages are never split or merged: each pop returns exactly one pushed
+/// message. All methods take the allocator that backs the queue; the
+/// `_global` variants use the global allocator.
+pub struct MessageQueue {
+    /// Length headers and payloads, oldest message first
+    buf: ByteBuffer,
+    /// Number of messages in `buf`
+    count: usize,
+}
+
+impl Default for MessageQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageQueue {
+    /// Creates an empty queue
+    pub const fn new() -> Self {
+        Self::from_buffer(ByteBuffer::new())
+    }
+
+    /// Creates an empty queue holding at most `max` bytes
+    ///
+    /// The limit covers length headers as well as payloads.
+    pub const fn with_max_len(max: BufLen) -> Self {
+        Self::from_buffer(ByteBuffer::with_max_len(max))
+    }
+
+    const fn from_buffer(buf: ByteBuffer) -> Self {
+        Self { buf, count: 0 }
+    }
+
+    /// Number of queued messages
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns true if no message is queued
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Bytes used by the queue, headers included
+    pub fn byte_len(&self) -> BufLen {
+        self.buf.len()
+    }
+
+    /// Appends a message
+    ///
+    /// # Errors
+    ///
+    /// Returns `ByteBufferError::Full` or `ByteBufferError::AllocationFailed`
+    /// if the message does not fit; the queue is unchanged.
+    pub fn push_message<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        data: &[u8],
+    ) -> Result<(), ByteBufferError> {
+        let start = self.buf.len() as usize;
+        let mut writer = self.buf.write(arena);
+        let result = writer
+            .put_varint(data.len() as u64)
+            .and_then(|_| writer.put_slice(data));
+        if result.is_err() {
+            self.buf.truncate(arena, start);
+            return result;
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Returns the length of the oldest message, or `None` if the queue is empty
+    pub fn peek_len<A: Allocator>(&self, arena: &A) -> Option<usize> {
+        self.header(arena).map(|(_, len)| len)
+    }
+
+    /// Removes the oldest message, copying it into `dest`
+    ///
+    /// Returns the message length.
+    ///
+    /// # Errors
+    ///
+    /// - `ByteBufferError::Incomplete` - the queue is empty
+    /// - `ByteBufferError::Overflow` - the message is longer than `dest`; it
+    ///   stays queued, use [`peek_len`](Self::peek_len) to size the buffer
+    pub fn pop_message_into<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        dest: &mut [u8],
+    ) -> Result<usize, ByteBufferError> {
+        let (header, len) = self.header(arena).ok_or(ByteBufferError::Incomplete)?;
+        if len > dest.len() {
+            return Err(ByteBufferError::Overflow);
+        }
+        self.buf.copy_to_slice(arena, header, &mut dest[..len]);
+        self.buf.remove_prefix(arena, header + len);
+        self.count -= 1;
+        Ok(len)
+    }
+
+    /// Removes the oldest message and returns it as a buffer
+    ///
+    /// A message shorter than a chunk is copied into a fresh chunk. Longer
+    /// payloads have their chunks relinked into the returned buffer; only a
+    /// chunk shared with the next message is split. Either way may allocate.
+    ///
+    /// # Errors
+    ///
+    /// - `ByteBufferError::Incomplete` - the queue is empty
+    /// - `ByteBufferError::AllocationFailed` - no chunk to split the message
+    ///   off; it stays queued
+    pub fn pop_message<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+    ) -> Result<ByteBuffer, ByteBufferError> {
+        let (header, len) = self.header(arena).ok_or(ByteBufferError::Incomplete)?;
+        let mut msg = self.buf.split_off_front(arena, header + len)?;
+        msg.remove_prefix(arena, header);
+        self.count -= 1;
+        Ok(msg)
+    }
+
+    /// Drops the oldest message without reading it
+    ///
+    /// Returns false if the queue is empty.
+    pub fn discard<A: Allocator>(&mut self, arena: &mut A) -> bool {
+        let Some((header, len)) = self.header(arena) else {
+            return false;
+        };
+        self.buf.remove_prefix(arena, header + len);
+        self.count -= 1;
+        true
+    }
+
+    /// Drops every message and frees the chunks
+    pub fn clear<A: Allocator>(&mut self, arena: &mut A) {
+        self.buf.write(arena).clear();
+        self.count = 0;
+    }
+
+    /// Header length and payload length of the oldest message
+    fn header<A: Allocator>(&self, arena: &A) -> Option<(usize, usize)> {
+        if self.count == 0 {
+            return None;
+        }
+        // Headers are only ever written by `push_message`
+        let len = self.buf.peek_varint(arena).ok()?;
+        Some((encoded_len(len), len as usize))
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl MessageQueue {
+    /// Append a message using the global allocator
+    pub fn push_message_global(&mut self, data: &[u8]) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.push_message(alloc, data))
+    }
+
+    /// Length of the oldest message (global allocator version)
+    pub fn peek_len_global(&self) -> Option<usize> {
+        crate::global::with_global_allocator(|alloc| self.peek_len(alloc))
+    }
+
+    /// Remove the oldest message into `dest` (global allocator version)
+    pub fn pop_message_into_global(&mut self, dest: &mut [u8]) -> Result<usize, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.pop_message_into(alloc, dest))
+    }
+
+    /// Remove the oldest message as a buffer (global allocator version)
+    pub fn pop_message_global(&mut self) -> Result<ByteBuffer, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.pop_message(alloc))
+    }
+
+    /// Drop the oldest message (global allocator version)
+    pub fn discard_global(&mut self) -> bool {
+        crate::global::with_global_allocator(|alloc| self.discard(alloc))
+    }
+
+    /// Drop every message (global allocator version)
+    pub fn clear_global(&mut self) {
+        crate::global::with_global_allocator(|alloc| self.clear(alloc))
+    }
+}
+
+// ============================================================================
+// Global Message Queue Wrapper (enabled with features = "bytebuffer-stream" + "global-alloc")
+// ============================================================================
+
+/// Global wrapper for MessageQueue (single-threaded, no mutex needed)
+///
+/// The message counterpart of [`GlobalDeque`]: lets an interrupt handler
+/// queue complete packets for the main loop.
+///
+/// # Safety
+/// This is only safe in single-threaded contexts. Do not use with multiple
+/// threads or concurrent access.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tinyalloc::prelude::*;
+///
+/// static PACKETS: GlobalMessageQueue = GlobalMessageQueue::new();
+///
+/// fn packet_received(packet: &[u8]) {
+///     PACKETS.push_message(packet).ok();
+/// }
+///
+/// fn main() {
+///     GlobalAllocatorConfig::Slab1K32.init();
+///
+///     let mut packet = [0u8; 64];
+///     loop {
+///         while let Ok(len) = PACKETS.pop_message_into(&mut packet) {
+///             handle(&packet[..len]);
+///         }
+///     }
+/// }
+/// # fn handle(_: &[u8]) {}
+/// ```
+///
+/// [`GlobalDeque`]: crate::utils::bytebuffer::GlobalDeque
+#[cfg(feature = "global-alloc")]
+pub struct GlobalMessageQueue {
+    inner: core::cell::UnsafeCell<MessageQueue>,
+}
+
+#[cfg(feature = "global-alloc")]
+unsafe impl Sync for GlobalMessageQueue {}
+
+#[cfg(feature = "global-alloc")]
+impl GlobalMessageQueue {
+    /// Create a new global message queue
+    pub const fn new() -> Self {
+        Self::from_queue(MessageQueue::new())
+    }
+
+    /// Create a new global message queue holding at most `max` bytes
+    pub const fn with_max_len(max: BufLen) -> Self {
+        Self::from_queue(MessageQueue::with_max_len(max))
+    }
+
+    const fn from_queue(queue: MessageQueue) -> Self {
+        Self {
+            inner: core::cell::UnsafeCell::new(queue),
+        }
+    }
+
+    /// Execute a closure with mutable access to the queue
+    ///
+    /// # Safety
+    /// Safe in single-threaded context. Caller must ensure no concurrent access.
+    #[inline]
+    pub fn with_mut<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut MessageQueue) -> R,
+    {
+        unsafe { f(&mut *self.inner.get()) }
+    }
+
+    /// Append a message using the global allocator
+    #[inline]
+    pub fn push_message(&self, data: &[u8]) -> Result<(), ByteBufferError> {
+        self.with_mut(|queue| queue.push_message_global(data))
+    }
+
+    /// Get the length of the oldest message
+    #[inline]
+    pub fn peek_len(&self) -> Option<usize> {
+        self.with_mut(|queue| queue.peek_len_global())
+    }
+
+    /// Remove the oldest message, copying it into `dest`
+    ///
+    /// See [`MessageQueue::pop_message_into`].
+    #[inline]
+    pub fn pop_message_into(&self, dest: &mut [u8]) -> Result<usize, ByteBufferError> {
+        self.with_mut(|queue| queue.pop_message_into_global(dest))
+    }
+
+    /// Remove the oldest message as a buffer backed by the global allocator
+    #[inline]
+    pub fn pop_message(&self) -> Result<ByteBuffer, ByteBufferError> {
+        self.with_mut(|queue| queue.pop_message_global())
+    }
+
+    /// Drop the oldest message without reading it
+    #[inline]
+    pub fn discard(&self) -> bool {
+        self.with_mut(|queue| queue.discard_global())
+    }
+
+    /// Get the number of queued messages
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.with_mut(|queue| queue.len())
+    }
+
+    /// Check if the queue is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.with_mut(|queue| queue.is_empty())
+    }
+
+    /// Drop every message using the global allocator
+    #[inline]
+    pub fn clear(&self) {
+        self.with_mut(|queue| queue.clear_global())
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl Default for GlobalMessageQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "tinyslab")]
+mod tests {
+    use super::*;
+    use crate::backend::tinyslab::TinySlabAllocator;
+
+    #[test]
+    fn test_messages_across_chunks() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut queue = MessageQueue::new();
+        let long: [u8; 200] = core::array::from_fn(|i| i as u8);
+
+        queue.push_message(&mut arena, b"a").unwrap();
+        queue.push_message(&mut arena, &long).unwrap();
+        queue.push_message(&mut arena, b"tail").unwrap();
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.byte_len(), 2 + 202 + 5);
+
+        let mut out = [0u8; 200];
+        assert_eq!(queue.pop_message_into(&mut arena, &mut out), Ok(1));
+        assert_eq!(out[0], b'a');
+
+        // Too small: the message stays queued
+        assert_eq!(queue.peek_len(&arena), Some(200));
+        assert_eq!(
+            queue.pop_message_into(&mut arena, &mut out[..10]),
+            Err(ByteBufferError::Overflow)
+        );
+        let msg = queue.pop_message(&mut arena).unwrap();
+        assert_eq!(msg.len(), 200);
+        assert_eq!(msg.copy_to_slice(&arena, 0, &mut out), 200);
+        assert_eq!(out, long);
+
+        assert_eq!(queue.pop_message_into(&mut arena, &mut out), Ok(4));
+        assert_eq!(&out[..4], b"tail");
+        assert_eq!(queue.peek_len(&arena), None);
+        assert_eq!(
+            queue.pop_message(&mut arena).err(),
+            Some(ByteBufferError::Incomplete)
+        );
+
+        let mut msg = msg;
+        msg.write(&mut arena).clear();
+        assert_eq!(arena.len(), 0);
+    }
+
+    #[test]
+    fn test_push_message_is_atomic() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut queue = MessageQueue::with_max_len(20);
+
+        queue.push_message(&mut arena, b"0123456789").unwrap();
+        assert_eq!(
+            queue.push_message(&mut arena, b"0123456789"),
+            Err(ByteBufferError::Full)
+        );
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.byte_len(), 11);
+
+        assert!(queue.discard(&mut arena));
+        assert!(!queue.discard(&mut arena));
+        queue.push_message(&mut arena, b"0123456789").unwrap();
+        queue.clear(&mut arena);
+        assert!(queue.is_empty());
+        assert_eq!(arena.len(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "global-alloc")]
+    fn test_global_message_queue() {
+        use crate::global::GlobalAllocatorConfig;
+
+        static PACKETS: GlobalMessageQueue = GlobalMessageQueue::new();
+        GlobalAllocatorConfig::Slab1K32.init();
+
+        PACKETS.push_message(b"ping").unwrap();
+        PACKETS.push_message(b"pong!").unwrap();
+        assert_eq!(PACKETS.len(), 2);
+        assert_eq!(PACKETS.peek_len(), Some(4));
+
+        let mut out = [0u8; 8];
+        assert_eq!(PACKETS.pop_message_into(&mut out), Ok(4));
+        assert_eq!(&out[..4], b"ping");
+        let mut msg = PACKETS.pop_message().unwrap();
+        assert_eq!(msg.len(), 5);
+        msg.clear();
+        assert!(PACKETS.is_empty());
+    }
+}
@@ -5,7 +5,11 @@
 //!
 //! # Feature Flag
 //!
-//! This module is only available when the `nominput` feature is enabled.
+//! This module is only available when the `nominput` feature is enabled. The
+//! feature pulls in `global-alloc` and `bytebuffer-deque`: the impl reads and
+//! skips bytes through the global allocator, so it only supports buffers that
+//! live there. Buffers in a local arena should use `ByteBuffer::view_from` or
+//! `ByteBuffer::slice` instead.
 //!
 //! # Zero-Copy Design  
 //!
@@ -17,6 +21,7 @@
 //!
 //! - Cannot provide contiguous `&[u8]` slices without allocation
 //! - `take_split()` creates logical views, not actual slices
+//! - Buffers must live in the global allocator (see Feature Flag)
 //! - Iterator-based parsing recommended for best results
 //! - Some nom combinators requiring `AsBytes` won't work
 //!
@@ -29,18 +34,16 @@
 use crate::utils::bytebuffer::ByteBuffer;
 
 // Helper struct to wrap the iterator with the correct lifetime
-#[cfg(feature = "global-alloc")]
 pub struct ByteBufferIter {
     buf: ByteBuffer,
     index: usize,
 }
 
-#[cfg(feature = "global-alloc")]
 impl Iterator for ByteBufferIter {
     type Item = u8;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index >= self.buf.len as usize {
+        if self.index >= self.buf.len() as usize {
             return None;
         }
 
@@ -58,15 +61,8 @@ impl Iterator for ByteBufferIter {
 impl nom::Input for ByteBuffer {
     type Item = u8;
 
-    #[cfg(feature = "global-alloc")]
     type Iter = ByteBufferIter;
-    #[cfg(not(feature = "global-alloc"))]
-    type Iter = core::iter::Empty<u8>;
-
-    #[cfg(feature = "global-alloc")]
     type IterIndices = core::iter::Enumerate<ByteBufferIter>;
-    #[cfg(not(feature = "global-alloc"))]
-    type IterIndices = core::iter::Empty<(usize, u8)>;
 
     fn input_len(&self) -> usize {
         ByteBuffer::input_len(self)
@@ -88,45 +84,22 @@ impl nom::Input for ByteBuffer {
     where
         P: Fn(Self::Item) -> bool,
     {
-        #[cfg(feature = "global-alloc")]
-        {
-            ByteBuffer::position_global(self, predicate)
-        }
-        #[cfg(not(feature = "global-alloc"))]
-        {
-            // Cannot implement without allocator reference
-            let _ = predicate;
-            None
-        }
+        ByteBuffer::position_global(self, predicate)
     }
 
     fn iter_elements(&self) -> Self::Iter {
-        #[cfg(feature = "global-alloc")]
-        {
-            ByteBufferIter {
-                buf: *self,
-                index: 0,
-            }
-        }
-        #[cfg(not(feature = "global-alloc"))]
-        {
-            core::iter::empty()
+        ByteBufferIter {
+            buf: *self,
+            index: 0,
         }
     }
 
     fn iter_indices(&self) -> Self::IterIndices {
-        #[cfg(feature = "global-alloc")]
-        {
-            ByteBufferIter {
-                buf: *self,
-                index: 0,
-            }
-            .enumerate()
-        }
-        #[cfg(not(feature = "global-alloc"))]
-        {
-            core::iter::empty()
+        ByteBufferIter {
+            buf: *self,
+            index: 0,
         }
+        .enumerate()
     }
 
     fn slice_index(&self, count: usize) -> Result<usize, nom::Needed> {
@@ -148,12 +121,9 @@ mod tests {
         assert_eq!(buf.input_len(), 0);
 
         let buf = ByteBuffer {
-            head: None,
-            tail: None,
             len: 42,
             max_len: None,
-            #[cfg(feature = "bytebuffer-deque")]
-            read_offset: 0,
+            ..ByteBuffer::new()
         };
         assert_eq!(buf.input_len(), 42);
     }
@@ -161,12 +131,9 @@ mod tests {
     #[test]
     fn test_take() {
         let buf = ByteBuffer {
-            head: None,
-            tail: None,
             len: 100,
             max_len: None,
-            #[cfg(feature = "bytebuffer-deque")]
-            read_offset: 0,
+            ..ByteBuffer::new()
         };
 
         let taken = buf.take(50);
@@ -181,52 +148,73 @@ mod tests {
 
     #[test]
     fn test_take_from() {
-        let buf = ByteBuffer {
-            head: None,
-            tail: None,
-            len: 100,
-            max_len: None,
-            #[cfg(feature = "bytebuffer-deque")]
-            read_offset: 0,
-        };
+        use crate::global::GlobalAllocatorConfig;
+
+        GlobalAllocatorConfig::Slab1K32.init();
+
+        let mut buf = ByteBuffer::new();
+        buf.extend(b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJ")
+            .unwrap();
 
         // take_from with index >= len returns empty
-        let remaining = buf.take_from(100);
-        assert_eq!(remaining.len(), 0);
+        assert_eq!(buf.take_from(46).len(), 0);
+        assert_eq!(buf.take_from(200).len(), 0);
 
-        let remaining = buf.take_from(200);
-        assert_eq!(remaining.len(), 0);
+        // Skipped bytes are gone, even past a chunk boundary
+        let remaining = buf.take_from(35);
+        assert_eq!(remaining.len(), 11);
+        assert!(remaining.iter_elements().eq(b"zABCDEFGHIJ".iter().copied()));
+        assert_eq!(buf.take_from(3).iter_elements().next(), Some(b'3'));
 
-        // take_from with index < len returns copy (limitation)
-        let remaining = buf.take_from(50);
-        assert_eq!(remaining.len(), 100); // Known limitation
+        // Cleanup
+        buf.clear();
     }
 
     #[test]
     fn test_take_split() {
         let buf = ByteBuffer {
-            head: None,
-            tail: None,
             len: 100,
             max_len: None,
-            #[cfg(feature = "bytebuffer-deque")]
-            read_offset: 0,
+            ..ByteBuffer::new()
         };
 
         let (_remaining, taken) = buf.take_split(30);
         assert_eq!(taken.len(), 30);
-        // Note: remaining.len() is 100 due to take_from limitation
+    }
+
+    #[test]
+    fn test_take_split_with_data() {
+        use crate::global::GlobalAllocatorConfig;
+
+        GlobalAllocatorConfig::Slab1K32.init();
+
+        // Long enough to span several chunks
+        let mut buf = ByteBuffer::new();
+        buf.extend(b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJ")
+            .unwrap();
+
+        let (rest, head) = buf.take_split(35);
+        assert!(
+            head.iter_elements()
+                .eq(b"0123456789abcdefghijklmnopqrstuvwxy".iter().copied())
+        );
+        assert!(rest.iter_elements().eq(b"zABCDEFGHIJ".iter().copied()));
+
+        // Splitting a split keeps addressing the right bytes
+        let (tail, mid) = rest.take_split(4);
+        assert!(mid.iter_elements().eq(b"zABC".iter().copied()));
+        assert_eq!(nom::Input::position(&tail, |b| b == b'J'), Some(6));
+
+        // Cleanup
+        buf.clear();
     }
 
     #[test]
     fn test_slice_index() {
         let buf = ByteBuffer {
-            head: None,
-            tail: None,
             len: 50,
             max_len: None,
-            #[cfg(feature = "bytebuffer-deque")]
-            read_offset: 0,
+            ..ByteBuffer::new()
         };
 
         // Valid index
@@ -244,16 +232,12 @@ mod tests {
     #[test]
     fn test_position_returns_none() {
         let buf = ByteBuffer::new();
-        // Call the trait method explicitly (no allocator needed)
+        // Call the trait method explicitly (empty buffer)
         let result = nom::Input::position(&buf, |_| true);
-        #[cfg(not(feature = "global-alloc"))]
         assert_eq!(result, None);
-        #[cfg(feature = "global-alloc")]
-        assert_eq!(result, None); // Empty buffer
     }
 
     #[test]
-    #[cfg(feature = "global-alloc")]
     fn test_position_with_global_alloc() {
         use crate::global::GlobalAllocatorConfig;
 
@@ -286,7 +270,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "global-alloc")]
     fn test_iter_elements_with_data() {
         use crate::global::GlobalAllocatorConfig;
 
@@ -314,7 +297,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "global-alloc")]
     fn test_iter_indices_with_data() {
         use crate::global::GlobalAllocatorConfig;
 
@@ -335,14 +317,12 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_clone_copy() {
         let buf1 = ByteBuffer {
-            head: None,
-            tail: None,
             len: 42,
             max_len: Some(100),
-            #[cfg(feature = "bytebuffer-deque")]
-            read_offset: 0,
+            ..ByteBuffer::new()
         };
 
         let buf2 = buf1; // Copy
@@ -0,0 +1,211 @@
+//! Multi-byte pattern search on ByteBuffer
+//!
+//! `position` and `consume_until` match one byte at a time. The methods here
+//! look for whole byte sequences such as `\r\n`, `+IPD,` or `0x7E 0x7E`,
+//! including matches that straddle chunk boundaries.
+//!
+//! Matching walks the chunk list in place with a [`ChunkCursor`]; nothing is
+//! copied out of the buffer. Each candidate position is compared directly
+//! against the needle, which suits the short markers used in framing.
+//!
+//! # Examples
+//!
+//! ```
+//! use tinyalloc::prelude::*;
+//!
+//! let mut alloc = TinySlabAllocator::<1024, 32>::new();
+//! let mut buf = ByteBuffer::new();
+//! buf.write(&mut alloc).extend(b"AT+CIPSEND\r\nOK\r\n").unwrap();
+//!
+//! assert!(buf.starts_with(&alloc, b"AT+"));
+//! assert!(buf.ends_with(&alloc, b"OK\r\n"));
+//! assert_eq!(buf.find(&alloc, b"\r\n"), Some(10));
+//! assert_eq!(buf.rfind(&alloc, b"\r\n"), Some(14));
+//! ```
+
+use crate::Allocator;
+use crate::utils::bytebuffer::{ByteBuffer, ChunkCursor};
+
+/// Returns true if the bytes at `cursor` start with `needle`
+fn matches_at<A: Allocator>(arena: &A, mut cursor: ChunkCursor, needle: &[u8]) -> bool {
+    needle.iter().all(|&b| cursor.next_byte(arena) == Some(b))
+}
+
+impl ByteBuffer {
+    /// Returns the position of the first occurrence of `needle`
+    ///
+    /// An empty needle matches at position 0.
+    pub fn find<A: Allocator>(&self, arena: &A, needle: &[u8]) -> Option<usize> {
+        self.find_from(arena, 0, needle)
+    }
+
+    /// Returns the position of the first occurrence of `needle` at or after `from`
+    pub fn find_from<A: Allocator>(&self, arena: &A, from: usize, needle: &[u8]) -> Option<usize> {
+        let last = (self.len() as usize).checked_sub(needle.len())?;
+        let mut cursor = ChunkCursor::seek(arena, self, from);
+        for pos in from..=last {
+            if matches_at(arena, cursor, needle) {
+                return Some(pos);
+            }
+            cursor.next_byte(arena);
+        }
+        None
+    }
+
+    /// Returns the position of the last occurrence of `needle`
+    ///
+    /// Chunks are only linked forwards, so this scans the whole buffer.
+    pub fn rfind<A: Allocator>(&self, arena: &A, needle: &[u8]) -> Option<usize> {
+        let last = (self.len() as usize).checked_sub(needle.len())?;
+        let mut cursor = ChunkCursor::seek(arena, self, 0);
+        let mut found = None;
+        for pos in 0..=last {
+            if matches_at(arena, cursor, needle) {
+                found = Some(pos);
+            }
+            cursor.next_byte(arena);
+        }
+        found
+    }
+
+    /// Returns true if `needle` occurs anywhere in the buffer
+    pub fn contains<A: Allocator>(&self, arena: &A, needle: &[u8]) -> bool {
+        self.find(arena, needle).is_some()
+    }
+
+    /// Returns true if the unread bytes begin with `prefix`
+    pub fn starts_with<A: Allocator>(&self, arena: &A, prefix: &[u8]) -> bool {
+        prefix.len() <= self.len() as usize
+            && matches_at(arena, ChunkCursor::seek(arena, self, 0), prefix)
+    }
+
+    /// Returns true if the buffer ends with `suffix`
+    pub fn ends_with<A: Allocator>(&self, arena: &A, suffix: &[u8]) -> bool {
+        let Some(start) = (self.len() as usize).checked_sub(suffix.len()) else {
+            return false;
+        };
+        matches_at(arena, ChunkCursor::seek(arena, self, start), suffix)
+    }
+
+    /// Remove bytes from front until (and including) the sequence `needle`
+    ///
+    /// Returns the number of bytes consumed if `needle` was found, or `None`
+    /// (consuming nothing) if it is not in the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "bytebuffer-stream")]
+    /// # {
+    /// use tinyalloc::prelude::*;
+    ///
+    /// let mut alloc = TinySlabAllocator::<512, 16>::new();
+    /// let mut buf = ByteBuffer::new();
+    ///
+    /// buf.write(&mut alloc).extend(b"noise+IPD,5:hello").unwrap();
+    /// assert_eq!(buf.consume_until_seq(&mut alloc, b"+IPD,"), Some(10));
+    /// assert_eq!(buf.peek_front(&alloc), Some(b'5'));
+    /// # }
+    /// ```
+    #[cfg(feature = "bytebuffer-stream")]
+    pub fn consume_until_seq<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        needle: &[u8],
+    ) -> Option<usize> {
+        let pos = self.find(arena, needle)?;
+        Some(self.remove_prefix(arena, pos + needle.len()))
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl ByteBuffer {
+    /// Finds the first occurrence of `needle` using global allocator
+    pub fn find_global(&self, needle: &[u8]) -> Option<usize> {
+        crate::global::with_global_allocator(|alloc| self.find(alloc, needle))
+    }
+
+    /// Finds the last occurrence of `needle` using global allocator
+    pub fn rfind_global(&self, needle: &[u8]) -> Option<usize> {
+        crate::global::with_global_allocator(|alloc| self.rfind(alloc, needle))
+    }
+
+    /// Checks whether `needle` occurs in the buffer using global allocator
+    pub fn contains_global(&self, needle: &[u8]) -> bool {
+        crate::global::with_global_allocator(|alloc| self.contains(alloc, needle))
+    }
+
+    /// Checks whether the buffer starts with `prefix` using global allocator
+    pub fn starts_with_global(&self, prefix: &[u8]) -> bool {
+        crate::global::with_global_allocator(|alloc| self.starts_with(alloc, prefix))
+    }
+
+    /// Checks whether the buffer ends with `suffix` using global allocator
+    pub fn ends_with_global(&self, suffix: &[u8]) -> bool {
+        crate::global::with_global_allocator(|alloc| self.ends_with(alloc, suffix))
+    }
+
+    /// Remove bytes until (and including) `needle` (global allocator version)
+    #[cfg(feature = "bytebuffer-stream")]
+    pub fn consume_until_seq_global(&mut self, needle: &[u8]) -> Option<usize> {
+        crate::global::with_global_allocator(|alloc| self.consume_until_seq(alloc, needle))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "tinyslab")]
+mod tests {
+    use super::*;
+    use crate::backend::tinyslab::TinySlabAllocator;
+
+    #[test]
+    fn test_find_across_chunks() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        // 30-byte chunks: the markers straddle the first and second boundary
+        buf.write(&mut arena).extend(&[b'.'; 28]).unwrap();
+        buf.write(&mut arena).extend(b"\r\n~~").unwrap();
+        buf.write(&mut arena).extend(&[b'.'; 24]).unwrap();
+        buf.write(&mut arena).extend(b"~~\r\n").unwrap();
+
+        assert_eq!(buf.find(&arena, b"\r\n"), Some(28));
+        assert_eq!(buf.rfind(&arena, b"\r\n"), Some(58));
+        assert_eq!(buf.find(&arena, b"~~"), Some(30));
+        assert_eq!(buf.rfind(&arena, b"~~"), Some(56));
+        assert_eq!(buf.find_from(&arena, 31, b"~~"), Some(56));
+        assert_eq!(buf.find(&arena, b"\n~~~"), None);
+        assert_eq!(buf.find(&arena, b""), Some(0));
+        assert!(buf.contains(&arena, b".~~\r"));
+        assert!(!buf.contains(&arena, &[b'.'; 29]));
+
+        assert!(buf.starts_with(&arena, &[b'.'; 28]));
+        assert!(!buf.starts_with(&arena, b".\r"));
+        assert!(buf.ends_with(&arena, b".~~\r\n"));
+        assert!(!buf.ends_with(&arena, &[b'.'; 61]));
+
+        // Needle longer than the buffer
+        let mut short = ByteBuffer::new();
+        short.write(&mut arena).extend(b"ab").unwrap();
+        assert_eq!(short.find(&arena, b"abc"), None);
+        assert_eq!(short.rfind(&arena, b"abc"), None);
+        assert!(!short.starts_with(&arena, b"abc"));
+    }
+
+    #[test]
+    #[cfg(feature = "bytebuffer-stream")]
+    fn test_consume_until_seq() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+        buf.write(&mut arena)
+            .extend(b"garbage\x7E\x7Eframe one\x7E\x7Eframe two")
+            .unwrap();
+
+        assert_eq!(buf.consume_until_seq(&mut arena, b"\x7E\x7E"), Some(9));
+        assert!(buf.starts_with(&arena, b"frame one"));
+        assert_eq!(buf.find(&arena, b"\x7E\x7E"), Some(9));
+        assert_eq!(buf.consume_until_seq(&mut arena, b"\x7E\x7E"), Some(11));
+        assert_eq!(buf.consume_until_seq(&mut arena, b"\x7E\x7E"), None);
+        assert_eq!(buf.len(), 9);
+    }
+}
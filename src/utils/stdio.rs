@@ -0,0 +1,220 @@
+//! `std::io` trait implementations
+//!
+//! Lets host tools and unit tests run the same parsing code as the firmware,
+//! feeding tinyalloc buffers from files, sockets or `io::Cursor`:
+//!
+//! - [`ByteBufferIo`] (from [`ByteBuffer::io`]) implements `Read`, `BufRead`
+//!   and `Write` for any allocator
+//! - `&GlobalDeque` implements `Read` and `Write` with the global allocator
+//! - [`GlobalDequeReader`] (from [`GlobalDeque::reader`]) implements `Read`
+//!   and `BufRead`; see [`ioadapter`](crate::utils::ioadapter) for why the
+//!   deque itself has no `BufRead`
+//!
+//! The stream behaves as with the `embedded-io` feature: `fill_buf` returns
+//! the unread part of the head chunk, `read` returns `Ok(0)` once the buffer
+//! is empty, and `write` stores as much as fits. A write that stores nothing
+//! fails with an `io::Error` wrapping the [`ByteBufferError`], so `io::copy`
+//! stops with `WriteZero` when the buffer is full.
+//!
+//! # Feature Flag
+//!
+//! This module is available when the `std` feature is enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "std")]
+//! # {
+//! use std::io::{self, BufRead, Cursor};
+//! use tinyalloc::prelude::*;
+//!
+//! let mut alloc = TinySlabAllocator::<1024, 32>::new();
+//! let mut buf = ByteBuffer::new();
+//!
+//! let mut io = buf.io(&mut alloc);
+//! let copied = io::copy(&mut Cursor::new(b"first\nsecond\n"), &mut io).unwrap();
+//! assert_eq!(copied, 13);
+//!
+//! let mut line = String::new();
+//! io.read_line(&mut line).unwrap();
+//! assert_eq!(line, "first\n");
+//! # }
+//! ```
+
+use std::io::{self, BufRead, ErrorKind, Read, Write};
+
+use crate::Allocator;
+#[cfg(doc)]
+use crate::utils::bytebuffer::ByteBuffer;
+use crate::utils::bytebuffer::ByteBufferError;
+#[cfg(feature = "global-alloc")]
+use crate::utils::bytebuffer::GlobalDeque;
+use crate::utils::ioadapter::ByteBufferIo;
+#[cfg(feature = "global-alloc")]
+use crate::utils::ioadapter::GlobalDequeReader;
+
+impl std::error::Error for ByteBufferError {}
+
+impl From<ByteBufferError> for io::Error {
+    fn from(err: ByteBufferError) -> Self {
+        let kind = match err {
+            ByteBufferError::AllocationFailed => ErrorKind::OutOfMemory,
+            ByteBufferError::Full => ErrorKind::WriteZero,
+            ByteBufferError::Incomplete => ErrorKind::UnexpectedEof,
+            ByteBufferError::Malformed | ByteBufferError::InvalidByte { .. } => {
+                ErrorKind::InvalidData
+            }
+            ByteBufferError::Overflow | ByteBufferError::OutOfRange => ErrorKind::InvalidInput,
+            ByteBufferError::Uninitialized => ErrorKind::Other,
+        };
+        io::Error::new(kind, err)
+    }
+}
+
+impl<A: Allocator> Read for ByteBufferIo<'_, A> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(self.read_front(buf))
+    }
+}
+
+impl<A: Allocator> BufRead for ByteBufferIo<'_, A> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.head_chunk())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.consume_front(amt);
+    }
+}
+
+impl<A: Allocator> Write for ByteBufferIo<'_, A> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(self.write_back(buf)?)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl Read for &GlobalDeque {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(self.read_front(buf))
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl Write for &GlobalDeque {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(self.write_back(buf)?)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl<const N: usize> Read for GlobalDequeReader<'_, N> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(self.read_buffered(buf))
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl<const N: usize> BufRead for GlobalDequeReader<'_, N> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.fill())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.consume_buffered(amt);
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "tinyslab")]
+mod tests {
+    use super::*;
+    use crate::backend::tinyslab::TinySlabAllocator;
+    use crate::utils::bytebuffer::ByteBuffer;
+    use std::io::Cursor;
+    use std::string::String;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_copy_in_and_read_lines() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+        let mut input = [b'x'; 100];
+        input[40] = b'\n';
+        input[99] = b'\n';
+
+        let mut io = buf.io(&mut arena);
+        assert_eq!(
+            io::copy(&mut Cursor::new(&input[..]), &mut io).unwrap(),
+            100
+        );
+
+        // Lines span chunk boundaries
+        let mut line = String::new();
+        assert_eq!(io.read_line(&mut line).unwrap(), 41);
+        line.clear();
+        assert_eq!(io.read_line(&mut line).unwrap(), 59);
+        assert_eq!(io.read_line(&mut line).unwrap(), 0);
+        assert!(io.buffer().is_empty());
+    }
+
+    #[test]
+    fn test_copy_stops_when_full() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::with_max_len(16);
+
+        let err = io::copy(&mut Cursor::new([7u8; 40]), &mut buf.io(&mut arena)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::WriteZero);
+        assert_eq!(buf.len(), 16);
+
+        let mut out = [0u8; 20];
+        assert_eq!(buf.io(&mut arena).read(&mut out).unwrap(), 16);
+        assert_eq!(out[..16], [7u8; 16]);
+    }
+
+    #[test]
+    #[cfg(feature = "global-alloc")]
+    fn test_global_deque_reader_lines() {
+        use crate::global::GlobalAllocatorConfig;
+
+        static QUEUE: GlobalDeque = GlobalDeque::new();
+        GlobalAllocatorConfig::Slab1K32.init();
+
+        // Lines longer than the reader's buffer still come out whole
+        let mut q = &QUEUE;
+        q.write_all(b"$GPGGA,123519,4807.038,N\n$GPRMC\n").unwrap();
+        let mut reader = GlobalDequeReader::<8>::new(&QUEUE);
+        let lines: Vec<String> = (&mut reader).lines().map(Result::unwrap).collect();
+        assert_eq!(lines, ["$GPGGA,123519,4807.038,N", "$GPRMC"]);
+        assert!(reader.deque().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "global-alloc")]
+    fn test_global_deque_copy() {
+        use crate::global::GlobalAllocatorConfig;
+
+        static QUEUE: GlobalDeque = GlobalDeque::new();
+        GlobalAllocatorConfig::Slab1K32.init();
+
+        let mut q = &QUEUE;
+        io::copy(&mut Cursor::new(b"ping\npong\n"), &mut q).unwrap();
+        let mut out = [0u8; 5];
+        q.read_exact(&mut out).unwrap();
+        assert_eq!(&out, b"ping\n");
+
+        // Line reading goes through the deque's own reader
+        let mut line = String::new();
+        QUEUE.reader().read_line(&mut line).unwrap();
+        assert_eq!(line, "pong\n");
+        assert!(QUEUE.is_empty());
+    }
+}
@@ -0,0 +1,471 @@
+//! UTF-8 text buffer built on ByteBuffer
+//!
+//! [`TextBuffer`] wraps a `ByteBuffer` that only ever holds valid UTF-8. Text
+//! can be pushed as `&str`/`char`, or as raw bytes straight off a serial link
+//! with [`push_bytes`](TextBuffer::push_bytes), which validates incrementally:
+//!
+//! - complete, valid characters are appended
+//! - a sequence cut off at the end of the input (up to 3 bytes) is kept aside
+//!   and completed by the next push
+//! - an invalid sequence rejects the whole push with
+//!   `ByteBufferError::InvalidByte`, whose offset is the first byte of the
+//!   input that cannot be part of valid UTF-8
+//!
+//! Characters may straddle chunk boundaries in the underlying buffer;
+//! [`chars`](TextBuffer::chars), [`pop_char`](TextBuffer::pop_char) and
+//! [`read_str`](TextBuffer::read_str) reassemble them.
+//!
+//! # Feature Flag
+//!
+//! This module is available when the `bytebuffer` feature is enabled;
+//! `pop_char` requires `bytebuffer-deque`.
+//!
+//! # Examples
+//!
+//! ```
+//! use tinyalloc::prelude::*;
+//!
+//! let mut alloc = TinySlabAllocator::<1024, 32>::new();
+//! let mut text = TextBuffer::new();
+//!
+//! // "héllo" arriving in two reads, split inside 'é' (0xC3 0xA9)
+//! text.push_bytes(&mut alloc, b"h\xC3").unwrap();
+//! assert_eq!(text.char_count(&alloc), 1);
+//! text.push_bytes(&mut alloc, b"\xA9llo").unwrap();
+//! assert_eq!(text.char_count(&alloc), 5);
+//! assert_eq!(text.len(), 6);
+//!
+//! let mut out = [0u8; 16];
+//! assert_eq!(text.read_str(&alloc, &mut out), "héllo");
+//!
+//! // Invalid input is rejected whole, with the offset of the bad byte
+//! assert_eq!(
+//!     text.push_bytes(&mut alloc, b"ok\xFF"),
+//!     Err(ByteBufferError::InvalidByte { offset: 2 })
+//! );
+//! assert_eq!(text.len(), 6);
+//! ```
+
+use crate::Allocator;
+use crate::utils::bytebuffer::{BufLen, ByteBuffer, ByteBufferError};
+
+/// Length of the UTF-8 sequence started by `lead`, 0 if it cannot start one
+fn sequence_len(lead: u8) -> usize {
+    match lead {
+        0x00..=0x7F => 1,
+        0xC2..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF4 => 4,
+        _ => 0,
+    }
+}
+
+/// Index of the first byte in `bytes` that cannot be part of valid UTF-8
+///
+/// `valid_up_to` and `error_len` come from the `Utf8Error`. A bad lead byte is
+/// itself the culprit; after a good lead byte it is the byte that breaks the
+/// sequence.
+fn invalid_byte(bytes: &[u8], valid_up_to: usize, error_len: usize) -> usize {
+    if sequence_len(bytes[valid_up_to]) > 1 {
+        valid_up_to + error_len
+    } else {
+        valid_up_to
+    }
+}
+
+/// Returns true if `b` is not a continuation byte, i.e. starts a character
+fn is_char_start(b: u8) -> bool {
+    b & 0xC0 != 0x80
+}
+
+/// Growable UTF-8 string stored in allocator chunks
+///
+/// Methods take the allocator backing the text; `_global` variants use the
+/// global allocator.
+pub struct TextBuffer {
+    /// Valid UTF-8 text
+    buf: ByteBuffer,
+    /// Start of a character whose remaining bytes have not arrived yet
+    pending: [u8; 3],
+    /// Number of bytes in `pending`
+    pending_len: u8,
+}
+
+impl Default for TextBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextBuffer {
+    /// Creates an empty text buffer
+    pub const fn new() -> Self {
+        Self::from_buffer(ByteBuffer::new())
+    }
+
+    /// Creates an empty text buffer holding at most `max` bytes of text
+    pub const fn with_max_len(max: BufLen) -> Self {
+        Self::from_buffer(ByteBuffer::with_max_len(max))
+    }
+
+    const fn from_buffer(buf: ByteBuffer) -> Self {
+        Self {
+            buf,
+            pending: [0; 3],
+            pending_len: 0,
+        }
+    }
+
+    /// Length of the text in bytes, excluding any incomplete sequence
+    pub fn len(&self) -> BufLen {
+        self.buf.len()
+    }
+
+    /// Returns true if the buffer holds no complete character
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Number of bytes held back waiting for the rest of a character
+    pub fn pending_len(&self) -> usize {
+        usize::from(self.pending_len)
+    }
+
+    /// The underlying buffer, for use with the `ByteBuffer` read APIs
+    pub fn as_buffer(&self) -> &ByteBuffer {
+        &self.buf
+    }
+
+    /// Number of characters in the text
+    ///
+    /// Counts character start bytes one chunk at a time, without decoding.
+    pub fn char_count<A: Allocator>(&self, arena: &A) -> usize {
+        self.buf
+            .read(arena)
+            .chunks()
+            .map(|chunk| chunk.iter().filter(|&&b| is_char_start(b)).count())
+            .sum()
+    }
+
+    /// Appends `s`
+    ///
+    /// # Errors
+    ///
+    /// - `ByteBufferError::InvalidByte { offset: 0 }` - an incomplete sequence
+    ///   from [`push_bytes`](Self::push_bytes) is pending, and `s` cannot
+    ///   complete it
+    /// - `ByteBufferError::Full` or `ByteBufferError::AllocationFailed`
+    ///
+    /// Nothing is appended on error.
+    pub fn push_str<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        s: &str,
+    ) -> Result<(), ByteBufferError> {
+        if self.pending_len > 0 {
+            return self.push_bytes(arena, s.as_bytes()).map(|_| ());
+        }
+        self.buf.write(arena).put_slice(s.as_bytes())
+    }
+
+    /// Appends one character
+    ///
+    /// Errors as [`push_str`](Self::push_str).
+    pub fn push_char<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        c: char,
+    ) -> Result<(), ByteBufferError> {
+        let mut utf8 = [0u8; 4];
+        self.push_str(arena, c.encode_utf8(&mut utf8))
+    }
+
+    /// Validates raw bytes and appends the complete characters
+    ///
+    /// A trailing incomplete sequence is held back until the next push.
+    /// Returns the number of bytes appended to the text, which may include
+    /// bytes held back by the previous push.
+    ///
+    /// # Errors
+    ///
+    /// - `ByteBufferError::InvalidByte` - `data` is not valid UTF-8; `offset`
+    ///   is the index in `data` of the first byte that cannot be accepted
+    /// - `ByteBufferError::Full` or `ByteBufferError::AllocationFailed`
+    ///
+    /// On error nothing is appended and any pending sequence is kept.
+    pub fn push_bytes<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        data: &[u8],
+    ) -> Result<usize, ByteBufferError> {
+        // Complete the pending character first
+        let pending_len = self.pending_len();
+        let mut head = [0u8; 4];
+        head[..pending_len].copy_from_slice(&self.pending[..pending_len]);
+        let mut head_len = pending_len;
+        let mut rest = data;
+        if pending_len > 0 {
+            let take = (sequence_len(head[0]) - pending_len).min(data.len());
+            head[pending_len..pending_len + take].copy_from_slice(&data[..take]);
+            head_len += take;
+            rest = &data[take..];
+            if let Err(e) = core::str::from_utf8(&head[..head_len]) {
+                let Some(bad) = e.error_len() else {
+                    // Still incomplete: all of `data` joins the pending bytes
+                    self.pending[..head_len].copy_from_slice(&head[..head_len]);
+                    self.pending_len = head_len as u8;
+                    return Ok(0);
+                };
+                let offset = invalid_byte(&head[..head_len], e.valid_up_to(), bad) - pending_len;
+                return Err(ByteBufferError::InvalidByte { offset });
+            }
+        }
+
+        let (valid, tail) = match core::str::from_utf8(rest) {
+            Ok(_) => (rest.len(), &rest[rest.len()..]),
+            Err(e) if e.error_len().is_none() => (e.valid_up_to(), &rest[e.valid_up_to()..]),
+            Err(e) => {
+                let bad = invalid_byte(rest, e.valid_up_to(), e.error_len().unwrap_or(1));
+                return Err(ByteBufferError::InvalidByte {
+                    offset: data.len() - rest.len() + bad,
+                });
+            }
+        };
+
+        let start = self.buf.len() as usize;
+        let mut w = self.buf.write(arena);
+        if let Err(e) = w
+            .put_slice(&head[..head_len])
+            .and_then(|_| w.put_slice(&rest[..valid]))
+        {
+            self.buf.truncate(arena, start);
+            return Err(e);
+        }
+        self.pending[..tail.len()].copy_from_slice(tail);
+        self.pending_len = tail.len() as u8;
+        Ok(head_len + valid)
+    }
+
+    /// Returns an iterator over the characters of the text
+    pub fn chars<'a, A: Allocator>(&'a self, arena: &'a A) -> impl Iterator<Item = char> + 'a {
+        let mut bytes = self
+            .buf
+            .read(arena)
+            .chunks()
+            .flat_map(|chunk| chunk.iter().copied());
+        core::iter::from_fn(move || {
+            let lead = bytes.next()?;
+            let len = sequence_len(lead);
+            let mut seq = [lead, 0, 0, 0];
+            for b in seq.iter_mut().take(len).skip(1) {
+                *b = bytes.next()?;
+            }
+            core::str::from_utf8(&seq[..len]).ok()?.chars().next()
+        })
+    }
+
+    /// Returns the first character without removing it
+    pub fn peek_char<A: Allocator>(&self, arena: &A) -> Option<char> {
+        self.chars(arena).next()
+    }
+
+    /// Copies as much of the text as fits into `dest`, whole characters only
+    ///
+    /// Returns the copied text, borrowed from `dest`.
+    pub fn read_str<'d, A: Allocator>(&self, arena: &A, dest: &'d mut [u8]) -> &'d str {
+        let copied = self.buf.copy_to_slice(arena, 0, dest);
+        let mut end = copied;
+        // Cut before a character whose first byte fit but not its last
+        if copied < self.buf.len() as usize {
+            while end > 0 && !self.buf.get(arena, end).is_some_and(is_char_start) {
+                end -= 1;
+            }
+        }
+        // Only whole characters of valid UTF-8 remain
+        core::str::from_utf8(&dest[..end]).unwrap_or_default()
+    }
+
+    /// Removes and returns the first character
+    #[cfg(feature = "bytebuffer-deque")]
+    pub fn pop_char<A: Allocator>(&mut self, arena: &mut A) -> Option<char> {
+        let c = self.peek_char(arena)?;
+        self.buf.remove_prefix(arena, c.len_utf8());
+        Some(c)
+    }
+
+    /// Removes all text and any pending bytes, freeing the chunks
+    pub fn clear<A: Allocator>(&mut self, arena: &mut A) {
+        self.buf.write(arena).clear();
+        self.pending_len = 0;
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl TextBuffer {
+    /// Number of characters in the text (global allocator version)
+    pub fn char_count_global(&self) -> usize {
+        crate::global::with_global_allocator(|alloc| self.char_count(alloc))
+    }
+
+    /// Append `s` using global allocator
+    pub fn push_str_global(&mut self, s: &str) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.push_str(alloc, s))
+    }
+
+    /// Append one character using global allocator
+    pub fn push_char_global(&mut self, c: char) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.push_char(alloc, c))
+    }
+
+    /// Validate and append raw bytes using global allocator
+    pub fn push_bytes_global(&mut self, data: &[u8]) -> Result<usize, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.push_bytes(alloc, data))
+    }
+
+    /// Return the first character (global allocator version)
+    pub fn peek_char_global(&self) -> Option<char> {
+        crate::global::with_global_allocator(|alloc| self.peek_char(alloc))
+    }
+
+    /// Copy whole characters into `dest` (global allocator version)
+    pub fn read_str_global<'d>(&self, dest: &'d mut [u8]) -> &'d str {
+        crate::global::with_global_allocator(|alloc| self.read_str(alloc, dest))
+    }
+
+    /// Remove and return the first character (global allocator version)
+    #[cfg(feature = "bytebuffer-deque")]
+    pub fn pop_char_global(&mut self) -> Option<char> {
+        crate::global::with_global_allocator(|alloc| self.pop_char(alloc))
+    }
+
+    /// Remove all text using global allocator
+    pub fn clear_global(&mut self) {
+        crate::global::with_global_allocator(|alloc| self.clear(alloc))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "tinyslab")]
+mod tests {
+    use super::*;
+    use crate::backend::tinyslab::TinySlabAllocator;
+
+    #[test]
+    fn test_chars_across_chunks() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut text = TextBuffer::new();
+
+        // 3-byte characters: 10 per 30-byte chunk would fit exactly, so shift
+        // by one to make every chunk boundary split a character
+        text.push_str(&mut arena, "x").unwrap();
+        for _ in 0..20 {
+            text.push_str(&mut arena, "€").unwrap();
+        }
+        text.push_char(&mut arena, '🦀').unwrap();
+        assert_eq!(text.len(), 1 + 60 + 4);
+        assert_eq!(text.char_count(&arena), 22);
+
+        let mut chars = text.chars(&arena);
+        assert_eq!(chars.next(), Some('x'));
+        assert_eq!(chars.by_ref().take(20).filter(|&c| c == '€').count(), 20);
+        assert_eq!(chars.next(), Some('🦀'));
+        assert_eq!(chars.next(), None);
+        drop(chars);
+
+        // Truncated to whole characters
+        let mut out = [0u8; 6];
+        assert_eq!(text.read_str(&arena, &mut out), "x€");
+        text.clear(&mut arena);
+        assert_eq!(arena.len(), 0);
+    }
+
+    #[test]
+    fn test_push_bytes_byte_by_byte() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut text = TextBuffer::new();
+        let input = "aé€🦀".as_bytes();
+
+        let mut appended = 0;
+        for b in input {
+            appended += text
+                .push_bytes(&mut arena, core::slice::from_ref(b))
+                .unwrap();
+        }
+        assert_eq!(appended, input.len());
+        assert_eq!(text.pending_len(), 0);
+        let mut out = [0u8; 16];
+        assert_eq!(text.read_str(&arena, &mut out), "aé€🦀");
+    }
+
+    #[test]
+    fn test_push_bytes_rejects_invalid() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut text = TextBuffer::new();
+
+        // Overlong encoding, surrogate, stray continuation byte
+        for (input, offset) in [
+            (&b"ab\xC0\x80"[..], 2),
+            (&b"\xED\xA0\x80"[..], 1),
+            (&b"abc\x80"[..], 3),
+        ] {
+            assert_eq!(
+                text.push_bytes(&mut arena, input),
+                Err(ByteBufferError::InvalidByte { offset })
+            );
+        }
+        assert!(text.is_empty());
+
+        // A pending sequence broken by the next push
+        assert_eq!(text.push_bytes(&mut arena, b"ok\xE2\x82"), Ok(2));
+        assert_eq!(text.pending_len(), 2);
+        assert_eq!(
+            text.push_str(&mut arena, "z"),
+            Err(ByteBufferError::InvalidByte { offset: 0 })
+        );
+        assert_eq!(text.pending_len(), 2);
+        assert_eq!(text.push_bytes(&mut arena, b"\xACz"), Ok(4));
+        assert_eq!(text.char_count(&arena), 4);
+    }
+
+    #[test]
+    fn test_push_bytes_invalid_offset_same_when_split() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+
+        // Truncated sequence, bad continuation, bad lead
+        for (input, offset) in [
+            (&b"\xE2\x82z"[..], 2),
+            (&b"a\xF0\x9F\x80\xFF"[..], 4),
+            (&b"\xE2\x82\xAC\x80"[..], 3),
+        ] {
+            let mut whole = TextBuffer::new();
+            assert_eq!(
+                whole.push_bytes(&mut arena, input),
+                Err(ByteBufferError::InvalidByte { offset })
+            );
+
+            // Same bytes split right before the bad one
+            let mut split = TextBuffer::new();
+            assert!(split.push_bytes(&mut arena, &input[..offset]).is_ok());
+            assert_eq!(
+                split.push_bytes(&mut arena, &input[offset..]),
+                Err(ByteBufferError::InvalidByte { offset: 0 })
+            );
+            split.clear(&mut arena);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_pop_char() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut text = TextBuffer::with_max_len(8);
+
+        text.push_str(&mut arena, "ñ€!").unwrap();
+        assert_eq!(text.push_str(&mut arena, "€€"), Err(ByteBufferError::Full));
+        assert_eq!(text.pop_char(&mut arena), Some('ñ'));
+        assert_eq!(text.peek_char(&arena), Some('€'));
+        assert_eq!(text.pop_char(&mut arena), Some('€'));
+        assert_eq!(text.pop_char(&mut arena), Some('!'));
+        assert_eq!(text.pop_char(&mut arena), None);
+    }
+}
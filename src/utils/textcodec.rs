@@ -0,0 +1,649 @@
+//! Hex and Base64 text encodings over ByteBuffer
+//!
+//! Encoders read binary data and append its text form to a `ByteBuffer`;
+//! decoders do the reverse. Both stream: the source is read a few dozen bytes
+//! at a time straight out of its chunks and the output is appended in batches,
+//! so neither side is ever gathered into one slice.
+//!
+//! - `ByteBuffer::encode_into(arena, encoding, range, out)` encodes a range of
+//!   a buffer
+//! - `ByteBuffer::decode_into(arena, encoding, range, out)` decodes a range of
+//!   a buffer holding text
+//! - `ByteBufferWrite::put_encoded(encoding, data)` and
+//!   `ByteBufferWrite::put_decoded(encoding, text)` work from plain slices
+//!
+//! Decoding is strict: whitespace, separators and characters outside the
+//! alphabet are rejected with `ByteBufferError::InvalidByte`, carrying the
+//! offset of the offending character in the source (buffer offset for
+//! `decode_into`, slice index for `put_decoded`). Text that ends part way
+//! through a byte or Base64 group is `ByteBufferError::Incomplete`. On any
+//! error the output buffer is left as it was.
+//!
+//! Base64 uses the standard alphabet (RFC 4648 section 4). Encoding always pads
+//! with `=`; decoding accepts padded and unpadded text.
+//!
+//! # Feature Flag
+//!
+//! This module is available when the `bytebuffer` feature is enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! use tinyalloc::prelude::*;
+//!
+//! let mut alloc = TinySlabAllocator::<1024, 32>::new();
+//! let mut data = ByteBuffer::new();
+//! data.write(&mut alloc).extend(b"\x01\xABhi").unwrap();
+//!
+//! let mut text = ByteBuffer::new();
+//! data.encode_into(&mut alloc, TextEncoding::Hex, .., &mut text).unwrap();
+//! assert_eq!(text.len(), 8); // "01ab6869"
+//!
+//! let mut b64 = ByteBuffer::new();
+//! data.encode_into(&mut alloc, TextEncoding::Base64, 2.., &mut b64).unwrap();
+//! let mut out = [0u8; 4];
+//! b64.copy_to_slice(&alloc, 0, &mut out);
+//! assert_eq!(&out, b"aGk=");
+//!
+//! let mut back = ByteBuffer::new();
+//! assert_eq!(text.decode_into(&mut alloc, TextEncoding::Hex, .., &mut back), Ok(4));
+//!
+//! let mut bad = ByteBuffer::new();
+//! bad.write(&mut alloc).extend(b"0g").unwrap();
+//! assert_eq!(
+//!     bad.decode_into(&mut alloc, TextEncoding::Hex, .., &mut back),
+//!     Err(ByteBufferError::InvalidByte { offset: 1 })
+//! );
+//! ```
+
+use core::ops::RangeBounds;
+
+use crate::Allocator;
+use crate::utils::bytebuffer::{
+    ByteBuffer, ByteBufferError, ByteBufferWrite, ChunkCursor, resolve_range,
+};
+
+/// Lowercase hex digits
+const HEX_LOWER: &[u8; 16] = b"0123456789abcdef";
+/// Uppercase hex digits
+const HEX_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+/// Standard Base64 alphabet
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+/// Base64 padding character
+const BASE64_PAD: u8 = b'=';
+
+/// Size of the staging arrays used while streaming
+const SCRATCH: usize = 48;
+
+/// Text encoding used by the encode and decode methods
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    /// Two lowercase hex digits per byte; decoding accepts either case
+    Hex,
+    /// Two uppercase hex digits per byte; decoding accepts either case
+    HexUpper,
+    /// Standard Base64 with `=` padding, four characters per three bytes
+    Base64,
+}
+
+impl TextEncoding {
+    /// Number of text bytes produced for `len` data bytes
+    pub const fn encoded_len(self, len: usize) -> usize {
+        match self {
+            TextEncoding::Hex | TextEncoding::HexUpper => len * 2,
+            TextEncoding::Base64 => len.div_ceil(3) * 4,
+        }
+    }
+}
+
+/// Value of a hex digit
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Value of a Base64 character, padding excluded
+fn base64_value(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+/// Incremental encoder appending text to a buffer
+struct Encoder {
+    encoding: TextEncoding,
+    /// Base64: bytes of the group not yet encoded
+    group: [u8; 3],
+    /// Base64: number of bytes in `group`
+    group_len: usize,
+}
+
+impl Encoder {
+    fn new(encoding: TextEncoding) -> Self {
+        Encoder {
+            encoding,
+            group: [0; 3],
+            group_len: 0,
+        }
+    }
+
+    /// Encodes `data`, appending complete text to `out`
+    fn push<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        out: &mut ByteBuffer,
+        data: &[u8],
+    ) -> Result<(), ByteBufferError> {
+        let mut text = [0u8; SCRATCH];
+        let mut n = 0;
+        for &b in data {
+            match self.encoding {
+                TextEncoding::Hex | TextEncoding::HexUpper => {
+                    let digits = if self.encoding == TextEncoding::Hex {
+                        HEX_LOWER
+                    } else {
+                        HEX_UPPER
+                    };
+                    text[n] = digits[usize::from(b >> 4)];
+                    text[n + 1] = digits[usize::from(b & 0x0F)];
+                    n += 2;
+                }
+                TextEncoding::Base64 => {
+                    self.group[self.group_len] = b;
+                    self.group_len += 1;
+                    if self.group_len == 3 {
+                        encode_group(&self.group, &mut text[n..n + 4]);
+                        n += 4;
+                        self.group_len = 0;
+                    }
+                }
+            }
+            if n > SCRATCH - 4 {
+                out.write(arena).put_slice(&text[..n])?;
+                n = 0;
+            }
+        }
+        out.write(arena).put_slice(&text[..n])
+    }
+
+    /// Appends the final, padded Base64 group, if any
+    fn finish<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+        out: &mut ByteBuffer,
+    ) -> Result<(), ByteBufferError> {
+        if self.group_len == 0 {
+            return Ok(());
+        }
+        let mut text = [BASE64_PAD; 4];
+        encode_group(&self.group[..self.group_len], &mut text);
+        out.write(arena).put_slice(&text)
+    }
+}
+
+/// Writes the `group.len() + 1` Base64 characters of a 1 to 3 byte group
+fn encode_group(group: &[u8], text: &mut [u8]) {
+    let b = [
+        group[0],
+        group.get(1).copied().unwrap_or(0),
+        group.get(2).copied().unwrap_or(0),
+    ];
+    let sextets = [
+        b[0] >> 2,
+        ((b[0] & 0x03) << 4) | (b[1] >> 4),
+        ((b[1] & 0x0F) << 2) | (b[2] >> 6),
+        b[2] & 0x3F,
+    ];
+    for (c, &s) in text.iter_mut().zip(&sextets).take(group.len() + 1) {
+        *c = BASE64_ALPHABET[usize::from(s)];
+    }
+}
+
+/// Incremental decoder fed one text character at a time
+struct Decoder {
+    encoding: TextEncoding,
+    /// Bits decoded but not yet output
+    acc: u16,
+    /// Number of valid bits in `acc`
+    bits: u32,
+    /// Characters consumed
+    count: usize,
+    /// Base64: padding characters seen
+    padding: usize,
+}
+
+impl Decoder {
+    fn new(encoding: TextEncoding) -> Self {
+        Decoder {
+            encoding,
+            acc: 0,
+            bits: 0,
+            count: 0,
+            padding: 0,
+        }
+    }
+
+    /// Consumes one character found at `offset`, returning a completed byte
+    fn feed(&mut self, c: u8, offset: usize) -> Result<Option<u8>, ByteBufferError> {
+        let invalid = ByteBufferError::InvalidByte { offset };
+        let (value, width) = match self.encoding {
+            TextEncoding::Hex | TextEncoding::HexUpper => (hex_value(c).ok_or(invalid)?, 4),
+            TextEncoding::Base64 => {
+                let position = self.count % 4;
+                if c == BASE64_PAD {
+                    // "xx==" or "xxx=", nothing after
+                    let valid = match self.padding {
+                        0 => position >= 2,
+                        1 => position == 3,
+                        _ => false,
+                    };
+                    if !valid {
+                        return Err(invalid);
+                    }
+                    self.padding += 1;
+                    self.count += 1;
+                    return Ok(None);
+                }
+                if self.padding > 0 {
+                    return Err(invalid);
+                }
+                (base64_value(c).ok_or(invalid)?, 6)
+            }
+        };
+        self.count += 1;
+        self.acc = (self.acc << width) | u16::from(value);
+        self.bits += width;
+        if self.bits < 8 {
+            return Ok(None);
+        }
+        self.bits -= 8;
+        let byte = (self.acc >> self.bits) as u8;
+        self.acc &= (1 << self.bits) - 1;
+        Ok(Some(byte))
+    }
+
+    /// Checks that the text did not stop part way through a byte or group
+    fn finish(&self) -> Result<(), ByteBufferError> {
+        let complete = match self.encoding {
+            TextEncoding::Hex | TextEncoding::HexUpper => self.bits == 0,
+            // A lone character holds only 6 bits; padding must fill its group
+            TextEncoding::Base64 => {
+                self.count % 4 != 1 && (self.padding == 0 || self.count % 4 == 0)
+            }
+        };
+        if complete {
+            Ok(())
+        } else {
+            Err(ByteBufferError::Incomplete)
+        }
+    }
+}
+
+/// Decodes `text`, whose first character is at `offset`, appending to `out`
+///
+/// Returns the number of bytes appended.
+fn decode_chunk<A: Allocator>(
+    decoder: &mut Decoder,
+    arena: &mut A,
+    out: &mut ByteBuffer,
+    text: &[u8],
+    offset: usize,
+) -> Result<usize, ByteBufferError> {
+    let mut data = [0u8; SCRATCH];
+    let mut n = 0;
+    for (i, &c) in text.iter().enumerate() {
+        if let Some(b) = decoder.feed(c, offset + i)? {
+            data[n] = b;
+            n += 1;
+        }
+    }
+    out.write(arena).put_slice(&data[..n])?;
+    Ok(n)
+}
+
+impl<A: Allocator> ByteBufferWrite<'_, A> {
+    /// Appends the text encoding of `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns `ByteBufferError::Full` or `ByteBufferError::AllocationFailed`
+    /// if the text does not fit; nothing is appended.
+    pub fn put_encoded(
+        &mut self,
+        encoding: TextEncoding,
+        data: &[u8],
+    ) -> Result<(), ByteBufferError> {
+        let start = self.buf.len() as usize;
+        let mut encoder = Encoder::new(encoding);
+        let result = encoder
+            .push(self.arena, self.buf, data)
+            .and_then(|_| encoder.finish(self.arena, self.buf));
+        if result.is_err() {
+            self.buf.truncate(self.arena, start);
+        }
+        result
+    }
+
+    /// Decodes `text` and appends the bytes
+    ///
+    /// Returns the number of bytes appended.
+    ///
+    /// # Errors
+    ///
+    /// `ByteBufferError::InvalidByte` with the index of the bad character,
+    /// `ByteBufferError::Incomplete` if `text` is truncated, or `Full` /
+    /// `AllocationFailed`. Nothing is appended on error.
+    pub fn put_decoded(
+        &mut self,
+        encoding: TextEncoding,
+        text: &[u8],
+    ) -> Result<usize, ByteBufferError> {
+        let start = self.buf.len() as usize;
+        let mut decoder = Decoder::new(encoding);
+        let mut total = 0;
+        let result = text
+            .chunks(SCRATCH)
+            .enumerate()
+            .try_for_each(|(i, chunk)| {
+                total += decode_chunk(&mut decoder, self.arena, self.buf, chunk, i * SCRATCH)?;
+                Ok(())
+            })
+            .and_then(|_| decoder.finish());
+        if let Err(e) = result {
+            self.buf.truncate(self.arena, start);
+            return Err(e);
+        }
+        Ok(total)
+    }
+}
+
+impl ByteBuffer {
+    /// Appends the text encoding of `range` of this buffer to `out`
+    ///
+    /// This buffer is not modified.
+    ///
+    /// # Errors
+    ///
+    /// `ByteBufferError::OutOfRange` for a bad range, otherwise as
+    /// `ByteBufferWrite::put_encoded`. `out` is unchanged on error.
+    pub fn encode_into<A: Allocator, R: RangeBounds<usize>>(
+        &self,
+        arena: &mut A,
+        encoding: TextEncoding,
+        range: R,
+        out: &mut ByteBuffer,
+    ) -> Result<(), ByteBufferError> {
+        let (start, end) = resolve_range(range, self.len() as usize)?;
+        let out_start = out.len() as usize;
+        let mut encoder = Encoder::new(encoding);
+        let mut cursor = ChunkCursor::seek(arena, self, start);
+        let mut left = end - start;
+        let mut result = Ok(());
+        let mut scratch = [0u8; SCRATCH];
+        while left > 0 && result.is_ok() {
+            let n = cursor.read(arena, &mut scratch[..left.min(SCRATCH)]);
+            if n == 0 {
+                result = Err(ByteBufferError::Uninitialized);
+                break;
+            }
+            result = encoder.push(arena, out, &scratch[..n]);
+            left -= n;
+        }
+        if let Err(e) = result.and_then(|_| encoder.finish(arena, out)) {
+            out.truncate(arena, out_start);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Decodes the text in `range` of this buffer, appending the bytes to `out`
+    ///
+    /// Returns the number of bytes appended. This buffer is not modified.
+    ///
+    /// # Errors
+    ///
+    /// - `ByteBufferError::InvalidByte` - a character is not valid; `offset` is
+    ///   its position in this buffer
+    /// - `ByteBufferError::Incomplete` - the text stops part way through a byte
+    ///   or Base64 group
+    /// - `ByteBufferError::OutOfRange`, `Full` or `AllocationFailed`
+    ///
+    /// `out` is unchanged on error.
+    pub fn decode_into<A: Allocator, R: RangeBounds<usize>>(
+        &self,
+        arena: &mut A,
+        encoding: TextEncoding,
+        range: R,
+        out: &mut ByteBuffer,
+    ) -> Result<usize, ByteBufferError> {
+        let (start, end) = resolve_range(range, self.len() as usize)?;
+        let out_start = out.len() as usize;
+        let mut decoder = Decoder::new(encoding);
+        let mut cursor = ChunkCursor::seek(arena, self, start);
+        let mut offset = start;
+        let mut total = 0;
+        let mut result = Ok(());
+        let mut scratch = [0u8; SCRATCH];
+        while offset < end && result.is_ok() {
+            let n = cursor.read(arena, &mut scratch[..(end - offset).min(SCRATCH)]);
+            if n == 0 {
+                result = Err(ByteBufferError::Uninitialized);
+                break;
+            }
+            result = decode_chunk(&mut decoder, arena, out, &scratch[..n], offset)
+                .map(|decoded| total += decoded);
+            offset += n;
+        }
+        if let Err(e) = result.and_then(|_| decoder.finish()) {
+            out.truncate(arena, out_start);
+            return Err(e);
+        }
+        Ok(total)
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl ByteBuffer {
+    /// Append the text encoding of `data` using global allocator
+    pub fn put_encoded(
+        &mut self,
+        encoding: TextEncoding,
+        data: &[u8],
+    ) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.write(alloc).put_encoded(encoding, data))
+    }
+
+    /// Decode `text` and append the bytes using global allocator
+    pub fn put_decoded(
+        &mut self,
+        encoding: TextEncoding,
+        text: &[u8],
+    ) -> Result<usize, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.write(alloc).put_decoded(encoding, text))
+    }
+
+    /// Append the text encoding of `range` to `out` (global allocator version)
+    pub fn encode_into_global<R: RangeBounds<usize>>(
+        &self,
+        encoding: TextEncoding,
+        range: R,
+        out: &mut ByteBuffer,
+    ) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.encode_into(alloc, encoding, range, out))
+    }
+
+    /// Decode the text in `range`, appending to `out` (global allocator version)
+    pub fn decode_into_global<R: RangeBounds<usize>>(
+        &self,
+        encoding: TextEncoding,
+        range: R,
+        out: &mut ByteBuffer,
+    ) -> Result<usize, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.decode_into(alloc, encoding, range, out))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "tinyslab")]
+mod tests {
+    use super::*;
+    use crate::backend::tinyslab::TinySlabAllocator;
+
+    fn contents<A: Allocator>(arena: &A, buf: &ByteBuffer) -> ([u8; 512], usize) {
+        let mut out = [0u8; 512];
+        let n = buf.copy_to_slice(arena, 0, &mut out);
+        (out, n)
+    }
+
+    #[test]
+    fn test_base64_vectors() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        // RFC 4648 section 10
+        let vectors: [(&[u8], &[u8]); 7] = [
+            (b"", b""),
+            (b"f", b"Zg=="),
+            (b"fo", b"Zm8="),
+            (b"foo", b"Zm9v"),
+            (b"foob", b"Zm9vYg=="),
+            (b"fooba", b"Zm9vYmE="),
+            (b"foobar", b"Zm9vYmFy"),
+        ];
+        for (data, text) in vectors {
+            let mut buf = ByteBuffer::new();
+            buf.write(&mut arena)
+                .put_encoded(TextEncoding::Base64, data)
+                .unwrap();
+            let (out, n) = contents(&arena, &buf);
+            assert_eq!(&out[..n], text);
+            assert_eq!(n, TextEncoding::Base64.encoded_len(data.len()));
+            buf.write(&mut arena).clear();
+
+            assert_eq!(
+                buf.write(&mut arena)
+                    .put_decoded(TextEncoding::Base64, text),
+                Ok(data.len())
+            );
+            let (out, n) = contents(&arena, &buf);
+            assert_eq!(&out[..n], data);
+            buf.write(&mut arena).clear();
+        }
+
+        // Unpadded input is accepted
+        let mut buf = ByteBuffer::new();
+        assert_eq!(
+            buf.write(&mut arena)
+                .put_decoded(TextEncoding::Base64, b"Zm9vYg"),
+            Ok(4)
+        );
+        buf.write(&mut arena).clear();
+    }
+
+    #[test]
+    fn test_roundtrip_across_chunks() {
+        let mut arena = TinySlabAllocator::<4096, 128>::new();
+        let data: [u8; 256] = core::array::from_fn(|i| i as u8);
+        let mut src = ByteBuffer::new();
+        src.write(&mut arena).extend(&data).unwrap();
+
+        for encoding in [
+            TextEncoding::Hex,
+            TextEncoding::HexUpper,
+            TextEncoding::Base64,
+        ] {
+            let mut text = ByteBuffer::new();
+            src.encode_into(&mut arena, encoding, 10..250, &mut text)
+                .unwrap();
+            assert_eq!(text.len() as usize, encoding.encoded_len(240));
+
+            let mut back = ByteBuffer::new();
+            assert_eq!(
+                text.decode_into(&mut arena, encoding, .., &mut back),
+                Ok(240)
+            );
+            let (out, n) = contents(&arena, &back);
+            assert_eq!(&out[..n], &data[10..250]);
+            text.write(&mut arena).clear();
+            back.write(&mut arena).clear();
+        }
+
+        let mut text = ByteBuffer::new();
+        src.encode_into(&mut arena, TextEncoding::HexUpper, 250.., &mut text)
+            .unwrap();
+        let (out, n) = contents(&arena, &text);
+        assert_eq!(&out[..n], b"FAFBFCFDFEFF");
+    }
+
+    #[test]
+    fn test_invalid_characters_report_offset() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut text = ByteBuffer::new();
+        let mut out = ByteBuffer::new();
+        out.write(&mut arena).extend(b"kept").unwrap();
+
+        // Offset is in the source buffer, past a chunk boundary
+        text.write(&mut arena).extend(&[b'a'; 40]).unwrap();
+        text.write(&mut arena).extend(b" 00").unwrap();
+        assert_eq!(
+            text.decode_into(&mut arena, TextEncoding::Hex, .., &mut out),
+            Err(ByteBufferError::InvalidByte { offset: 40 })
+        );
+        assert_eq!(
+            text.decode_into(&mut arena, TextEncoding::Hex, 35..40, &mut out),
+            Err(ByteBufferError::Incomplete)
+        );
+        assert_eq!(out.len(), 4);
+
+        let mut buf = ByteBuffer::new();
+        let mut w = buf.write(&mut arena);
+        assert_eq!(
+            w.put_decoded(TextEncoding::Base64, b"Zm9v=g=="),
+            Err(ByteBufferError::InvalidByte { offset: 4 })
+        );
+        assert_eq!(
+            w.put_decoded(TextEncoding::Base64, b"Zg==Zg=="),
+            Err(ByteBufferError::InvalidByte { offset: 4 })
+        );
+        assert_eq!(
+            w.put_decoded(TextEncoding::Base64, b"Zm9vY"),
+            Err(ByteBufferError::Incomplete)
+        );
+        assert_eq!(
+            w.put_decoded(TextEncoding::Base64, b"Zg="),
+            Err(ByteBufferError::Incomplete)
+        );
+        assert_eq!(
+            w.put_decoded(TextEncoding::Base64, b"Zm-v"),
+            Err(ByteBufferError::InvalidByte { offset: 2 })
+        );
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_encode_into_is_atomic() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut src = ByteBuffer::new();
+        src.write(&mut arena).extend(&[0x5A; 30]).unwrap();
+
+        let mut out = ByteBuffer::with_max_len(50);
+        out.write(&mut arena).extend(b"log: ").unwrap();
+        assert_eq!(
+            src.encode_into(&mut arena, TextEncoding::Hex, .., &mut out),
+            Err(ByteBufferError::Full)
+        );
+        assert_eq!(out.len(), 5);
+        assert_eq!(
+            src.encode_into(&mut arena, TextEncoding::Hex, 20..40, &mut out),
+            Err(ByteBufferError::OutOfRange)
+        );
+    }
+}
@@ -0,0 +1,275 @@
+//! LEB128 varints for ByteBuffer
+//!
+//! Unsigned values use the protobuf/LEB128 layout: seven bits per byte, least
+//! significant group first, high bit set on every byte but the last. Signed
+//! values are zigzag-mapped first (`0, -1, 1, -2, ...` become `0, 1, 2, 3, ...`)
+//! so small negative numbers stay short.
+//!
+//! - `put_varint(v)` / `put_varint_zigzag(v)` on [`ByteBufferWrite`]
+//! - `peek_varint(arena)` / `peek_varint_zigzag(arena)` on [`ByteBuffer`]
+//! - `get_varint(arena)` / `get_varint_zigzag(arena)` consume (`bytebuffer-deque`)
+//!
+//! Readers tell two failures apart, and consume nothing on either:
+//!
+//! - `ByteBufferError::Incomplete` - the buffer ends inside the varint; wait for
+//!   more bytes and retry
+//! - `ByteBufferError::Malformed` - the bytes can never form a valid `u64`
+//!   (more than 10 bytes, or bits past the 64th set); resynchronise the stream
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "bytebuffer-deque")]
+//! # {
+//! use tinyalloc::prelude::*;
+//!
+//! let mut alloc = TinySlabAllocator::<1024, 32>::new();
+//! let mut buf = ByteBuffer::new();
+//!
+//! buf.write(&mut alloc).put_varint(300).unwrap();
+//! buf.write(&mut alloc).put_varint_zigzag(-2).unwrap();
+//! assert_eq!(buf.len(), 3); // 0xAC 0x02, then 0x03
+//!
+//! assert_eq!(buf.get_varint(&mut alloc), Ok(300));
+//! assert_eq!(buf.get_varint_zigzag(&mut alloc), Ok(-2));
+//! assert_eq!(buf.get_varint(&mut alloc), Err(ByteBufferError::Incomplete));
+//! # }
+//! ```
+
+use crate::Allocator;
+use crate::utils::bytebuffer::{ByteBuffer, ByteBufferError, ByteBufferWrite};
+
+/// Longest encoding of a `u64`
+const MAX_VARINT_LEN: usize = 10;
+
+/// Encodes `value` into `out`, returning the number of bytes used
+fn encode(mut value: u64, out: &mut [u8; MAX_VARINT_LEN]) -> usize {
+    let mut n = 0;
+    while value >= 0x80 {
+        out[n] = (value as u8) | 0x80;
+        value >>= 7;
+        n += 1;
+    }
+    out[n] = value as u8;
+    n + 1
+}
+
+/// Number of bytes `value` takes when encoded
+#[cfg(feature = "bytebuffer-stream")]
+pub(crate) fn encoded_len(value: u64) -> usize {
+    let bits = 64 - value.leading_zeros() as usize;
+    bits.div_ceil(7).max(1)
+}
+
+/// Decodes a varint from the start of `bytes`, returning the value and its length
+fn decode(bytes: impl Iterator<Item = u8>) -> Result<(u64, usize), ByteBufferError> {
+    let mut value = 0u64;
+    let mut seen = 0;
+    for (i, b) in bytes.take(MAX_VARINT_LEN).enumerate() {
+        // The 10th byte holds only the top bit of a u64
+        if i == MAX_VARINT_LEN - 1 && b > 1 {
+            return Err(ByteBufferError::Malformed);
+        }
+        value |= u64::from(b & 0x7F) << (7 * i);
+        if b & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+        seen = i + 1;
+    }
+    if seen == MAX_VARINT_LEN {
+        Err(ByteBufferError::Malformed)
+    } else {
+        Err(ByteBufferError::Incomplete)
+    }
+}
+
+/// Maps a signed value onto an unsigned one, small magnitudes first
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Inverse of [`zigzag`]
+fn unzigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+impl<A: Allocator> ByteBufferWrite<'_, A> {
+    /// Appends an unsigned LEB128 varint (1 to 10 bytes)
+    ///
+    /// # Errors
+    ///
+    /// Returns `ByteBufferError::Full` or `ByteBufferError::AllocationFailed`
+    /// without writing anything if the encoding does not fit.
+    pub fn put_varint(&mut self, value: u64) -> Result<(), ByteBufferError> {
+        let mut out = [0u8; MAX_VARINT_LEN];
+        let n = encode(value, &mut out);
+        self.put_slice(&out[..n])
+    }
+
+    /// Appends a zigzag-encoded signed varint
+    ///
+    /// # Errors
+    ///
+    /// Same as [`put_varint`](Self::put_varint).
+    pub fn put_varint_zigzag(&mut self, value: i64) -> Result<(), ByteBufferError> {
+        self.put_varint(zigzag(value))
+    }
+}
+
+impl ByteBuffer {
+    /// Reads an unsigned varint from the front without consuming it
+    ///
+    /// Returns `ByteBufferError::Incomplete` if the buffer ends inside the
+    /// varint and `ByteBufferError::Malformed` if it can never be valid.
+    pub fn peek_varint<A: Allocator>(&self, arena: &A) -> Result<u64, ByteBufferError> {
+        decode(self.read(arena).bytes()).map(|(value, _)| value)
+    }
+
+    /// Reads a zigzag-encoded signed varint from the front without consuming it
+    pub fn peek_varint_zigzag<A: Allocator>(&self, arena: &A) -> Result<i64, ByteBufferError> {
+        self.peek_varint(arena).map(unzigzag)
+    }
+
+    /// Reads and consumes an unsigned varint from the front
+    ///
+    /// On error nothing is consumed; see [`peek_varint`](Self::peek_varint).
+    #[cfg(feature = "bytebuffer-deque")]
+    pub fn get_varint<A: Allocator>(&mut self, arena: &mut A) -> Result<u64, ByteBufferError> {
+        let (value, len) = decode(self.read(arena).bytes())?;
+        self.remove_prefix(arena, len);
+        Ok(value)
+    }
+
+    /// Reads and consumes a zigzag-encoded signed varint from the front
+    #[cfg(feature = "bytebuffer-deque")]
+    pub fn get_varint_zigzag<A: Allocator>(
+        &mut self,
+        arena: &mut A,
+    ) -> Result<i64, ByteBufferError> {
+        self.get_varint(arena).map(unzigzag)
+    }
+}
+
+#[cfg(feature = "global-alloc")]
+impl ByteBuffer {
+    /// Append an unsigned varint using the global allocator
+    pub fn put_varint(&mut self, value: u64) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.write(alloc).put_varint(value))
+    }
+
+    /// Append a zigzag-encoded signed varint using the global allocator
+    pub fn put_varint_zigzag(&mut self, value: i64) -> Result<(), ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.write(alloc).put_varint_zigzag(value))
+    }
+
+    /// Read an unsigned varint without consuming it using global allocator
+    pub fn peek_varint_global(&self) -> Result<u64, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.peek_varint(alloc))
+    }
+
+    /// Read a signed zigzag varint without consuming it using global allocator
+    pub fn peek_varint_zigzag_global(&self) -> Result<i64, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.peek_varint_zigzag(alloc))
+    }
+
+    /// Read and consume an unsigned varint using global allocator
+    #[cfg(feature = "bytebuffer-deque")]
+    pub fn get_varint_global(&mut self) -> Result<u64, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.get_varint(alloc))
+    }
+
+    /// Read and consume a signed zigzag varint using global allocator
+    #[cfg(feature = "bytebuffer-deque")]
+    pub fn get_varint_zigzag_global(&mut self) -> Result<i64, ByteBufferError> {
+        crate::global::with_global_allocator(|alloc| self.get_varint_zigzag(alloc))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "tinyslab")]
+mod tests {
+    use super::*;
+    use crate::backend::tinyslab::TinySlabAllocator;
+
+    #[test]
+    fn test_encoding_layout() {
+        let mut out = [0u8; MAX_VARINT_LEN];
+        let n = encode(0, &mut out);
+        assert_eq!(&out[..n], &[0x00]);
+        let n = encode(127, &mut out);
+        assert_eq!(&out[..n], &[0x7F]);
+        let n = encode(128, &mut out);
+        assert_eq!(&out[..n], &[0x80, 0x01]);
+        let n = encode(300, &mut out);
+        assert_eq!(&out[..n], &[0xAC, 0x02]);
+        assert_eq!(encode(u64::MAX, &mut out), 10);
+        assert_eq!(out[9], 0x01);
+        #[cfg(feature = "bytebuffer-stream")]
+        for v in [0, 127, 128, 300, 1 << 35, u64::MAX] {
+            assert_eq!(encoded_len(v), encode(v, &mut out));
+        }
+
+        assert_eq!(zigzag(0), 0);
+        assert_eq!(zigzag(-1), 1);
+        assert_eq!(zigzag(1), 2);
+        assert_eq!(zigzag(i64::MIN), u64::MAX);
+        for v in [0, -1, 1, 63, -64, i64::MAX, i64::MIN] {
+            assert_eq!(unzigzag(zigzag(v)), v);
+        }
+    }
+
+    #[test]
+    fn test_peek_incomplete_and_malformed() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+
+        buf.write(&mut arena).extend(&[0xAC]).unwrap();
+        assert_eq!(buf.peek_varint(&arena), Err(ByteBufferError::Incomplete));
+        buf.write(&mut arena).extend(&[0x02]).unwrap();
+        assert_eq!(buf.peek_varint(&arena), Ok(300));
+        assert_eq!(buf.len(), 2);
+
+        // Ten continuation bytes can never finish
+        let mut buf = ByteBuffer::new();
+        buf.write(&mut arena).extend(&[0xFF; 10]).unwrap();
+        assert_eq!(buf.peek_varint(&arena), Err(ByteBufferError::Malformed));
+
+        // The tenth byte may only carry the top bit
+        let mut buf = ByteBuffer::new();
+        buf.write(&mut arena).extend(&[0xFF; 9]).unwrap();
+        assert_eq!(buf.peek_varint(&arena), Err(ByteBufferError::Incomplete));
+        buf.write(&mut arena).append(0x02).unwrap();
+        assert_eq!(buf.peek_varint(&arena), Err(ByteBufferError::Malformed));
+    }
+
+    #[test]
+    #[cfg(feature = "bytebuffer-deque")]
+    fn test_roundtrip_across_chunks() {
+        let mut arena = TinySlabAllocator::<2048, 64>::new();
+        let mut buf = ByteBuffer::new();
+        let values = [0, 1, 127, 128, 16_383, 16_384, u32::MAX as u64, u64::MAX];
+
+        // Filler shifts the varints so several straddle chunk boundaries
+        buf.write(&mut arena).extend(&[0; 25]).unwrap();
+        for &v in &values {
+            buf.write(&mut arena).put_varint(v).unwrap();
+            buf.write(&mut arena)
+                .put_varint_zigzag(-(v as i64))
+                .unwrap();
+        }
+        buf.remove_prefix(&mut arena, 25);
+
+        for &v in &values {
+            assert_eq!(buf.get_varint(&mut arena), Ok(v));
+            assert_eq!(buf.get_varint_zigzag(&mut arena), Ok(-(v as i64)));
+        }
+        assert!(buf.is_empty());
+
+        // A failed read consumes nothing
+        buf.write(&mut arena).extend(&[0x80, 0x80]).unwrap();
+        assert_eq!(buf.get_varint(&mut arena), Err(ByteBufferError::Incomplete));
+        assert_eq!(buf.len(), 2);
+        buf.write(&mut arena).append(0x01).unwrap();
+        assert_eq!(buf.get_varint(&mut arena), Ok(1 << 14));
+    }
+}